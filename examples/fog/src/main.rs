@@ -51,22 +51,10 @@ pub async fn run() {
     let mut fog_enabled = true;
 
     // main loop
-    let mut color_texture = Texture2D::new_empty::<[f16; 4]>(
+    let mut scene_target = TextureRenderTarget::<[f16; 4], f32>::new(
         &context,
         camera.viewport().width,
         camera.viewport().height,
-        Interpolation::Nearest,
-        Interpolation::Nearest,
-        None,
-        Wrapping::ClampToEdge,
-        Wrapping::ClampToEdge,
-    );
-    let mut depth_texture = DepthTexture2D::new::<f32>(
-        &context,
-        camera.viewport().width,
-        camera.viewport().height,
-        Wrapping::ClampToEdge,
-        Wrapping::ClampToEdge,
     );
     window.render_loop(move |mut frame_input| {
         let mut change = frame_input.first_frame;
@@ -84,35 +72,13 @@ pub async fn run() {
         }
 
         if change {
-            // Draw the scene to a render target if a change has occured
-            if camera.viewport().width != color_texture.width()
-                || camera.viewport().height != color_texture.height()
-            {
-                color_texture = Texture2D::new_empty::<[f16; 4]>(
-                    &context,
-                    camera.viewport().width,
-                    camera.viewport().height,
-                    Interpolation::Nearest,
-                    Interpolation::Nearest,
-                    None,
-                    Wrapping::ClampToEdge,
-                    Wrapping::ClampToEdge,
-                );
-                depth_texture = DepthTexture2D::new::<f32>(
-                    &context,
-                    camera.viewport().width,
-                    camera.viewport().height,
-                    Wrapping::ClampToEdge,
-                    Wrapping::ClampToEdge,
-                );
-            }
+            // Draw the scene to a color and depth texture if a change has occured
+            scene_target.resize(camera.viewport().width, camera.viewport().height);
             camera.disable_tone_and_color_mapping();
-            RenderTarget::new(
-                color_texture.as_color_target(None),
-                depth_texture.as_depth_target(),
-            )
-            .clear(ClearState::default())
-            .render(&camera, &monkey, &[&ambient, &directional]);
+            scene_target
+                .as_render_target()
+                .clear(ClearState::default())
+                .render(&camera, &monkey, &[&ambient, &directional]);
         }
 
         change |= fog_enabled; // Always render if fog is enabled since it contain animation.
@@ -125,16 +91,16 @@ pub async fn run() {
                     &fog_effect,
                     &camera,
                     &[],
-                    Some(ColorTexture::Single(&color_texture)),
-                    Some(DepthTexture::Single(&depth_texture)),
+                    Some(scene_target.color_texture()),
+                    Some(scene_target.depth_texture()),
                 );
             } else {
                 frame_input.screen().apply_screen_effect(
                     &ScreenEffect::default(),
                     &camera,
                     &[],
-                    Some(ColorTexture::Single(&color_texture)),
-                    Some(DepthTexture::Single(&depth_texture)),
+                    Some(scene_target.color_texture()),
+                    Some(scene_target.depth_texture()),
                 );
             }
         }
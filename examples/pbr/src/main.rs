@@ -143,6 +143,9 @@ pub async fn run() {
                         NormalDistributionFunction::TrowbridgeReitzGGX,
                         GeometryFunction::SmithSchlickGGX,
                     ),
+                    exposure: model.material.exposure,
+                    gamma: model.material.gamma,
+                    vertex_color_mode: model.material.vertex_color_mode,
                 };
                 model.render_with_material(&material, &camera, &[&light]);
                 gui.render()
@@ -0,0 +1,83 @@
+use three_d::*;
+
+pub fn main() {
+    let window = Window::new(WindowSettings {
+        title: "Viewports!".to_string(),
+        max_size: Some((1280, 720)),
+        ..Default::default()
+    })
+    .unwrap();
+    let context = window.gl();
+
+    let mut perspective_camera = Camera::new_perspective(
+        window.viewport(),
+        vec3(4.0, 3.0, 5.0),
+        vec3(0.0, 0.0, 0.0),
+        vec3(0.0, 1.0, 0.0),
+        degrees(45.0),
+        0.1,
+        100.0,
+    );
+    let mut orthographic_camera = Camera::new_orthographic(
+        window.viewport(),
+        vec3(4.0, 3.0, 5.0),
+        vec3(0.0, 0.0, 0.0),
+        vec3(0.0, 1.0, 0.0),
+        5.0,
+        0.1,
+        100.0,
+    );
+
+    let mut cube = Gm::new(
+        Mesh::new(&context, &CpuMesh::cube()),
+        PhysicalMaterial::new_opaque(
+            &context,
+            &CpuMaterial {
+                albedo: Srgba::new(200, 80, 30, 255),
+                ..Default::default()
+            },
+        ),
+    );
+    cube.set_animation(|time| Mat4::from_angle_y(radians(time * 0.001)));
+
+    let light0 = DirectionalLight::new(&context, 1.0, Srgba::WHITE, &vec3(-1.0, -1.0, -1.0));
+    let light1 = AmbientLight::new(&context, 0.2, Srgba::WHITE);
+
+    window.render_loop(move |frame_input| {
+        // Split the window in a left and a right half, one for each camera
+        let width = frame_input.viewport.width / 2;
+        let left = Viewport {
+            x: frame_input.viewport.x,
+            y: frame_input.viewport.y,
+            width,
+            height: frame_input.viewport.height,
+        };
+        let right = Viewport {
+            x: frame_input.viewport.x + width as i32,
+            y: frame_input.viewport.y,
+            width: frame_input.viewport.width - width,
+            height: frame_input.viewport.height,
+        };
+        perspective_camera.set_viewport(left);
+        orthographic_camera.set_viewport(right);
+
+        cube.animate(frame_input.accumulated_time as f32);
+
+        // Each camera only clears and renders into its own viewport
+        let screen = frame_input.screen();
+        screen
+            .clear_partially(
+                left.into(),
+                ClearState::color_and_depth(0.8, 0.8, 0.8, 1.0, 1.0),
+            )
+            .render_to_viewport(&perspective_camera, &cube, &[&light0, &light1]);
+        screen
+            .clear_partially(
+                right.into(),
+                ClearState::color_and_depth(0.6, 0.6, 0.7, 1.0, 1.0),
+            )
+            .render_to_viewport(&orthographic_camera, &cube, &[&light0, &light1]);
+
+        FrameOutput::default()
+    });
+}
@@ -32,6 +32,10 @@ mod scissor_box;
 #[doc(inline)]
 pub use scissor_box::*;
 
+mod color;
+#[doc(inline)]
+pub use color::*;
+
 pub mod prelude {
 
     //!
@@ -59,6 +63,10 @@ pub enum CoreError {
     ShaderCompilation(String, String, String),
     #[error("failed to link shader program: {0}")]
     ShaderLink(String),
+    #[error("the compressed texture format {0:?} is not supported")]
+    UnsupportedCompressedFormat(CompressedFormat),
+    #[error("{0} is not a valid hexadecimal color, expected the form #RGB, #RRGGBB or #RRGGBBAA")]
+    InvalidHexColor(String),
 }
 
 pub(crate) fn full_screen_draw(
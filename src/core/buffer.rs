@@ -42,6 +42,29 @@ impl BufferDataType for Quat {}
 
 impl<T: BufferDataType + ?Sized> BufferDataType for &T {}
 
+///
+/// A hint to the graphics driver about how often the data in a buffer is updated, which is used to decide where to store the data.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferUsage {
+    /// The data is uploaded once and used many times.
+    Static,
+    /// The data is updated repeatedly and used many times, for example a few times each frame.
+    Dynamic,
+    /// The data is updated every time it is used, for example data that changes each frame.
+    Stream,
+}
+
+impl BufferUsage {
+    fn to_const(self) -> u32 {
+        match self {
+            Self::Static => crate::context::STATIC_DRAW,
+            Self::Dynamic => crate::context::DYNAMIC_DRAW,
+            Self::Stream => crate::context::STREAM_DRAW,
+        }
+    }
+}
+
 struct Buffer {
     context: Context,
     id: crate::context::Buffer,
@@ -74,15 +97,24 @@ impl Buffer {
     pub fn fill<T: BufferDataType>(&mut self, data: &[T]) {
         self.bind();
         unsafe {
-            self.context.buffer_data_u8_slice(
-                crate::context::ARRAY_BUFFER,
-                to_byte_slice(data),
-                if self.attribute_count > 0 {
-                    crate::context::DYNAMIC_DRAW
-                } else {
-                    crate::context::STATIC_DRAW
-                },
-            );
+            if self.attribute_count > 0 {
+                // The buffer has been filled before, so it is probably updated often.
+                // Orphan the old storage before uploading, to avoid waiting for draw calls that are still reading from it.
+                let bytes = to_byte_slice(data);
+                self.context.buffer_data_size(
+                    crate::context::ARRAY_BUFFER,
+                    bytes.len() as i32,
+                    crate::context::DYNAMIC_DRAW,
+                );
+                self.context
+                    .buffer_sub_data_u8_slice(crate::context::ARRAY_BUFFER, 0, bytes);
+            } else {
+                self.context.buffer_data_u8_slice(
+                    crate::context::ARRAY_BUFFER,
+                    to_byte_slice(data),
+                    crate::context::STATIC_DRAW,
+                );
+            }
             self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
         }
         self.attribute_count = data.len() as u32;
@@ -91,6 +123,32 @@ impl Buffer {
         self.normalized = T::normalized();
     }
 
+    pub fn update<T: BufferDataType>(&mut self, offset: u32, data: &[T]) {
+        if self.attribute_count == 0 {
+            panic!("cannot update a buffer which has not been filled");
+        }
+        if T::data_type() != self.data_type || T::size() != self.data_size {
+            panic!("the data type used to update a buffer must be the same as the data type used to fill it");
+        }
+        if offset as usize + data.len() > self.attribute_count as usize {
+            panic!(
+                "cannot update {} attributes at offset {} in a buffer with {} attributes",
+                data.len(),
+                offset,
+                self.attribute_count
+            );
+        }
+        self.bind();
+        unsafe {
+            self.context.buffer_sub_data_u8_slice(
+                crate::context::ARRAY_BUFFER,
+                (offset as usize * std::mem::size_of::<T>()) as i32,
+                to_byte_slice(data),
+            );
+            self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
+        }
+    }
+
     pub fn attribute_count(&self) -> u32 {
         self.attribute_count
     }
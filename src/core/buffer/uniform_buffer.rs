@@ -1,5 +1,72 @@
 use crate::core::*;
 
+///
+/// The type of a variable in a [UniformBuffer] created with [UniformBuffer::new_std140].
+///
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UniformBufferElement {
+    Float,
+    Vec2,
+    Vec3,
+    Vec4,
+    Mat3,
+    Mat4,
+    /// An array with the given number of elements of the given type, see [UniformBufferElement::array].
+    /// Each element in the array is padded to the size of a `vec4`, so the data for an array of `float`s must contain four values for each element.
+    Array(Box<UniformBufferElement>, u32),
+}
+
+impl UniformBufferElement {
+    ///
+    /// Returns the type of an array with the given number of elements of the given type.
+    ///
+    pub fn array(element: UniformBufferElement, length: u32) -> Self {
+        Self::Array(Box::new(element), length)
+    }
+
+    ///
+    /// Returns the alignment and size, both in number of `f32`s, of a variable of this type using the std140 layout rules.
+    ///
+    fn alignment_and_size(&self) -> (usize, usize) {
+        match self {
+            Self::Float => (1, 1),
+            Self::Vec2 => (2, 2),
+            Self::Vec3 => (4, 3),
+            Self::Vec4 => (4, 4),
+            Self::Mat3 => (4, 12),
+            Self::Mat4 => (4, 16),
+            Self::Array(element, length) => {
+                let (_, size) = element.alignment_and_size();
+                (4, round_up(size, 4) * *length as usize)
+            }
+        }
+    }
+}
+
+///
+/// Returns the offsets and lengths, in number of `f32`s, of variables of the given types laid out according to the std140 layout rules
+/// together with the total length of the uniform block.
+///
+fn std140_layout(elements: &[UniformBufferElement]) -> (Vec<usize>, Vec<usize>, usize) {
+    let mut offsets = Vec::new();
+    let mut lengths = Vec::new();
+    let mut length = 0;
+    for element in elements {
+        let (alignment, size) = element.alignment_and_size();
+        length = round_up(length, alignment);
+        offsets.push(length);
+        lengths.push(size);
+        length += size;
+    }
+    // The size of a uniform block is rounded up to the alignment of a vec4
+    (offsets, lengths, round_up(length, 4))
+}
+
+fn round_up(value: usize, multiple: usize) -> usize {
+    value + (multiple - value % multiple) % multiple
+}
+
 ///
 /// A buffer for transferring a set of uniform variables to the shader program
 /// (see also [use_uniform_block](crate::core::Program::use_uniform_block)).
@@ -8,7 +75,9 @@ pub struct UniformBuffer {
     context: Context,
     id: crate::context::Buffer,
     offsets: Vec<usize>,
+    lengths: Vec<usize>,
     data: Vec<f32>,
+    usage: BufferUsage,
 }
 
 impl UniformBuffer {
@@ -17,21 +86,63 @@ impl UniformBuffer {
     /// So for example if you create a uniform buffer with `&[3, 1, 4, 16]` as the `sizes` argument, you will have a uniform buffer that has four variables:
     /// The first with 3 elements (a [Vec3]), the second with 1 element (a `f32`), the third with four elements (a [Vec4]) and the last with 16 elements (a [Mat4]).
     /// The variables are initialized to 0.
+    /// The buffer is created with the [BufferUsage::Dynamic] usage hint, use [UniformBuffer::new_with_usage] to specify another hint.
     ///
     pub fn new(context: &Context, sizes: &[u32]) -> UniformBuffer {
-        let id = unsafe { context.create_buffer().expect("Failed creating buffer") };
+        Self::new_with_usage(context, sizes, BufferUsage::Dynamic)
+    }
 
+    ///
+    /// Creates a new uniform buffer with room for a set of variables of varying length defined by the `sizes` argument (see [UniformBuffer::new])
+    /// and with the given usage hint, for example [BufferUsage::Stream] if the variables are updated every frame.
+    ///
+    pub fn new_with_usage(context: &Context, sizes: &[u32], usage: BufferUsage) -> UniformBuffer {
         let mut offsets = Vec::new();
         let mut length = 0;
         for size in sizes {
             offsets.push(length);
             length += *size as usize;
         }
+        Self::new_with_layout(
+            context,
+            offsets,
+            sizes.iter().map(|size| *size as usize).collect(),
+            length,
+            usage,
+        )
+    }
+
+    ///
+    /// Creates a new uniform buffer with a variable for each of the given types where the variables are laid out according to the std140 layout rules,
+    /// ie. the layout of a uniform block declared with `layout (std140)` in the shader, and with the given usage hint (see [UniformBuffer::new_with_usage]).
+    /// The padding between the variables, for example after a [Vec3] followed by a [Vec4], is computed automatically
+    /// so the variables can be updated by index using the typed methods, for example [UniformBuffer::update_vec3] and [UniformBuffer::update_mat4].
+    /// The variables are initialized to 0.
+    ///
+    pub fn new_std140(
+        context: &Context,
+        elements: &[UniformBufferElement],
+        usage: BufferUsage,
+    ) -> UniformBuffer {
+        let (offsets, lengths, length) = std140_layout(elements);
+        Self::new_with_layout(context, offsets, lengths, length, usage)
+    }
+
+    fn new_with_layout(
+        context: &Context,
+        offsets: Vec<usize>,
+        lengths: Vec<usize>,
+        length: usize,
+        usage: BufferUsage,
+    ) -> UniformBuffer {
+        let id = unsafe { context.create_buffer().expect("Failed creating buffer") };
         let buffer = UniformBuffer {
             context: context.clone(),
             id,
             offsets,
+            lengths,
             data: vec![0.0; length],
+            usage,
         };
         buffer.send();
         buffer
@@ -63,7 +174,7 @@ impl UniformBuffer {
             }
             self.data
                 .splice(offset..offset + length, data.iter().cloned());
-            self.send();
+            self.send_range(offset, length);
         } else {
             panic!(
                 "the index {} is outside the expected range [0, {}]",
@@ -71,7 +182,56 @@ impl UniformBuffer {
                 self.offsets.len() - 1
             );
         }
-        //TODO: Send to GPU (contextBufferSubData)
+    }
+
+    ///
+    /// Update the `float` variable at the given index, see [UniformBuffer::update].
+    ///
+    pub fn update_f32(&mut self, index: u32, value: f32) {
+        self.update(index, &[value]);
+    }
+
+    ///
+    /// Update the `vec2` variable at the given index, see [UniformBuffer::update].
+    ///
+    pub fn update_vec2(&mut self, index: u32, value: Vec2) {
+        self.update(index, &[value.x, value.y]);
+    }
+
+    ///
+    /// Update the `vec3` variable at the given index, see [UniformBuffer::update].
+    ///
+    pub fn update_vec3(&mut self, index: u32, value: Vec3) {
+        self.update(index, &[value.x, value.y, value.z]);
+    }
+
+    ///
+    /// Update the `vec4` variable at the given index, see [UniformBuffer::update].
+    ///
+    pub fn update_vec4(&mut self, index: u32, value: Vec4) {
+        self.update(index, &[value.x, value.y, value.z, value.w]);
+    }
+
+    ///
+    /// Update the `mat3` variable at the given index, see [UniformBuffer::update].
+    /// Each column is padded to the size of a `vec4`, so the variable must have been created with 12 elements, for example using [UniformBufferElement::Mat3].
+    ///
+    pub fn update_mat3(&mut self, index: u32, value: Mat3) {
+        self.update(
+            index,
+            &[
+                value.x.x, value.x.y, value.x.z, 0.0, value.y.x, value.y.y, value.y.z, 0.0,
+                value.z.x, value.z.y, value.z.z, 0.0,
+            ],
+        );
+    }
+
+    ///
+    /// Update the `mat4` variable at the given index, see [UniformBuffer::update].
+    ///
+    pub fn update_mat4(&mut self, index: u32, value: Mat4) {
+        let data: &[f32; 16] = value.as_ref();
+        self.update(index, data);
     }
 
     ///
@@ -86,13 +246,24 @@ impl UniformBuffer {
         if index >= self.offsets.len() {
             None
         } else {
-            let offset = self.offsets[index];
-            let length = if index + 1 == self.offsets.len() {
-                self.data.len()
-            } else {
-                self.offsets[index + 1]
-            } - offset;
-            Some((offset, length))
+            Some((self.offsets[index], self.lengths[index]))
+        }
+    }
+
+    ///
+    /// Uploads only the `offset..offset + length` range of the data, which requires that the buffer is already allocated by [UniformBuffer::send].
+    ///
+    fn send_range(&self, offset: usize, length: usize) {
+        unsafe {
+            self.context
+                .bind_buffer(crate::context::UNIFORM_BUFFER, Some(self.id));
+            self.context.buffer_sub_data_u8_slice(
+                crate::context::UNIFORM_BUFFER,
+                (offset * std::mem::size_of::<f32>()) as i32,
+                to_byte_slice(&self.data[offset..offset + length]),
+            );
+            self.context
+                .bind_buffer(crate::context::UNIFORM_BUFFER, None);
         }
     }
 
@@ -100,11 +271,15 @@ impl UniformBuffer {
         unsafe {
             self.context
                 .bind_buffer(crate::context::UNIFORM_BUFFER, Some(self.id));
-            self.context.buffer_data_u8_slice(
+            // Orphan the old storage before uploading, to avoid waiting for draw calls that are still reading from it.
+            let bytes = to_byte_slice(&self.data);
+            self.context.buffer_data_size(
                 crate::context::UNIFORM_BUFFER,
-                to_byte_slice(&self.data),
-                crate::context::STATIC_DRAW,
+                bytes.len() as i32,
+                self.usage.to_const(),
             );
+            self.context
+                .buffer_sub_data_u8_slice(crate::context::UNIFORM_BUFFER, 0, bytes);
             self.context
                 .bind_buffer(crate::context::UNIFORM_BUFFER, None);
         }
@@ -118,3 +293,48 @@ impl Drop for UniformBuffer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn std140_vec3_followed_by_float() {
+        // A float can be packed into the padding after a vec3
+        let (offsets, lengths, length) =
+            std140_layout(&[UniformBufferElement::Vec3, UniformBufferElement::Float]);
+        assert_eq!(offsets, vec![0, 3]);
+        assert_eq!(lengths, vec![3, 1]);
+        assert_eq!(length, 4);
+    }
+
+    #[test]
+    fn std140_mixed_types() {
+        let (offsets, lengths, length) = std140_layout(&[
+            UniformBufferElement::Float,
+            UniformBufferElement::Vec3,
+            UniformBufferElement::Vec2,
+            UniformBufferElement::Mat4,
+            UniformBufferElement::Float,
+            UniformBufferElement::Mat3,
+            UniformBufferElement::Vec4,
+        ]);
+        assert_eq!(offsets, vec![0, 4, 8, 12, 28, 32, 44]);
+        assert_eq!(lengths, vec![1, 3, 2, 16, 1, 12, 4]);
+        assert_eq!(length, 48);
+    }
+
+    #[test]
+    fn std140_arrays() {
+        let (offsets, lengths, length) = std140_layout(&[
+            UniformBufferElement::Float,
+            UniformBufferElement::array(UniformBufferElement::Float, 3),
+            UniformBufferElement::array(UniformBufferElement::Vec3, 2),
+            UniformBufferElement::array(UniformBufferElement::Mat4, 2),
+            UniformBufferElement::Vec2,
+        ]);
+        assert_eq!(offsets, vec![0, 4, 16, 24, 56]);
+        assert_eq!(lengths, vec![1, 12, 8, 32, 2]);
+        assert_eq!(length, 60);
+    }
+}
@@ -37,6 +37,18 @@ impl VertexBuffer {
         self.buffer.fill(data);
     }
 
+    ///
+    /// Updates a part of the vertex buffer, starting at the vertex with the given offset, with the given data without reallocating the buffer.
+    /// This is useful when only some of the data changes each frame, use [VertexBuffer::fill] to replace all of the data or to change the size of the buffer.
+    /// The buffer must already be filled with data of the same type, for example using [VertexBuffer::new_with_data] or [VertexBuffer::fill].
+    ///
+    /// Will panic if the buffer has not been filled, if the data type is different from the type of the data in the buffer
+    /// or if the updated range is outside of the buffer, ie. if `offset + data.len()` is larger than [VertexBuffer::vertex_count].
+    ///
+    pub fn update<T: BufferDataType>(&mut self, offset: u32, data: &[T]) {
+        self.buffer.update(offset, data);
+    }
+
     ///
     /// The number of values in the buffer.
     ///
@@ -0,0 +1,135 @@
+use crate::core::*;
+
+/// Fully transparent black, which is for example useful as a clear color when rendering into a texture that is blended with the scene afterwards.
+pub const SRGBA_TRANSPARENT: Srgba = Srgba::new(0, 0, 0, 0);
+
+///
+/// Converts a color in linear sRGB color space, for example the result of [Srgba::to_linear_srgb], back to an [Srgba] color.
+/// The red, green and blue channels are encoded with the sRGB transfer function while the alpha channel is stored as is.
+/// All channels are clamped to the range `[0, 1]`.
+///
+pub fn srgba_from_linear(color: Vec4) -> Srgba {
+    let convert = |c: f32| {
+        let c = c.clamp(0.0, 1.0);
+        let c = if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        (c * 255.0).round() as u8
+    };
+    Srgba::new(
+        convert(color.x),
+        convert(color.y),
+        convert(color.z),
+        (color.w.clamp(0.0, 1.0) * 255.0).round() as u8,
+    )
+}
+
+///
+/// Parses an [Srgba] color from a hexadecimal string in one of the forms `RGB`, `RRGGBB` or `RRGGBBAA`, optionally prefixed with `#`,
+/// for example `"#f80"`, `"#ff8800"` or `"#ff880080"`. The color is opaque unless the alpha channel is given.
+///
+pub fn srgba_from_hex(hex: &str) -> Result<Srgba, CoreError> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    let error = || CoreError::InvalidHexColor(hex.to_string());
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(error());
+    }
+    let channel = |i: usize, length: usize| {
+        u8::from_str_radix(&digits[i * length..(i + 1) * length], 16).map_err(|_| error())
+    };
+    match digits.len() {
+        3 => Ok(Srgba::new_opaque(
+            channel(0, 1)? * 17,
+            channel(1, 1)? * 17,
+            channel(2, 1)? * 17,
+        )),
+        6 => Ok(Srgba::new_opaque(
+            channel(0, 2)?,
+            channel(1, 2)?,
+            channel(2, 2)?,
+        )),
+        8 => Ok(Srgba::new(
+            channel(0, 2)?,
+            channel(1, 2)?,
+            channel(2, 2)?,
+            channel(3, 2)?,
+        )),
+        _ => Err(error()),
+    }
+}
+
+///
+/// Returns the color in hexadecimal form `#RRGGBBAA`, which can be parsed again using [srgba_from_hex].
+///
+pub fn srgba_to_hex(color: Srgba) -> String {
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        color.r, color.g, color.b, color.a
+    )
+}
+
+///
+/// Linearly interpolates between the two colors, where a factor of zero returns the first color and a factor of one returns the second color.
+/// The interpolation happens in linear sRGB color space, which gives a perceptually more correct result than interpolating the sRGB encoded values.
+///
+pub fn mix_srgba(a: Srgba, b: Srgba, factor: f32) -> Srgba {
+    let factor = factor.clamp(0.0, 1.0);
+    srgba_from_linear(a.to_linear_srgb() * (1.0 - factor) + b.to_linear_srgb() * factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_short_form() {
+        assert_eq!(
+            srgba_from_hex("#f80").unwrap(),
+            Srgba::new(255, 136, 0, 255)
+        );
+        assert_eq!(srgba_from_hex("f80").unwrap(), Srgba::new(255, 136, 0, 255));
+    }
+
+    #[test]
+    fn hex_long_form() {
+        assert_eq!(
+            srgba_from_hex("#1a2B3c").unwrap(),
+            Srgba::new(26, 43, 60, 255)
+        );
+    }
+
+    #[test]
+    fn hex_with_alpha() {
+        let color = Srgba::new(255, 136, 0, 128);
+        assert_eq!(srgba_from_hex("#ff880080").unwrap(), color);
+        assert_eq!(srgba_from_hex(&srgba_to_hex(color)).unwrap(), color);
+    }
+
+    #[test]
+    fn hex_invalid() {
+        for hex in ["", "#", "#ff88", "#ff880", "#gg8800", "#ff8800801", "#+f80"] {
+            assert!(
+                matches!(srgba_from_hex(hex), Err(CoreError::InvalidHexColor(h)) if h == hex),
+                "{hex} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn linear_round_trip() {
+        for value in 0..=255u8 {
+            let color = Srgba::new(value, value / 2, 255 - value, value);
+            let result = srgba_from_linear(color.to_linear_srgb());
+            for (a, b) in [
+                (color.r, result.r),
+                (color.g, result.g),
+                (color.b, result.b),
+                (color.a, result.a),
+            ] {
+                assert!(a.abs_diff(b) <= 1, "{color:?} became {result:?}");
+            }
+        }
+    }
+}
@@ -17,6 +17,94 @@ pub struct Context {
     pub(super) vao: crate::context::VertexArray,
     /// A cache of programs to avoid recompiling a [Program] every frame.
     pub programs: Arc<RwLock<HashMap<Vec<u8>, Program>>>,
+    default_clear_state: Arc<RwLock<ClearState>>,
+    scissor_box: Arc<RwLock<ScissorBox>>,
+    capabilities: ContextCapabilities,
+}
+
+///
+/// The limits and optional features supported by the graphics driver, see [Context::capabilities].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContextCapabilities {
+    /// The maximum width and height of a 2D texture.
+    pub max_texture_size: u32,
+    /// The maximum width, height and depth of a 3D texture.
+    pub max_3d_texture_size: u32,
+    /// The maximum width and height of each side of a cube map texture.
+    pub max_cube_map_texture_size: u32,
+    /// The maximum number of layers in a texture array, see for example [Texture2DArray].
+    pub max_array_texture_layers: u32,
+    /// The maximum number of samples in a multisample render target, see for example [RenderTargetMultisample].
+    pub max_samples: u32,
+    /// The maximum number of textures that can be used in a fragment shader at the same time.
+    pub max_texture_image_units: u32,
+    /// The maximum number of [UniformBuffer]s that can be used at the same time.
+    pub max_uniform_buffer_bindings: u32,
+    /// The maximum number of vertex attributes, ie. the total number of attributes used by a [VertexBuffer] or [InstanceBuffer].
+    pub max_vertex_attribs: u32,
+    /// The maximum number of color attachments of a render target, ie. the number of layers that can be rendered to at the same time.
+    pub max_color_attachments: u32,
+    /// The maximum anisotropy for anisotropic texture filtering, see for example [Texture2D::set_anisotropy]. One if anisotropic filtering is not supported.
+    pub max_anisotropy: f32,
+    /// Whether or not floating point textures can be used as color targets, for example a [Texture2D] with `[f32; 4]` or `[f16; 4]` data.
+    pub float_color_buffers: bool,
+    /// Whether or not half precision floating point textures can be used as color targets, for example a [Texture2D] with `[f16; 4]` data.
+    /// This is also true if [ContextCapabilities::float_color_buffers] is true.
+    pub half_float_color_buffers: bool,
+    /// Whether or not floating point textures can be sampled with linear interpolation.
+    pub float_linear_filtering: bool,
+    /// Whether or not instanced rendering is supported, which is required by for example an [InstanceBuffer].
+    pub instancing: bool,
+    /// Whether or not texture arrays are supported, which is required by for example a [Texture2DArray].
+    pub texture_arrays: bool,
+}
+
+impl ContextCapabilities {
+    fn new(context: &crate::context::Context) -> Self {
+        let extensions = context.supported_extensions();
+        let has_extension = |names: &[&str]| names.iter().any(|name| extensions.contains(*name));
+        let get = |parameter: u32| unsafe { context.get_parameter_i32(parameter).max(0) as u32 };
+        let version = context.version();
+        let is_embedded = version.is_embedded;
+        let float_color_buffers =
+            !is_embedded || has_extension(&["GL_EXT_color_buffer_float", "EXT_color_buffer_float"]);
+        Self {
+            max_texture_size: get(crate::context::MAX_TEXTURE_SIZE),
+            max_3d_texture_size: get(crate::context::MAX_3D_TEXTURE_SIZE),
+            max_cube_map_texture_size: get(crate::context::MAX_CUBE_MAP_TEXTURE_SIZE),
+            max_array_texture_layers: get(crate::context::MAX_ARRAY_TEXTURE_LAYERS),
+            max_samples: get(crate::context::MAX_SAMPLES),
+            max_texture_image_units: get(crate::context::MAX_TEXTURE_IMAGE_UNITS),
+            max_uniform_buffer_bindings: get(crate::context::MAX_UNIFORM_BUFFER_BINDINGS),
+            max_vertex_attribs: get(crate::context::MAX_VERTEX_ATTRIBS),
+            max_color_attachments: get(crate::context::MAX_COLOR_ATTACHMENTS),
+            max_anisotropy: if has_extension(&[
+                "GL_EXT_texture_filter_anisotropic",
+                "GL_ARB_texture_filter_anisotropic",
+                "EXT_texture_filter_anisotropic",
+            ]) {
+                unsafe {
+                    context
+                        .get_parameter_f32(crate::context::MAX_TEXTURE_MAX_ANISOTROPY_EXT)
+                        .max(1.0)
+                }
+            } else {
+                1.0
+            },
+            float_color_buffers,
+            half_float_color_buffers: float_color_buffers
+                || has_extension(&[
+                    "GL_EXT_color_buffer_half_float",
+                    "EXT_color_buffer_half_float",
+                ]),
+            float_linear_filtering: !is_embedded
+                || has_extension(&["GL_OES_texture_float_linear", "OES_texture_float_linear"]),
+            instancing: version.major > 3
+                || (version.major == 3 && (is_embedded || version.minor >= 3)),
+            texture_arrays: version.major >= 3 && get(crate::context::MAX_ARRAY_TEXTURE_LAYERS) > 0,
+        }
+    }
 }
 
 impl Context {
@@ -41,20 +129,100 @@ impl Context {
                 .create_vertex_array()
                 .map_err(CoreError::ContextCreation)?;
             Self {
+                capabilities: ContextCapabilities::new(&context),
                 context,
                 vao,
                 programs: Arc::new(RwLock::new(HashMap::new())),
+                default_clear_state: Arc::new(RwLock::new(ClearState::default())),
+                scissor_box: Arc::new(RwLock::new(ScissorBox::new_at_origo(0, 0))),
             }
         };
         Ok(c)
     }
 
+    ///
+    /// Returns the default [ClearState] for this context which is used by [RenderTarget::clear_default].
+    /// Initially this is the same as [ClearState::default].
+    ///
+    pub fn default_clear_state(&self) -> ClearState {
+        *self.default_clear_state.read().unwrap()
+    }
+
+    ///
+    /// Sets the default [ClearState] for this context which is used by [RenderTarget::clear_default].
+    /// The default clear state is shared between all clones of this context, so it only needs to be set once, for example at startup.
+    ///
+    pub fn set_default_clear_state(&self, clear_state: ClearState) {
+        *self.default_clear_state.write().unwrap() = clear_state;
+    }
+
+    ///
+    /// Returns the limits and optional features supported by the graphics driver.
+    /// The capabilities are queried once when the context is created.
+    ///
+    pub fn capabilities(&self) -> ContextCapabilities {
+        self.capabilities
+    }
+
+    ///
+    /// Returns the maximum number of samples supported by the graphics driver for multisample render targets,
+    /// see for example [RenderTargetMultisample].
+    ///
+    pub fn max_samples(&self) -> u32 {
+        self.capabilities.max_samples
+    }
+
+    ///
+    /// Returns the maximum anisotropy supported by the graphics driver for anisotropic texture filtering, see for example [Texture2D::set_anisotropy].
+    /// Returns 1 if anisotropic filtering is not supported.
+    ///
+    pub fn max_anisotropy(&self) -> f32 {
+        self.capabilities.max_anisotropy
+    }
+
+    ///
+    /// Returns the maximum width and height of a 2D texture supported by the graphics driver.
+    ///
+    pub fn max_texture_size(&self) -> u32 {
+        self.capabilities.max_texture_size
+    }
+
+    ///
+    /// Returns the maximum number of layers in a texture array supported by the graphics driver.
+    ///
+    pub fn max_array_texture_layers(&self) -> u32 {
+        self.capabilities.max_array_texture_layers
+    }
+
+    ///
+    /// Returns the maximum number of [UniformBuffer]s that can be used at the same time.
+    ///
+    pub fn max_uniform_buffer_bindings(&self) -> u32 {
+        self.capabilities.max_uniform_buffer_bindings
+    }
+
+    ///
+    /// Returns the maximum number of vertex attributes supported by the graphics driver.
+    ///
+    pub fn max_vertex_attribs(&self) -> u32 {
+        self.capabilities.max_vertex_attribs
+    }
+
     ///
     /// Set the scissor test for this context (see [ScissorBox]).
+    /// A scissor box with zero width or height disables the scissor test.
+    /// The scissor box is further restricted by [RenderStates::scissor] in each render call.
     ///
     pub fn set_scissor(&self, scissor_box: ScissorBox) {
+        *self.scissor_box.write().unwrap() = scissor_box;
+        self.apply_scissor(
+            Some(scissor_box).filter(|scissor_box| scissor_box.width > 0 && scissor_box.height > 0),
+        );
+    }
+
+    fn apply_scissor(&self, scissor_box: Option<ScissorBox>) {
         unsafe {
-            if scissor_box.width > 0 && scissor_box.height > 0 {
+            if let Some(scissor_box) = scissor_box {
                 self.enable(crate::context::SCISSOR_TEST);
                 self.scissor(
                     scissor_box.x,
@@ -188,6 +356,61 @@ impl Context {
         }
     }
 
+    ///
+    /// Set the stencil state for this context (see [Stencil]).
+    ///
+    pub fn set_stencil(&self, stencil: Stencil) {
+        unsafe {
+            if let Stencil::Enabled {
+                test,
+                reference,
+                read_mask,
+                write_mask,
+                fail,
+                depth_fail,
+                pass,
+            } = stencil
+            {
+                self.enable(crate::context::STENCIL_TEST);
+                self.stencil_func(
+                    match test {
+                        StencilTest::Never => crate::context::NEVER,
+                        StencilTest::Less => crate::context::LESS,
+                        StencilTest::Equal => crate::context::EQUAL,
+                        StencilTest::LessOrEqual => crate::context::LEQUAL,
+                        StencilTest::Greater => crate::context::GREATER,
+                        StencilTest::NotEqual => crate::context::NOTEQUAL,
+                        StencilTest::GreaterOrEqual => crate::context::GEQUAL,
+                        StencilTest::Always => crate::context::ALWAYS,
+                    },
+                    reference as i32,
+                    read_mask as u32,
+                );
+                self.stencil_mask(write_mask as u32);
+                self.stencil_op(
+                    Self::stencil_const_from_operation(fail),
+                    Self::stencil_const_from_operation(depth_fail),
+                    Self::stencil_const_from_operation(pass),
+                );
+            } else {
+                self.disable(crate::context::STENCIL_TEST);
+            }
+        }
+    }
+
+    fn stencil_const_from_operation(operation: StencilOperation) -> u32 {
+        match operation {
+            StencilOperation::Keep => crate::context::KEEP,
+            StencilOperation::Zero => crate::context::ZERO,
+            StencilOperation::Replace => crate::context::REPLACE,
+            StencilOperation::Increment => crate::context::INCR,
+            StencilOperation::IncrementWrap => crate::context::INCR_WRAP,
+            StencilOperation::Decrement => crate::context::DECR,
+            StencilOperation::DecrementWrap => crate::context::DECR_WRAP,
+            StencilOperation::Invert => crate::context::INVERT,
+        }
+    }
+
     fn blend_const_from_multiplier(multiplier: BlendMultiplierType) -> u32 {
         match multiplier {
             BlendMultiplierType::Zero => crate::context::ZERO,
@@ -225,6 +448,30 @@ impl Context {
             self.set_depth_test(render_states.depth_test);
         }
         self.set_blend(render_states.blend);
+        self.set_polygon_offset(render_states.polygon_offset);
+        self.set_stencil(render_states.stencil);
+        let scissor_box = *self.scissor_box.read().unwrap();
+        let enabled = scissor_box.width > 0 && scissor_box.height > 0;
+        self.apply_scissor(match render_states.scissor {
+            Some(scissor) if enabled => Some(scissor_box.intersection(scissor)),
+            Some(scissor) => Some(scissor),
+            None if enabled => Some(scissor_box),
+            None => None,
+        });
+    }
+
+    ///
+    /// Set the polygon offset for this context given as a `(factor, units)` pair or disable it if `None` (see [RenderStates::polygon_offset]).
+    ///
+    pub fn set_polygon_offset(&self, polygon_offset: Option<(f32, f32)>) {
+        unsafe {
+            if let Some((factor, units)) = polygon_offset {
+                self.enable(crate::context::POLYGON_OFFSET_FILL);
+                self.polygon_offset(factor, units);
+            } else {
+                self.disable(crate::context::POLYGON_OFFSET_FILL);
+            }
+        }
     }
 
     ///
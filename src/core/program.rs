@@ -444,6 +444,32 @@ impl Program {
             .expect("Unexpected rendering error occured")
     }
 
+    ///
+    /// Draws `count` number of vertices as line segments with the given render states and viewport using this shader program,
+    /// ie. the first and second vertex defines the first line segment, the third and fourth vertex defines the second line segment etc.
+    /// Requires that all attributes and uniforms have been defined using the use_attribute and use_uniform methods.
+    /// Note that the width of the lines is always one pixel.
+    ///
+    pub fn draw_lines(&self, render_states: RenderStates, viewport: Viewport, count: u32) {
+        self.context.set_viewport(viewport);
+        self.context.set_render_states(render_states);
+        self.use_program();
+        unsafe {
+            self.context
+                .draw_arrays(crate::context::LINES, 0, count as i32);
+            for location in self.attributes.values() {
+                self.context.disable_vertex_attrib_array(*location);
+            }
+            self.context.bind_vertex_array(None);
+        }
+        self.unuse_program();
+
+        #[cfg(debug_assertions)]
+        self.context
+            .error_check()
+            .expect("Unexpected rendering error occured")
+    }
+
     ///
     /// Same as [Program::draw_arrays] except it renders 'instance_count' instances of the same set of triangles.
     /// Use the [Program::use_instance_attribute], method to send unique data for each instance to the shader.
@@ -5,7 +5,7 @@
 ///
 /// A set of render specific states that has to be specified at each render call.
 ///
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct RenderStates {
     ///
     /// Defines which channels (red, green, blue, alpha and depth) to write to in a render call.
@@ -31,6 +31,112 @@ pub struct RenderStates {
     /// Defines whether the triangles that are backfacing, frontfacing or both should be skipped in a render call.
     ///
     pub cull: Cull,
+
+    ///
+    /// Defines an offset, given as a `(factor, units)` pair, which is added to the depth of each fragment before the depth test and before it is written to the depth buffer.
+    /// The offset is `factor * m + units * r` where `m` is the maximum depth slope of the triangle and `r` is the smallest resolvable depth difference.
+    /// Negative values moves the fragments towards the camera, which is useful to avoid z-fighting when rendering decals or other geometry that is coplanar with other geometry.
+    /// The default is no offset.
+    ///
+    pub polygon_offset: Option<(f32, f32)>,
+
+    ///
+    /// Restricts the render call to the pixels inside the given [ScissorBox](crate::core::ScissorBox), without changing the viewport transformation.
+    /// The box is intersected with the scissor box given to the render target, for example in [RenderTarget::write_partially](crate::core::RenderTarget::write_partially),
+    /// so it can only restrict the area that is rendered to further. Use [RenderTarget::clear_partially](crate::core::RenderTarget::clear_partially) to restrict a clear in the same way.
+    /// The default is no restriction.
+    ///
+    pub scissor: Option<crate::core::ScissorBox>,
+
+    ///
+    /// Defines the stencil test and how the stencil buffer is updated in a render call.
+    /// Requires that the render target has a stencil buffer, for example a depth texture with the [Depth24Stencil8](crate::core::Depth24Stencil8) format
+    /// or a window created with a stencil buffer.
+    ///
+    pub stencil: Stencil,
+}
+
+impl RenderStates {
+    ///
+    /// Returns a [RenderStatesBuilder] which can be used to construct [RenderStates], starting from the default render states.
+    ///
+    pub fn builder() -> RenderStatesBuilder {
+        RenderStatesBuilder::default()
+    }
+}
+
+///
+/// A builder for [RenderStates], see [RenderStates::builder].
+/// Any state that is not specified is set to its default value.
+///
+#[derive(Debug, Copy, Clone, Default)]
+pub struct RenderStatesBuilder {
+    render_states: RenderStates,
+}
+
+impl RenderStatesBuilder {
+    ///
+    /// Sets the [WriteMask].
+    ///
+    pub fn write_mask(mut self, write_mask: WriteMask) -> Self {
+        self.render_states.write_mask = write_mask;
+        self
+    }
+
+    ///
+    /// Sets the [DepthTest].
+    ///
+    pub fn depth_test(mut self, depth_test: DepthTest) -> Self {
+        self.render_states.depth_test = depth_test;
+        self
+    }
+
+    ///
+    /// Sets the [Blend] state.
+    ///
+    pub fn blend(mut self, blend: Blend) -> Self {
+        self.render_states.blend = blend;
+        self
+    }
+
+    ///
+    /// Sets the [Cull] state.
+    ///
+    pub fn cull(mut self, cull: Cull) -> Self {
+        self.render_states.cull = cull;
+        self
+    }
+
+    ///
+    /// Sets the polygon offset, see [RenderStates::polygon_offset].
+    ///
+    pub fn polygon_offset(mut self, factor: f32, units: f32) -> Self {
+        self.render_states.polygon_offset = Some((factor, units));
+        self
+    }
+
+    ///
+    /// Sets the scissor box, see [RenderStates::scissor].
+    ///
+    pub fn scissor(mut self, scissor_box: crate::core::ScissorBox) -> Self {
+        self.render_states.scissor = Some(scissor_box);
+        self
+    }
+
+    ///
+    /// Sets the [Stencil] state.
+    ///
+    pub fn stencil(mut self, stencil: Stencil) -> Self {
+        self.render_states.stencil = stencil;
+        self
+    }
+
+    ///
+    /// Returns the constructed [RenderStates].
+    ///
+    pub fn build(self) -> RenderStates {
+        self.render_states
+    }
 }
 
 ///
@@ -79,6 +185,121 @@ impl Default for DepthTest {
     }
 }
 
+///
+/// Determines whether or not a fragment/pixel from the current render call should be discarded
+/// when comparing the reference value of the [Stencil] state with the value in the stencil buffer, ie. `reference & read_mask` compared to `stencil & read_mask`.
+///
+#[allow(missing_docs)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StencilTest {
+    Never,
+    Less,
+    Equal,
+    LessOrEqual,
+    Greater,
+    NotEqual,
+    GreaterOrEqual,
+    Always,
+}
+
+///
+/// Defines how the value in the stencil buffer is updated, see [Stencil].
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StencilOperation {
+    /// Keeps the current value.
+    Keep,
+    /// Sets the value to zero.
+    Zero,
+    /// Sets the value to the reference value.
+    Replace,
+    /// Increments the value, clamping at the maximum value.
+    Increment,
+    /// Increments the value, wrapping to zero at the maximum value.
+    IncrementWrap,
+    /// Decrements the value, clamping at zero.
+    Decrement,
+    /// Decrements the value, wrapping to the maximum value at zero.
+    DecrementWrap,
+    /// Inverts the bits of the value.
+    Invert,
+}
+
+///
+/// Defines the stencil test and how the stencil buffer is updated in a render call.
+/// The stencil buffer contains an 8 bit value for each pixel which can be used to mask out parts of the render target, for example for portals, mirrors or outlines.
+///
+/// A minimal outline recipe using two render calls is to first render the object with [Stencil::write] which writes one to the stencil buffer for all pixels covered by the object,
+/// and then render a slightly scaled up version of the object in the outline color with [Stencil::not_equal] and depth test [DepthTest::Always],
+/// so only the pixels around the object is rendered.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Stencil {
+    /// The stencil test is enabled.
+    Enabled {
+        /// The comparison between the reference value and the value in the stencil buffer which determines if a fragment passes the stencil test.
+        test: StencilTest,
+        /// The reference value used in the stencil test and by [StencilOperation::Replace].
+        reference: u8,
+        /// A mask that is applied to both the reference value and the value in the stencil buffer before the comparison.
+        read_mask: u8,
+        /// A mask that defines which bits of the value in the stencil buffer that can be updated.
+        write_mask: u8,
+        /// The operation applied when the stencil test fails.
+        fail: StencilOperation,
+        /// The operation applied when the stencil test passes but the depth test fails.
+        depth_fail: StencilOperation,
+        /// The operation applied when both the stencil test and the depth test passes.
+        pass: StencilOperation,
+    },
+    /// The stencil test is disabled and the stencil buffer is not updated.
+    #[default]
+    Disabled,
+}
+
+impl Stencil {
+    ///
+    /// Writes the given reference value to the stencil buffer for all rendered pixels without discarding anything.
+    ///
+    pub const fn write(reference: u8) -> Self {
+        Self::Enabled {
+            test: StencilTest::Always,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0xFF,
+            fail: StencilOperation::Keep,
+            depth_fail: StencilOperation::Keep,
+            pass: StencilOperation::Replace,
+        }
+    }
+
+    ///
+    /// Only renders pixels where the value in the stencil buffer is equal to the given reference value and leaves the stencil buffer unchanged.
+    ///
+    pub const fn equal(reference: u8) -> Self {
+        Self::test(StencilTest::Equal, reference)
+    }
+
+    ///
+    /// Only renders pixels where the value in the stencil buffer is not equal to the given reference value and leaves the stencil buffer unchanged.
+    ///
+    pub const fn not_equal(reference: u8) -> Self {
+        Self::test(StencilTest::NotEqual, reference)
+    }
+
+    const fn test(test: StencilTest, reference: u8) -> Self {
+        Self::Enabled {
+            test,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0x00,
+            fail: StencilOperation::Keep,
+            depth_fail: StencilOperation::Keep,
+            pass: StencilOperation::Keep,
+        }
+    }
+}
+
 ///
 /// Defines which channels (red, green, blue, alpha and depth) to write to in a render call.
 ///
@@ -201,6 +422,19 @@ impl Blend {
         rgb_equation: BlendEquationType::Add,
         alpha_equation: BlendEquationType::Add,
     };
+
+    ///
+    /// Transparency blending parameters for when the output color of the render call is premultiplied with the alpha value, for example when using textures with premultiplied alpha (see [premultiply_alpha](crate::core::premultiply_alpha)).
+    /// Works the same way on desktop and web.
+    ///
+    pub const PREMULTIPLIED_ALPHA: Self = Self::Enabled {
+        source_rgb_multiplier: BlendMultiplierType::One,
+        source_alpha_multiplier: BlendMultiplierType::Zero,
+        destination_rgb_multiplier: BlendMultiplierType::OneMinusSrcAlpha,
+        destination_alpha_multiplier: BlendMultiplierType::One,
+        rgb_equation: BlendEquationType::Add,
+        alpha_equation: BlendEquationType::Add,
+    };
 }
 
 impl Default for Blend {
@@ -240,3 +474,57 @@ pub enum BlendEquationType {
     Max,
     Min,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ScissorBox;
+
+    #[test]
+    fn builder_defaults() {
+        assert_eq!(RenderStates::builder().build(), RenderStates::default());
+    }
+
+    #[test]
+    fn builder_equals_struct_expression() {
+        let scissor = ScissorBox {
+            x: 1,
+            y: 2,
+            width: 3,
+            height: 4,
+        };
+        let built = RenderStates::builder()
+            .write_mask(WriteMask::COLOR)
+            .depth_test(DepthTest::Always)
+            .blend(Blend::TRANSPARENCY)
+            .cull(Cull::Back)
+            .polygon_offset(-1.0, -2.0)
+            .scissor(scissor)
+            .stencil(Stencil::write(1))
+            .build();
+        let expected = RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            blend: Blend::TRANSPARENCY,
+            cull: Cull::Back,
+            polygon_offset: Some((-1.0, -2.0)),
+            scissor: Some(scissor),
+            stencil: Stencil::write(1),
+        };
+        assert_eq!(built, expected);
+    }
+
+    #[test]
+    fn builder_equals_partial_struct_expression() {
+        let built = RenderStates::builder()
+            .cull(Cull::Front)
+            .depth_test(DepthTest::Greater)
+            .build();
+        let expected = RenderStates {
+            cull: Cull::Front,
+            depth_test: DepthTest::Greater,
+            ..Default::default()
+        };
+        assert_eq!(built, expected);
+    }
+}
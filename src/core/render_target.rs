@@ -26,6 +26,14 @@ mod depth_target_multisample;
 #[doc(inline)]
 pub use depth_target_multisample::*;
 
+mod pixel_readback;
+#[doc(inline)]
+pub use pixel_readback::*;
+
+mod texture_render_target;
+#[doc(inline)]
+pub use texture_render_target::*;
+
 use crate::core::*;
 
 use crate::context::Framebuffer;
@@ -37,6 +45,7 @@ use crate::context::Framebuffer;
 ///
 pub struct RenderTarget<'a> {
     id: Option<Framebuffer>,
+    owned: bool,
     color: Option<ColorTarget<'a>>,
     depth: Option<DepthTarget<'a>>,
     pub(crate) context: Context,
@@ -53,6 +62,7 @@ impl<'a> RenderTarget<'a> {
         Self {
             context: context.clone(),
             id: None,
+            owned: false,
             color: None,
             depth: None,
             width,
@@ -69,6 +79,7 @@ impl<'a> RenderTarget<'a> {
         Self {
             context: color.context.clone(),
             id: Some(new_framebuffer(&color.context)),
+            owned: true,
             color: Some(color),
             depth: Some(depth),
             width,
@@ -93,6 +104,14 @@ impl<'a> RenderTarget<'a> {
         self.clear_partially(self.scissor_box(), clear_state)
     }
 
+    ///
+    /// Clears the color and depth of this render target as defined by the default clear state of the context (see [Context::set_default_clear_state]).
+    /// Use [RenderTarget::clear] to override the default clear state for a single call.
+    ///
+    pub fn clear_default(&self) -> &Self {
+        self.clear(self.context.default_clear_state())
+    }
+
     ///
     /// Clears the color and depth of the part of this render target that is inside the given scissor box.
     ///
@@ -159,22 +178,7 @@ impl<'a> RenderTarget<'a> {
     /// - 32-bit float RGBA (Specify `T` as either `Vec4<f32>` or `[f32; 4]`) which works with any render target using `f16` or `f32` as its base type.
     ///
     pub fn read_color_partially<T: TextureDataType>(&self, scissor_box: ScissorBox) -> Vec<T> {
-        if self.id.is_some() && self.color.is_none() {
-            panic!("Cannot read color from a render target without a color target");
-        }
-        let format = format_from_data_type::<T>();
-        let data_type = T::data_type();
-
-        // On web, the read format needs to be RGBA and f16 is not supported (see https://webglfundamentals.org/webgl/lessons/webgl-readpixels.html).
-        #[cfg(target_arch = "wasm32")]
-        if format != crate::context::RGBA
-            || !(data_type == crate::context::UNSIGNED_BYTE || data_type == crate::context::FLOAT)
-        {
-            panic!("Only the texture data types `Vec4<T>` and `[T; 4]` where `T` is either `u8` or `f32` are supported when reading color from a render target on web.");
-        }
-
-        self.bind(crate::context::DRAW_FRAMEBUFFER);
-        self.bind(crate::context::READ_FRAMEBUFFER);
+        let (format, data_type) = self.color_read_format::<T>();
         let data_size = std::mem::size_of::<T>();
         let mut bytes =
             vec![0u8; scissor_box.width as usize * scissor_box.height as usize * data_size];
@@ -198,6 +202,28 @@ impl<'a> RenderTarget<'a> {
         pixels
     }
 
+    ///
+    /// Starts an asynchronous read of the colors of the pixels in this render target and returns a [PixelReadback] handle.
+    /// Contrary to [RenderTarget::read_color], this does not stall the pipeline while waiting for the GPU to finish rendering,
+    /// instead use [PixelReadback::try_get] to get the pixels when they are available, typically a frame or two later.
+    /// The same restrictions on the generic parameter `T` as for [RenderTarget::read_color] applies.
+    ///
+    pub fn read_color_async<T: TextureDataType>(&self) -> PixelReadback<T> {
+        self.read_color_partially_async(self.scissor_box())
+    }
+
+    ///
+    /// Starts an asynchronous read of the colors of the pixels in this render target inside the given scissor box.
+    /// See [RenderTarget::read_color_async] for more information.
+    ///
+    pub fn read_color_partially_async<T: TextureDataType>(
+        &self,
+        scissor_box: ScissorBox,
+    ) -> PixelReadback<T> {
+        let (format, data_type) = self.color_read_format::<T>();
+        PixelReadback::new(&self.context, scissor_box, format, data_type)
+    }
+
     ///
     /// Returns the depth values in this render target.
     ///
@@ -211,7 +237,7 @@ impl<'a> RenderTarget<'a> {
     ///
     #[cfg(not(target_arch = "wasm32"))]
     pub fn read_depth_partially(&self, scissor_box: ScissorBox) -> Vec<f32> {
-        if self.id.is_some() && self.depth.is_none() {
+        if self.color.is_some() && self.depth.is_none() {
             panic!("cannot read depth from a render target without a depth target");
         }
         self.bind(crate::context::DRAW_FRAMEBUFFER);
@@ -234,6 +260,9 @@ impl<'a> RenderTarget<'a> {
     ///
     /// Creates a [RenderTarget] with the given low-level [Framebuffer]. Should only be used if the [Framebuffer] is used for something else, ie. to be able
     /// to combine this crate with functionality of another crate. Also see [Self::into_framebuffer].
+    /// The render target takes ownership of the framebuffer, ie. the framebuffer is deleted when the render target is dropped,
+    /// unless it is transformed back into a framebuffer using [Self::into_framebuffer].
+    /// Use [Self::from_borrowed_framebuffer] for a framebuffer that is owned by someone else.
     ///
     pub fn from_framebuffer(
         context: &Context,
@@ -243,6 +272,32 @@ impl<'a> RenderTarget<'a> {
     ) -> Self {
         Self {
             id: Some(framebuffer),
+            owned: true,
+            color: None,
+            depth: None,
+            context: context.clone(),
+            width,
+            height,
+        }
+    }
+
+    ///
+    /// Creates a [RenderTarget] that renders into the given low-level [Framebuffer] with the given size, which is created and owned by someone else,
+    /// for example a host application or a GUI library that embeds the rendering of this crate.
+    /// Clearing, writing and reading works the same as for any other render target, but contrary to [Self::from_framebuffer],
+    /// the framebuffer is never deleted by this crate, so the owner is responsible for deleting it, but not before the render target is dropped.
+    /// The attachments of the framebuffer are also managed by the owner, so make sure the framebuffer is complete and has the given size,
+    /// and note that mip maps are not generated for the attached textures after writing.
+    ///
+    pub fn from_borrowed_framebuffer(
+        context: &Context,
+        width: u32,
+        height: u32,
+        framebuffer: Framebuffer,
+    ) -> Self {
+        Self {
+            id: Some(framebuffer),
+            owned: false,
             color: None,
             depth: None,
             context: context.clone(),
@@ -259,21 +314,46 @@ impl<'a> RenderTarget<'a> {
         self.id.take()
     }
 
-    pub(in crate::core) fn blit_to(&self, target: &RenderTarget) {
+    ///
+    /// Copies the content of this render target into the given target render target using the low-level blit functionality of the graphics driver.
+    /// The whole render target is copied into the whole target, so if the two render targets have different sizes, the content is scaled using the given interpolation,
+    /// for example to downsample an off-screen render target or to copy it to the screen.
+    /// Both render targets have the origin in the bottom left corner, so the content is not flipped.
+    ///
+    /// The write mask specifies whether to copy color, depth or both. The individual color channels cannot be masked, so all color channels are copied if any of them is enabled.
+    /// Only the parts that are present in both render targets are copied, where the screen and render targets created from a framebuffer (see for example [Self::from_borrowed_framebuffer])
+    /// are assumed to have both color and depth.
+    ///
+    /// **Panics:** If there is nothing to copy, ie. if neither color nor depth are enabled by the write mask and present in both render targets,
+    /// if depth is copied with another interpolation than [Interpolation::Nearest] or if the interpolation is [Interpolation::CubicSpline].
+    ///
+    pub fn blit_to(
+        &self,
+        target: &RenderTarget,
+        interpolation: Interpolation,
+        write_mask: WriteMask,
+    ) {
+        let copy_color =
+            (write_mask.red || write_mask.green || write_mask.blue || write_mask.alpha)
+                && self.has_color()
+                && target.has_color();
+        let copy_depth = write_mask.depth && self.has_depth() && target.has_depth();
+        let mask = match (copy_color, copy_depth) {
+            (true, true) => crate::context::COLOR_BUFFER_BIT | crate::context::DEPTH_BUFFER_BIT,
+            (true, false) => crate::context::COLOR_BUFFER_BIT,
+            (false, true) => crate::context::DEPTH_BUFFER_BIT,
+            (false, false) => panic!(
+                "nothing to copy, neither color nor depth is enabled by the write mask and present in both render targets"
+            ),
+        };
+        let filter = match interpolation {
+            Interpolation::Nearest => crate::context::NEAREST,
+            Interpolation::Linear if !copy_depth => crate::context::LINEAR,
+            Interpolation::Linear => panic!("depth can only be copied using nearest interpolation"),
+            _ => panic!("can only copy render targets using nearest or linear interpolation"),
+        };
         self.bind(crate::context::DRAW_FRAMEBUFFER);
         target.bind(crate::context::DRAW_FRAMEBUFFER);
-        let target_is_screen = target.color.is_none() && target.depth.is_none();
-        let mask = if self.color.is_some() && (target.color.is_some() || target_is_screen) {
-            let mut mask = crate::context::COLOR_BUFFER_BIT;
-            if self.depth.is_some() && (target.depth.is_some() || target_is_screen) {
-                mask |= crate::context::DEPTH_BUFFER_BIT;
-            }
-            mask
-        } else if self.depth.is_some() && (target.depth.is_some() || target_is_screen) {
-            crate::context::DEPTH_BUFFER_BIT
-        } else {
-            unreachable!()
-        };
         self.context
             .set_scissor(ScissorBox::new_at_origo(target.width, target.height));
         unsafe {
@@ -290,17 +370,46 @@ impl<'a> RenderTarget<'a> {
                 target.width as i32,
                 target.height as i32,
                 mask,
-                crate::context::NEAREST,
+                filter,
             );
         }
     }
 
+    fn has_color(&self) -> bool {
+        self.color.is_some() || self.depth.is_none()
+    }
+
+    fn has_depth(&self) -> bool {
+        self.depth.is_some() || self.color.is_none()
+    }
+
+    fn color_read_format<T: TextureDataType>(&self) -> (u32, u32) {
+        if self.depth.is_some() && self.color.is_none() {
+            panic!("Cannot read color from a render target without a color target");
+        }
+        let format = format_from_data_type::<T>();
+        let data_type = T::data_type();
+
+        // On web, the read format needs to be RGBA and f16 is not supported (see https://webglfundamentals.org/webgl/lessons/webgl-readpixels.html).
+        #[cfg(target_arch = "wasm32")]
+        if format != crate::context::RGBA
+            || !(data_type == crate::context::UNSIGNED_BYTE || data_type == crate::context::FLOAT)
+        {
+            panic!("Only the texture data types `Vec4<T>` and `[T; 4]` where `T` is either `u8` or `f32` are supported when reading color from a render target on web.");
+        }
+
+        self.bind(crate::context::DRAW_FRAMEBUFFER);
+        self.bind(crate::context::READ_FRAMEBUFFER);
+        (format, data_type)
+    }
+
     fn new_color(color: ColorTarget<'a>) -> Self {
         let width = color.width();
         let height = color.height();
         Self {
             context: color.context.clone(),
             id: Some(new_framebuffer(&color.context)),
+            owned: true,
             color: Some(color),
             depth: None,
             width,
@@ -314,6 +423,7 @@ impl<'a> RenderTarget<'a> {
         Self {
             context: depth.context.clone(),
             id: Some(new_framebuffer(&depth.context)),
+            owned: true,
             depth: Some(depth),
             color: None,
             width,
@@ -337,7 +447,7 @@ impl<'a> RenderTarget<'a> {
 impl Drop for RenderTarget<'_> {
     fn drop(&mut self) {
         unsafe {
-            if let Some(id) = self.id {
+            if let Some(id) = self.id.filter(|_| self.owned) {
                 self.context.delete_framebuffer(id);
             }
         }
@@ -362,12 +472,7 @@ fn new_framebuffer(context: &Context) -> crate::context::Framebuffer {
 
 #[cfg(debug_assertions)]
 fn multisample_sanity_check(context: &Context, number_of_samples: u32) {
-    let max_samples: u32 = unsafe {
-        context
-            .get_parameter_i32(crate::context::MAX_SAMPLES)
-            .try_into()
-            .unwrap()
-    };
+    let max_samples = context.max_samples();
     if number_of_samples > max_samples {
         panic!("number_of_samples ({}) for multisample target is larger than supported number of samples: {}", number_of_samples, max_samples);
     }
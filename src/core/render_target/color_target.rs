@@ -85,6 +85,7 @@ impl<'a> ColorTarget<'a> {
             scissor_box,
             ClearState {
                 depth: None,
+                stencil: None,
                 ..clear_state
             },
         );
@@ -195,7 +196,7 @@ impl<'a> ColorTarget<'a> {
             match target {
                 ColorTexture::Single(texture) => {
                     if self.mip_level.is_none() {
-                        texture.generate_mip_maps()
+                        texture.generate_mip_maps_after_write()
                     }
                 }
                 ColorTexture::Array { texture, .. } => {
@@ -18,6 +18,7 @@ impl<C: TextureDataType> ColorTargetMultisample<C> {
     ///
     /// Constructs a new multisample color target with the given dimensions and number of samples.
     /// The number of samples must be larger than 0, less than or equal to the maximum number of samples supported by the hardware and power of two.
+    /// The number of samples is validated against [Context::max_samples] in debug builds and panics if it is invalid. Typical values are 4 or 8.
     ///
     pub fn new(context: &Context, width: u32, height: u32, number_of_samples: u32) -> Self {
         #[cfg(debug_assertions)]
@@ -47,6 +48,7 @@ impl<C: TextureDataType> ColorTargetMultisample<C> {
             scissor_box,
             ClearState {
                 depth: None,
+                stencil: None,
                 ..clear_state
             },
         );
@@ -100,7 +102,11 @@ impl<C: TextureDataType> ColorTargetMultisample<C> {
     /// The target must have the same width, height and [TextureDataType] as this target.
     ///
     pub fn resolve_to(&self, target: &ColorTarget<'_>) {
-        self.as_render_target().blit_to(&target.as_render_target());
+        self.as_render_target().blit_to(
+            &target.as_render_target(),
+            Interpolation::Nearest,
+            WriteMask::COLOR_AND_DEPTH,
+        );
     }
 
     ///
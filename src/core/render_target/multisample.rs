@@ -21,6 +21,7 @@ impl<C: TextureDataType, D: DepthTextureDataType> RenderTargetMultisample<C, D>
     ///
     /// Constructs a new multisample render target with the given dimensions and number of samples.
     /// The number of samples must be larger than 0, less than or equal to the maximum number of samples supported by the hardware and power of two.
+    /// The number of samples is validated against [Context::max_samples] in debug builds and panics if it is invalid. Typical values are 4 or 8.
     ///
     pub fn new(context: &Context, width: u32, height: u32, number_of_samples: u32) -> Self {
         #[cfg(debug_assertions)]
@@ -102,7 +103,11 @@ impl<C: TextureDataType, D: DepthTextureDataType> RenderTargetMultisample<C, D>
     pub fn resolve_color_to(&self, target: &ColorTarget<'_>) {
         ColorTarget::new_texture_2d_multisample(&self.context, &self.color)
             .as_render_target()
-            .blit_to(&target.as_render_target());
+            .blit_to(
+                &target.as_render_target(),
+                Interpolation::Nearest,
+                WriteMask::COLOR_AND_DEPTH,
+            );
     }
 
     ///
@@ -112,7 +117,11 @@ impl<C: TextureDataType, D: DepthTextureDataType> RenderTargetMultisample<C, D>
     pub fn resolve_depth_to(&self, target: &DepthTarget<'_>) {
         DepthTarget::new_texture_2d_multisample(&self.context, &self.depth)
             .as_render_target()
-            .blit_to(&target.as_render_target());
+            .blit_to(
+                &target.as_render_target(),
+                Interpolation::Nearest,
+                WriteMask::COLOR_AND_DEPTH,
+            );
     }
 
     ///
@@ -121,7 +130,8 @@ impl<C: TextureDataType, D: DepthTextureDataType> RenderTargetMultisample<C, D>
     /// If the given render target is the screen render target, it must be non-multisampled or have the same number of samples as this target.
     ///
     pub fn resolve_to(&self, target: &RenderTarget<'_>) {
-        self.as_render_target().blit_to(target);
+        self.as_render_target()
+            .blit_to(target, Interpolation::Nearest, WriteMask::COLOR_AND_DEPTH);
     }
 
     ///
@@ -0,0 +1,110 @@
+use crate::core::*;
+
+///
+/// A handle to an asynchronous read of the pixels in a render target, see [RenderTarget::read_color_async].
+/// The pixels are copied into a buffer on the GPU and are only transferred to the CPU when calling [PixelReadback::try_get]
+/// after the GPU has finished the copy, which avoids stalling the pipeline.
+/// Typically, the data is available a frame or two after the read was issued.
+///
+pub struct PixelReadback<T: TextureDataType> {
+    context: Context,
+    buffer: crate::context::Buffer,
+    fence: crate::context::Fence,
+    width: u32,
+    height: u32,
+    _t: std::marker::PhantomData<T>,
+}
+
+impl<T: TextureDataType> PixelReadback<T> {
+    pub(in crate::core) fn new(
+        context: &Context,
+        scissor_box: ScissorBox,
+        format: u32,
+        data_type: u32,
+    ) -> Self {
+        let byte_size =
+            scissor_box.width as usize * scissor_box.height as usize * std::mem::size_of::<T>();
+        unsafe {
+            let buffer = context
+                .create_buffer()
+                .expect("Failed creating pixel pack buffer");
+            context.bind_buffer(crate::context::PIXEL_PACK_BUFFER, Some(buffer));
+            context.buffer_data_size(
+                crate::context::PIXEL_PACK_BUFFER,
+                byte_size as i32,
+                crate::context::STREAM_READ,
+            );
+            context.read_pixels(
+                scissor_box.x,
+                scissor_box.y,
+                scissor_box.width as i32,
+                scissor_box.height as i32,
+                format,
+                data_type,
+                crate::context::PixelPackData::BufferOffset(0),
+            );
+            context.bind_buffer(crate::context::PIXEL_PACK_BUFFER, None);
+            let fence = context
+                .fence_sync(crate::context::SYNC_GPU_COMMANDS_COMPLETE, 0)
+                .expect("Failed creating fence");
+            context.flush();
+            Self {
+                context: context.clone(),
+                buffer,
+                fence,
+                width: scissor_box.width,
+                height: scissor_box.height,
+                _t: std::marker::PhantomData,
+            }
+        }
+    }
+
+    ///
+    /// Returns true if the GPU has finished copying the pixels, ie. if [PixelReadback::try_get] will return the data.
+    ///
+    pub fn is_ready(&self) -> bool {
+        unsafe { self.context.get_sync_status(self.fence) == crate::context::SIGNALED }
+    }
+
+    ///
+    /// Returns the pixels if the GPU has finished copying them, otherwise `None` is returned and this method should be called again later, for example next frame.
+    /// The pixels are returned in the same order as [RenderTarget::read_color].
+    ///
+    pub fn try_get(&self) -> Option<Vec<T>> {
+        if !self.is_ready() {
+            return None;
+        }
+        let mut bytes =
+            vec![0u8; self.width as usize * self.height as usize * std::mem::size_of::<T>()];
+        unsafe {
+            self.context
+                .bind_buffer(crate::context::PIXEL_PACK_BUFFER, Some(self.buffer));
+            self.context
+                .get_buffer_sub_data(crate::context::PIXEL_PACK_BUFFER, 0, &mut bytes);
+            self.context
+                .bind_buffer(crate::context::PIXEL_PACK_BUFFER, None);
+        }
+        let mut pixels = from_byte_slice(&bytes).to_vec();
+        flip_y(&mut pixels, self.width as usize, self.height as usize);
+        Some(pixels)
+    }
+
+    /// The width of the area that is read.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the area that is read.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+impl<T: TextureDataType> Drop for PixelReadback<T> {
+    fn drop(&mut self) {
+        unsafe {
+            self.context.delete_sync(self.fence);
+            self.context.delete_buffer(self.buffer);
+        }
+    }
+}
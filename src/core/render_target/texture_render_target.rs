@@ -0,0 +1,92 @@
+use crate::core::*;
+
+///
+/// A color texture and a depth texture of the same size which can be rendered into using [TextureRenderTarget::as_render_target]
+/// and afterwards, for example, used as input to an effect using [TextureRenderTarget::color_texture] and [TextureRenderTarget::depth_texture].
+/// The format of the color texture is given by the [TextureDataType] `C` and the format of the depth texture by the [DepthTextureDataType] `D`.
+///
+pub struct TextureRenderTarget<C: TextureDataType, D: DepthTextureDataType> {
+    context: Context,
+    color: Texture2D,
+    depth: DepthTexture2D,
+    _c: std::marker::PhantomData<C>,
+    _d: std::marker::PhantomData<D>,
+}
+
+impl<C: TextureDataType, D: DepthTextureDataType> TextureRenderTarget<C, D> {
+    ///
+    /// Constructs a new color and depth texture with the given dimensions.
+    /// The textures are sampled with nearest interpolation and clamped to the edge.
+    ///
+    pub fn new(context: &Context, width: u32, height: u32) -> Self {
+        Self {
+            context: context.clone(),
+            color: Texture2D::new_empty::<C>(
+                context,
+                width,
+                height,
+                Interpolation::Nearest,
+                Interpolation::Nearest,
+                None,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+            ),
+            depth: DepthTexture2D::new::<D>(
+                context,
+                width,
+                height,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+            ),
+            _c: std::marker::PhantomData,
+            _d: std::marker::PhantomData,
+        }
+    }
+
+    ///
+    /// Recreates the textures with the given dimensions if they differ from the current dimensions, for example when the viewport has changed.
+    /// Returns true if the textures were recreated, in which case the content of the textures is lost.
+    ///
+    pub fn resize(&mut self, width: u32, height: u32) -> bool {
+        if width != self.width() || height != self.height() {
+            *self = Self::new(&self.context, width, height);
+            true
+        } else {
+            false
+        }
+    }
+
+    ///
+    /// Returns a [RenderTarget] which can be used to clear, write to and read from the color and depth textures.
+    ///
+    pub fn as_render_target(&mut self) -> RenderTarget<'_> {
+        RenderTarget::new(
+            self.color.as_color_target(None),
+            self.depth.as_depth_target(),
+        )
+    }
+
+    ///
+    /// Returns the color texture, for example to use as input to an effect.
+    ///
+    pub fn color_texture(&self) -> ColorTexture<'_> {
+        ColorTexture::Single(&self.color)
+    }
+
+    ///
+    /// Returns the depth texture, for example to use as input to an effect.
+    ///
+    pub fn depth_texture(&self) -> DepthTexture<'_> {
+        DepthTexture::Single(&self.depth)
+    }
+
+    /// The width of the textures.
+    pub fn width(&self) -> u32 {
+        self.color.width()
+    }
+
+    /// The height of the textures.
+    pub fn height(&self) -> u32 {
+        self.color.height()
+    }
+}
@@ -7,6 +7,16 @@ float saturate(in float value)
     return clamp(value, 0.0, 1.0);
 }
 
+// Encodes a unit length normal into a color where each component is in the 0 - 1 range
+vec3 encode_normal(vec3 normal) {
+    return 0.5 * normal + 0.5;
+}
+
+// Decodes a normal which is encoded using encode_normal
+vec3 decode_normal(vec3 color) {
+    return normalize(2.0 * color - 1.0);
+}
+
 vec3 world_pos_from_depth(mat4 viewProjectionInverse, float depth, vec2 uv) {
     vec4 clipSpacePosition = vec4(uv * 2.0 - 1.0, depth * 2.0 - 1.0, 1.0);
     vec4 position = viewProjectionInverse * clipSpacePosition;
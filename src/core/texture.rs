@@ -37,6 +37,10 @@ mod depth_texture2d_multisample;
 #[doc(inline)]
 pub(in crate::core) use depth_texture2d_multisample::*;
 
+mod compressed_format;
+#[doc(inline)]
+pub use compressed_format::*;
+
 use data_type::*;
 pub use three_d_asset::texture::{
     Interpolation, Texture2D as CpuTexture, Texture3D as CpuTexture3D, TextureData, Wrapping,
@@ -67,9 +71,17 @@ pub trait DepthTextureDataType: DepthDataType {}
 #[derive(Clone, Copy, Default, Debug)]
 pub struct f24 {}
 
+///
+/// 24 bit float depth combined with an 8 bit stencil value which can be used as [DepthTextureDataType].
+/// Use this when rendering into a depth texture together with a stencil test, see [RenderStates::stencil].
+///
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Depth24Stencil8 {}
+
 impl DepthTextureDataType for f16 {}
 impl DepthTextureDataType for f24 {}
 impl DepthTextureDataType for f32 {}
+impl DepthTextureDataType for Depth24Stencil8 {}
 
 ///
 /// A reference to some type of texture containing colors.
@@ -271,6 +283,40 @@ impl DepthTexture<'_> {
     }
 }
 
+///
+/// Multiplies the red, green and blue channels of each pixel in the given texture with the alpha channel of that pixel.
+/// Textures with premultiplied alpha avoid dark halos at the edges of transparent regions when the texture is filtered
+/// and should be rendered with [Blend::PREMULTIPLIED_ALPHA] blending.
+/// Textures without an alpha channel are left unchanged.
+///
+/// **Note:** The premultiplication should happen in linear color space, so if the texture contains sRGB encoded colors,
+/// for example when loaded from a PNG file, call `cpu_texture.data.to_linear_srgb()` before this function.
+/// Alternatively, load the texture using [crate::io::load_texture] with premultiplied alpha, which handles sRGB encoded colors.
+///
+pub fn premultiply_alpha(cpu_texture: &mut CpuTexture) {
+    match cpu_texture.data {
+        TextureData::RgbaU8(ref mut data) => data.iter_mut().for_each(|c| {
+            let a = c[3] as f32 / 255.0;
+            for channel in c.iter_mut().take(3) {
+                *channel = (*channel as f32 * a).round() as u8;
+            }
+        }),
+        TextureData::RgbaF16(ref mut data) => data.iter_mut().for_each(|c| {
+            let a = c[3].to_f32();
+            for channel in c.iter_mut().take(3) {
+                *channel = f16::from_f32(channel.to_f32() * a);
+            }
+        }),
+        TextureData::RgbaF32(ref mut data) => data.iter_mut().for_each(|c| {
+            let a = c[3];
+            for channel in c.iter_mut().take(3) {
+                *channel *= a;
+            }
+        }),
+        _ => {}
+    }
+}
+
 use crate::core::*;
 
 // COMMON TEXTURE FUNCTIONS
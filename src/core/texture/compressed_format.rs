@@ -0,0 +1,128 @@
+use crate::core::*;
+
+///
+/// A GPU compressed texture format, see [Texture2D::new_compressed].
+/// The data is divided into blocks of pixels where each block is compressed into a fixed number of bytes.
+/// Use [CompressedFormat::is_supported] to check whether the format is supported by the current graphics driver.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CompressedFormat {
+    /// BC1 (also known as DXT1) with RGB colors and a 1 bit alpha channel. Usually supported on desktop.
+    Bc1,
+    /// BC3 (also known as DXT5) with RGBA colors. Usually supported on desktop.
+    Bc3,
+    /// BC5 (also known as RGTC2) with two channels, for example for normal maps. Usually supported on desktop.
+    Bc5,
+    /// BC7 (also known as BPTC) with high quality RGBA colors. Usually supported on desktop.
+    Bc7,
+    /// ETC2 with RGB colors. Usually supported on mobile.
+    Etc2Rgb,
+    /// ETC2 with RGBA colors. Usually supported on mobile.
+    Etc2Rgba,
+    /// ASTC with RGBA colors and a block size of 4x4 pixels. Usually supported on mobile.
+    Astc4x4,
+    /// ASTC with RGBA colors and a block size of 6x6 pixels. Usually supported on mobile.
+    Astc6x6,
+    /// ASTC with RGBA colors and a block size of 8x8 pixels. Usually supported on mobile.
+    Astc8x8,
+}
+
+impl CompressedFormat {
+    ///
+    /// Returns the width and height in pixels of each compressed block.
+    ///
+    pub fn block_size(&self) -> (u32, u32) {
+        match self {
+            Self::Astc6x6 => (6, 6),
+            Self::Astc8x8 => (8, 8),
+            _ => (4, 4),
+        }
+    }
+
+    ///
+    /// Returns the number of bytes used for each compressed block.
+    ///
+    pub fn block_byte_size(&self) -> usize {
+        match self {
+            Self::Bc1 | Self::Etc2Rgb => 8,
+            _ => 16,
+        }
+    }
+
+    ///
+    /// Returns the number of bytes needed for compressed data with the given width and height in pixels.
+    ///
+    pub fn data_byte_size(&self, width: u32, height: u32) -> usize {
+        let (block_width, block_height) = self.block_size();
+        // Rounded up, since partial blocks at the right and top edges take up a whole block
+        let blocks_x = (width + (block_width - width % block_width) % block_width) / block_width;
+        let blocks_y =
+            (height + (block_height - height % block_height) % block_height) / block_height;
+        blocks_x as usize * blocks_y as usize * self.block_byte_size()
+    }
+
+    ///
+    /// Returns whether or not this format is supported by the graphics driver of the given context.
+    ///
+    pub fn is_supported(&self, context: &Context) -> bool {
+        let extensions = context.supported_extensions();
+        self.extensions()
+            .iter()
+            .any(|extension| extensions.contains(*extension))
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::Bc1 | Self::Bc3 => &[
+                "GL_EXT_texture_compression_s3tc",
+                "WEBGL_compressed_texture_s3tc",
+            ],
+            Self::Bc5 => &[
+                "GL_ARB_texture_compression_rgtc",
+                "GL_EXT_texture_compression_rgtc",
+                "EXT_texture_compression_rgtc",
+            ],
+            Self::Bc7 => &[
+                "GL_ARB_texture_compression_bptc",
+                "GL_EXT_texture_compression_bptc",
+                "EXT_texture_compression_bptc",
+            ],
+            Self::Etc2Rgb | Self::Etc2Rgba => &[
+                "GL_ARB_ES3_compatibility",
+                "GL_OES_compressed_ETC2_RGB8_texture",
+                "WEBGL_compressed_texture_etc",
+            ],
+            Self::Astc4x4 | Self::Astc6x6 | Self::Astc8x8 => &[
+                "GL_KHR_texture_compression_astc_ldr",
+                "WEBGL_compressed_texture_astc",
+            ],
+        }
+    }
+
+    pub(in crate::core) fn internal_format(&self) -> u32 {
+        match self {
+            Self::Bc1 => crate::context::COMPRESSED_RGBA_S3TC_DXT1_EXT,
+            Self::Bc3 => crate::context::COMPRESSED_RGBA_S3TC_DXT5_EXT,
+            Self::Bc5 => crate::context::COMPRESSED_RG_RGTC2,
+            Self::Bc7 => crate::context::COMPRESSED_RGBA_BPTC_UNORM,
+            Self::Etc2Rgb => crate::context::COMPRESSED_RGB8_ETC2,
+            Self::Etc2Rgba => crate::context::COMPRESSED_RGBA8_ETC2_EAC,
+            Self::Astc4x4 => crate::context::COMPRESSED_RGBA_ASTC_4x4_KHR,
+            Self::Astc6x6 => crate::context::COMPRESSED_RGBA_ASTC_6x6_KHR,
+            Self::Astc8x8 => crate::context::COMPRESSED_RGBA_ASTC_8x8_KHR,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_byte_size_rounds_up_to_whole_blocks() {
+        assert_eq!(CompressedFormat::Bc1.data_byte_size(4, 4), 8);
+        assert_eq!(CompressedFormat::Bc1.data_byte_size(5, 4), 16);
+        assert_eq!(CompressedFormat::Bc7.data_byte_size(1, 1), 16);
+        assert_eq!(CompressedFormat::Astc6x6.data_byte_size(13, 12), 3 * 2 * 16);
+    }
+}
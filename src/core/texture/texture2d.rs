@@ -10,11 +10,15 @@ pub struct Texture2D {
     height: u32,
     number_of_mip_maps: u32,
     data_byte_size: usize,
+    compressed_format: Option<CompressedFormat>,
+    explicit_mip_maps: bool,
 }
 
 impl Texture2D {
     ///
     /// Construcs a new texture with the given data.
+    /// The data is sampled as is, so if the data contains sRGB encoded colors which should be used in lighting calculations, use [Texture2D::new_srgb] instead.
+    /// Single channel data, for example [TextureData::RF32], is stored in a single channel texture (for example `R32F`) and is sampled in the red channel.
     ///
     /// **Note:** Mip maps will not be generated for RGB16F and RGB32F format, even if `mip_map_filter` is specified.
     ///
@@ -35,6 +39,16 @@ impl Texture2D {
         }
     }
 
+    ///
+    /// Construcs a new texture with the given data where the color channels are premultiplied with the alpha channel before the data is transferred to the GPU.
+    /// See [premultiply_alpha] for more information.
+    ///
+    pub fn new_with_premultiplied_alpha(context: &Context, cpu_texture: &CpuTexture) -> Self {
+        let mut cpu_texture = cpu_texture.clone();
+        premultiply_alpha(&mut cpu_texture);
+        Self::new(context, &cpu_texture)
+    }
+
     fn new_with_data<T: TextureDataType>(
         context: &Context,
         cpu_texture: &CpuTexture,
@@ -58,6 +72,8 @@ impl Texture2D {
     /// Constructs a new empty 2D texture with the given parameters.
     /// The format is determined by the generic [TextureDataType] parameter
     /// (for example, if [u8; 4] is specified, the format is RGBA and the data type is byte).
+    /// Specify [Interpolation::Linear] as both the minification and mip map filter to get trilinear filtering,
+    /// which reduces shimmering when the texture is viewed at a distance.
     ///
     /// **Note:** Mip maps will not be generated for RGB16F and RGB32F format, even if `mip_map_filter` is specified.
     ///
@@ -70,6 +86,61 @@ impl Texture2D {
         mip_map_filter: Option<Interpolation>,
         wrap_s: Wrapping,
         wrap_t: Wrapping,
+    ) -> Self {
+        Self::new_empty_with_internal_format::<T>(
+            context,
+            width,
+            height,
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            T::internal_format(),
+        )
+    }
+
+    ///
+    /// Constructs a new texture from the given data, where 8-bit RGB and RGBA data ([TextureData::RgbU8] and [TextureData::RgbaU8]) is interpreted as sRGB encoded colors,
+    /// for example the colors in a PNG or JPEG image.
+    /// The colors are stored in an sRGB texture format (`SRGB8_ALPHA8`) and decoded to linear sRGB by the GPU when the texture is sampled,
+    /// which is more precise than converting the data to linear sRGB before constructing the texture with [Texture2D::new].
+    /// Any other data is handled as in [Texture2D::new], ie. assumed to be in linear color space.
+    ///
+    /// Use this for textures containing colors, like albedo and emissive textures, but not for textures containing other data, like normal maps or roughness maps.
+    /// The alpha channel is never decoded. Note that filtering and mip map generation of sRGB textures happen in linear color space.
+    ///
+    pub fn new_srgb(context: &Context, cpu_texture: &CpuTexture) -> Self {
+        let data = match cpu_texture.data {
+            TextureData::RgbU8(ref data) => data.iter().map(|c| [c[0], c[1], c[2], 255]).collect(),
+            TextureData::RgbaU8(ref data) => data.clone(),
+            _ => return Self::new(context, cpu_texture),
+        };
+        let mut texture = Self::new_empty_with_internal_format::<[u8; 4]>(
+            context,
+            cpu_texture.width,
+            cpu_texture.height,
+            cpu_texture.min_filter,
+            cpu_texture.mag_filter,
+            cpu_texture.mip_map_filter,
+            cpu_texture.wrap_s,
+            cpu_texture.wrap_t,
+            crate::context::SRGB8_ALPHA8,
+        );
+        texture.fill(&data);
+        texture
+    }
+
+    fn new_empty_with_internal_format<T: TextureDataType>(
+        context: &Context,
+        width: u32,
+        height: u32,
+        min_filter: Interpolation,
+        mag_filter: Interpolation,
+        mip_map_filter: Option<Interpolation>,
+        wrap_s: Wrapping,
+        wrap_t: Wrapping,
+        internal_format: u32,
     ) -> Self {
         let id = generate(context);
         let number_of_mip_maps =
@@ -81,6 +152,8 @@ impl Texture2D {
             height,
             number_of_mip_maps,
             data_byte_size: std::mem::size_of::<T>(),
+            compressed_format: None,
+            explicit_mip_maps: false,
         };
         texture.bind();
         set_parameters(
@@ -101,7 +174,7 @@ impl Texture2D {
             context.tex_storage_2d(
                 crate::context::TEXTURE_2D,
                 number_of_mip_maps as i32,
-                T::internal_format(),
+                internal_format,
                 width as i32,
                 height as i32,
             );
@@ -110,14 +183,201 @@ impl Texture2D {
         texture
     }
 
+    ///
+    /// Constructs a new 2D texture where each mip level is given explicitly instead of being generated, for example if the mip levels are precomputed by an artist or a tool.
+    /// The first element in `mip_maps` is the data for the base level with the given width and height and each of the following elements
+    /// is the data for the next mip level, which has half the width and height of the previous level (rounded down, but at least one).
+    /// Not all mip levels down to a size of one pixel need to be given.
+    /// The format is determined by the generic [TextureDataType] parameter.
+    ///
+    /// # Panic
+    /// Will panic if no mip levels are given, if more mip levels are given than is possible for the given size or
+    /// if the length of the data for a mip level does not correspond to the size of that level.
+    ///
+    pub fn new_with_mip_maps<T: TextureDataType>(
+        context: &Context,
+        mip_maps: &[&[T]],
+        width: u32,
+        height: u32,
+        min_filter: Interpolation,
+        mag_filter: Interpolation,
+        mip_map_filter: Interpolation,
+        wrap_s: Wrapping,
+        wrap_t: Wrapping,
+    ) -> Self {
+        let number_of_mip_maps = check_number_of_mip_maps(width, height, mip_maps.len());
+        let id = generate(context);
+        let texture = Self {
+            context: context.clone(),
+            id,
+            width,
+            height,
+            number_of_mip_maps,
+            data_byte_size: std::mem::size_of::<T>(),
+            compressed_format: None,
+            explicit_mip_maps: true,
+        };
+        texture.bind();
+        set_parameters(
+            context,
+            crate::context::TEXTURE_2D,
+            min_filter,
+            mag_filter,
+            if number_of_mip_maps == 1 {
+                None
+            } else {
+                Some(mip_map_filter)
+            },
+            wrap_s,
+            wrap_t,
+            None,
+        );
+        unsafe {
+            context.tex_storage_2d(
+                crate::context::TEXTURE_2D,
+                number_of_mip_maps as i32,
+                T::internal_format(),
+                width as i32,
+                height as i32,
+            );
+        }
+        for (level, data) in mip_maps.iter().enumerate() {
+            let level_width = (width >> level).max(1);
+            let level_height = (height >> level).max(1);
+            check_data_length::<T>(
+                level_width,
+                level_height,
+                1,
+                texture.data_byte_size,
+                data.len(),
+            );
+            let mut data = data.to_vec();
+            flip_y(&mut data, level_width as usize, level_height as usize);
+            unsafe {
+                context.tex_sub_image_2d(
+                    crate::context::TEXTURE_2D,
+                    level as i32,
+                    0,
+                    0,
+                    level_width as i32,
+                    level_height as i32,
+                    format_from_data_type::<T>(),
+                    T::data_type(),
+                    crate::context::PixelUnpackData::Slice(to_byte_slice(&data)),
+                );
+            }
+        }
+        texture
+    }
+
+    ///
+    /// Constructs a new 2D texture from data compressed in the given [CompressedFormat].
+    /// The first element in `mip_maps` is the compressed data for the base level with the given width and height and each of the following elements
+    /// is the compressed data for the next mip level, as described in [Texture2D::new_with_mip_maps].
+    /// Compressed textures can only be sampled, ie. it is not possible to [fill](Texture2D::fill) them, render to them or [generate mip maps](Texture2D::generate_mip_maps) for them.
+    ///
+    /// **Note:** In contrast to the other constructors, the data is not flipped vertically before it is transferred to the GPU,
+    /// since the pixels inside each compressed block cannot be flipped without decompressing the block.
+    /// The uv coordinates assume that the first row of the texture is the bottom row, so the data must be compressed from an image which is flipped vertically,
+    /// which is usually an option in texture compression tools, otherwise the texture is sampled upside down.
+    ///
+    /// Returns an error if the format is not supported by the graphics driver, see [CompressedFormat::is_supported].
+    ///
+    /// # Panic
+    /// Will panic if no mip levels are given, if more mip levels are given than is possible for the given size or
+    /// if the length of the data for a mip level does not correspond to the size of that level.
+    ///
+    pub fn new_compressed(
+        context: &Context,
+        format: CompressedFormat,
+        mip_maps: &[&[u8]],
+        width: u32,
+        height: u32,
+        min_filter: Interpolation,
+        mag_filter: Interpolation,
+        mip_map_filter: Interpolation,
+        wrap_s: Wrapping,
+        wrap_t: Wrapping,
+    ) -> Result<Self, CoreError> {
+        if !format.is_supported(context) {
+            return Err(CoreError::UnsupportedCompressedFormat(format));
+        }
+        let number_of_mip_maps = check_number_of_mip_maps(width, height, mip_maps.len());
+        let id = generate(context);
+        let texture = Self {
+            context: context.clone(),
+            id,
+            width,
+            height,
+            number_of_mip_maps,
+            data_byte_size: 0,
+            compressed_format: Some(format),
+            explicit_mip_maps: true,
+        };
+        texture.bind();
+        set_parameters(
+            context,
+            crate::context::TEXTURE_2D,
+            min_filter,
+            mag_filter,
+            if number_of_mip_maps == 1 {
+                None
+            } else {
+                Some(mip_map_filter)
+            },
+            wrap_s,
+            wrap_t,
+            None,
+        );
+        unsafe {
+            context.tex_storage_2d(
+                crate::context::TEXTURE_2D,
+                number_of_mip_maps as i32,
+                format.internal_format(),
+                width as i32,
+                height as i32,
+            );
+        }
+        for (level, data) in mip_maps.iter().enumerate() {
+            let level_width = (width >> level).max(1);
+            let level_height = (height >> level).max(1);
+            let expected_bytes = format.data_byte_size(level_width, level_height);
+            if expected_bytes != data.len() {
+                panic!(
+                    "invalid size of compressed texture data for mip level {} (expected {} bytes but got {} bytes)",
+                    level,
+                    expected_bytes,
+                    data.len()
+                )
+            }
+            unsafe {
+                context.compressed_tex_sub_image_2d(
+                    crate::context::TEXTURE_2D,
+                    level as i32,
+                    0,
+                    0,
+                    level_width as i32,
+                    level_height as i32,
+                    format.internal_format(),
+                    crate::context::CompressedPixelUnpackData::Slice(data),
+                );
+            }
+        }
+        Ok(texture)
+    }
+
     ///
     /// Fills this texture with the given data.
+    /// The mip maps are regenerated from the new data, unless the mip levels were given explicitly at construction (see [Texture2D::new_with_mip_maps]),
+    /// in which case only the base level is replaced and the other levels are kept.
     ///
     /// # Panic
     /// Will panic if the length of the data does not correspond to the width, height and format specified at construction.
     /// It is therefore necessary to create a new texture if the texture size or format has changed.
+    /// Will also panic if the texture is compressed (see [Texture2D::new_compressed]).
     ///
     pub fn fill<T: TextureDataType>(&mut self, data: &[T]) {
+        self.check_not_compressed("fill");
         check_data_length::<T>(self.width, self.height, 1, self.data_byte_size, data.len());
         self.bind();
         let mut data = data.to_owned();
@@ -135,21 +395,115 @@ impl Texture2D {
                 crate::context::PixelUnpackData::Slice(to_byte_slice(&data)),
             );
         }
-        self.generate_mip_maps();
+        self.generate_mip_maps_after_write();
+    }
+
+    ///
+    /// Fills the rectangular region of this texture with the given position and size with the given data, while the rest of the texture is left unchanged.
+    /// The position `(x, y)` is the top left corner of the region measured in pixels from the top left corner of the texture
+    /// and the data is ordered row by row from the top of the region, ie. in the same way as for [Texture2D::fill].
+    /// As for [Texture2D::fill], the mip maps are only regenerated if the mip levels were not given explicitly at construction.
+    ///
+    /// # Panic
+    /// Will panic if the region is not inside the texture or if the length of the data does not correspond to the size of the region and the format specified at construction.
+    /// Will also panic if the texture is compressed (see [Texture2D::new_compressed]).
+    ///
+    pub fn fill_subregion<T: TextureDataType>(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        data: &[T],
+    ) {
+        self.check_not_compressed("fill_subregion");
+        let right = x.checked_add(width).filter(|right| *right <= self.width);
+        let bottom = y
+            .checked_add(height)
+            .filter(|bottom| *bottom <= self.height);
+        if right.is_none() || bottom.is_none() {
+            panic!(
+                "the region at ({}, {}) of size {}x{} is outside the texture of size {}x{}",
+                x, y, width, height, self.width, self.height
+            );
+        }
+        check_data_length::<T>(width, height, 1, self.data_byte_size, data.len());
+        self.bind();
+        let mut data = data.to_owned();
+        flip_y(&mut data, width as usize, height as usize);
+        unsafe {
+            self.context.tex_sub_image_2d(
+                crate::context::TEXTURE_2D,
+                0,
+                x as i32,
+                (self.height - y - height) as i32,
+                width as i32,
+                height as i32,
+                format_from_data_type::<T>(),
+                T::data_type(),
+                crate::context::PixelUnpackData::Slice(to_byte_slice(&data)),
+            );
+        }
+        self.generate_mip_maps_after_write();
     }
 
     ///
     /// Returns a [ColorTarget] which can be used to clear, write to and read from the given mip level of this texture.
     /// Combine this together with a [DepthTarget] with [RenderTarget::new] to be able to write to both a depth and color target at the same time.
-    /// If `None` is specified as the mip level, the 0 level mip level is used and mip maps are generated after a write operation if a mip map filter is specified.
+    /// If `None` is specified as the mip level, the 0 level mip level is used and mip maps are generated after a write operation if a mip map filter is specified
+    /// and the mip levels were not given explicitly at construction (see [Texture2D::new_with_mip_maps]).
     /// Otherwise, the given mip level is used and no mip maps are generated.
     ///
     /// **Note:** [DepthTest] is disabled if not also writing to a depth texture.
     ///
+    /// # Panic
+    /// Will panic if the texture is compressed (see [Texture2D::new_compressed]).
+    ///
     pub fn as_color_target(&mut self, mip_level: Option<u32>) -> ColorTarget<'_> {
+        self.check_not_compressed("render to");
         ColorTarget::new_texture2d(&self.context, self, mip_level)
     }
 
+    ///
+    /// Sets how the texture is sampled outside the [0, 1] range of the uv coordinates in the horizontal (s) and vertical (t) direction,
+    /// without having to recreate the texture.
+    ///
+    pub fn set_wrapping(&mut self, wrap_s: Wrapping, wrap_t: Wrapping) {
+        self.bind();
+        unsafe {
+            self.context.tex_parameter_i32(
+                crate::context::TEXTURE_2D,
+                crate::context::TEXTURE_WRAP_S,
+                wrapping_from(wrap_s),
+            );
+            self.context.tex_parameter_i32(
+                crate::context::TEXTURE_2D,
+                crate::context::TEXTURE_WRAP_T,
+                wrapping_from(wrap_t),
+            );
+        }
+    }
+
+    ///
+    /// Sets the anisotropy used when sampling this texture, which makes the texture look sharper when viewed at a grazing angle.
+    /// The anisotropy is clamped to the range between 1, which disables anisotropic filtering, and [Context::max_anisotropy].
+    /// If anisotropic filtering is not supported, this does nothing and the texture is sampled using the specified minification and mip map filters.
+    /// For the best result, combine with linear minification and mip map filters.
+    ///
+    pub fn set_anisotropy(&mut self, anisotropy: f32) {
+        let max_anisotropy = self.context.max_anisotropy();
+        if max_anisotropy > 1.0 {
+            self.bind();
+            unsafe {
+                self.context.tex_parameter_f32(
+                    crate::context::TEXTURE_2D,
+                    crate::context::TEXTURE_MAX_ANISOTROPY_EXT,
+                    anisotropy.clamp(1.0, max_anisotropy),
+                );
+            }
+        }
+    }
+
     /// The width of this texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -160,8 +514,28 @@ impl Texture2D {
         self.height
     }
 
-    pub(crate) fn generate_mip_maps(&self) {
+    ///
+    /// Returns the number of mip map levels of this texture, which is 1 if no mip map filter was specified at construction.
+    ///
+    pub fn number_of_mip_maps(&self) -> u32 {
+        self.number_of_mip_maps
+    }
+
+    ///
+    /// Generates the mip maps of this texture from the content of the base level.
+    /// This is done automatically when filling the texture or after writing to it using [Texture2D::as_color_target] with `None` as mip level,
+    /// so it is only necessary to call this after modifying the base level in other ways, for example after writing to it with an explicit mip level.
+    /// The exception is a texture constructed with explicit mip levels (see [Texture2D::new_with_mip_maps]), where the given levels are never overwritten automatically,
+    /// so calling this is the only way to replace them with generated mip maps.
+    /// Does nothing if no mip map filter was specified at construction.
+    /// The texture size does not need to be a power of two, since mip maps of any size are supported on all supported platforms (OpenGL 3.3+, OpenGL ES 3.0+ and WebGL 2).
+    ///
+    /// # Panic
+    /// Will panic if the texture is compressed (see [Texture2D::new_compressed]) and has more than one mip level, since mip maps cannot be generated for compressed formats.
+    ///
+    pub fn generate_mip_maps(&self) {
         if self.number_of_mip_maps > 1 {
+            self.check_not_compressed("generate mip maps for");
             self.bind();
             unsafe {
                 self.context.generate_mipmap(crate::context::TEXTURE_2D);
@@ -169,6 +543,28 @@ impl Texture2D {
         }
     }
 
+    ///
+    /// Returns the format of this texture if it is compressed (see [Texture2D::new_compressed]), otherwise `None`.
+    ///
+    pub fn compressed_format(&self) -> Option<CompressedFormat> {
+        self.compressed_format
+    }
+
+    pub(in crate::core) fn generate_mip_maps_after_write(&self) {
+        if !self.explicit_mip_maps {
+            self.generate_mip_maps();
+        }
+    }
+
+    fn check_not_compressed(&self, operation: &str) {
+        if let Some(format) = self.compressed_format {
+            panic!(
+                "cannot {} a texture compressed in the {:?} format",
+                operation, format
+            );
+        }
+    }
+
     pub(in crate::core) fn bind_as_color_target(&self, channel: u32, mip_level: u32) {
         unsafe {
             self.context.framebuffer_texture_2d(
@@ -195,3 +591,14 @@ impl Drop for Texture2D {
         }
     }
 }
+
+fn check_number_of_mip_maps(width: u32, height: u32, number_of_mip_maps: usize) -> u32 {
+    let max_number_of_mip_maps = 32 - width.max(height).max(1).leading_zeros();
+    if number_of_mip_maps == 0 || number_of_mip_maps > max_number_of_mip_maps as usize {
+        panic!(
+            "invalid number of mip levels (expected between 1 and {} but got {})",
+            max_number_of_mip_maps, number_of_mip_maps
+        )
+    }
+    number_of_mip_maps as u32
+}
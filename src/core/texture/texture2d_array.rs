@@ -266,6 +266,25 @@ impl Texture2DArray {
         ColorTarget::new_texture_2d_array(&self.context, self, layers, mip_level)
     }
 
+    ///
+    /// Returns the colors of the pixels in the given layer of this texture array.
+    /// The number of channels per pixel and the data format for each channel returned from this function is specified by the generic parameter `T`
+    /// and the same restrictions as for [ColorTarget::read] applies.
+    ///
+    /// # Panic
+    /// Will panic if the layer is not in the range `[0, depth)`.
+    ///
+    pub fn read_layer<T: TextureDataType>(&self, layer: u32) -> Vec<T> {
+        if layer >= self.depth {
+            panic!(
+                "cannot read the layer {} since there are only {} layers in the texture array",
+                layer, self.depth
+            )
+        }
+        let layers = [layer];
+        ColorTarget::new_texture_2d_array(&self.context, self, &layers, None).read()
+    }
+
     /// The width of this texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -120,6 +120,8 @@ impl TextureCubeMap {
     ///
     /// **Note:** Mip maps will not be generated for RGB16F and RGB32F format, even if `mip_map_filter` is specified.
     ///
+    /// **Panics:** If the six cpu textures do not have the same width, height and [TextureData] format.
+    ///
     pub fn new(
         context: &Context,
         right: &CpuTexture,
@@ -129,6 +131,7 @@ impl TextureCubeMap {
         front: &CpuTexture,
         back: &CpuTexture,
     ) -> Self {
+        check_sides(right, left, top, bottom, front, back);
         match &front.data {
             TextureData::RU8(front_data) => Self::new_with_data(
                 context,
@@ -571,3 +574,71 @@ impl Drop for TextureCubeMap {
         }
     }
 }
+
+fn check_sides(
+    right: &CpuTexture,
+    left: &CpuTexture,
+    top: &CpuTexture,
+    bottom: &CpuTexture,
+    front: &CpuTexture,
+    back: &CpuTexture,
+) {
+    for (side, texture) in [
+        ("right", right),
+        ("left", left),
+        ("top", top),
+        ("bottom", bottom),
+        ("back", back),
+    ] {
+        if texture.width != front.width || texture.height != front.height {
+            panic!(
+                "all of the images used for cube map sides must have the same size, the {} side is {}x{} while the front side is {}x{}",
+                side, texture.width, texture.height, front.width, front.height
+            );
+        }
+        if std::mem::discriminant(&texture.data) != std::mem::discriminant(&front.data) {
+            panic!(
+                "all of the images used for cube map sides must have the same texture data type, the {} side differs from the front side",
+                side
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn side(width: u32, height: u32) -> CpuTexture {
+        CpuTexture {
+            data: TextureData::RgbaU8(vec![[0, 0, 0, 255]; (width * height) as usize]),
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_sides() {
+        let s = side(2, 2);
+        check_sides(&s, &s, &s, &s, &s, &s);
+    }
+
+    #[test]
+    #[should_panic(expected = "the top side is 2x1 while the front side is 2x2")]
+    fn different_side_size() {
+        let s = side(2, 2);
+        check_sides(&s, &s, &side(2, 1), &s, &s, &s);
+    }
+
+    #[test]
+    #[should_panic(expected = "the back side differs from the front side")]
+    fn different_side_format() {
+        let s = side(2, 2);
+        let back = CpuTexture {
+            data: TextureData::RgbaF32(vec![[0.0; 4]; 4]),
+            ..side(2, 2)
+        };
+        check_sides(&s, &s, &s, &s, &s, &back);
+    }
+}
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+///
+/// An error from any of the modules in this crate, which makes it possible to use `?` on results from different modules in the same function.
+/// Each variant wraps the error type of a module and can be constructed from that error type using [From], for example from a [CoreError](crate::core::CoreError).
+///
+#[derive(Error, Debug)]
+#[allow(missing_docs)]
+pub enum ThreeDError {
+    #[error(transparent)]
+    Core(#[from] crate::core::CoreError),
+    #[error(transparent)]
+    Renderer(#[from] crate::renderer::RendererError),
+    #[error(transparent)]
+    Asset(#[from] three_d_asset::Error),
+    #[cfg(feature = "window")]
+    #[error(transparent)]
+    Window(#[from] crate::window::WindowError),
+    #[cfg(all(feature = "headless", not(target_arch = "wasm32")))]
+    #[error(transparent)]
+    Headless(#[from] crate::window::HeadlessError),
+}
+
+///
+/// A result where the error is a [ThreeDError].
+///
+pub type ThreeDResult<T> = Result<T, ThreeDError>;
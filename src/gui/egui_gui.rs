@@ -173,15 +173,24 @@ impl GUI {
                     Event::MouseLeave => Some(egui::Event::PointerGone),
                     Event::MouseWheel {
                         delta,
+                        lines,
                         handled,
                         modifiers,
                         ..
                     } => {
                         if !handled {
-                            Some(egui::Event::MouseWheel {
-                                delta: egui::Vec2::new(delta.0 as f32, delta.1 as f32),
-                                unit: egui::MouseWheelUnit::Point,
-                                modifiers: modifiers.into(),
+                            Some(if let Some(lines) = lines {
+                                egui::Event::MouseWheel {
+                                    delta: egui::Vec2::new(lines.0, lines.1),
+                                    unit: egui::MouseWheelUnit::Line,
+                                    modifiers: modifiers.into(),
+                                }
+                            } else {
+                                egui::Event::MouseWheel {
+                                    delta: egui::Vec2::new(delta.0, delta.1),
+                                    unit: egui::MouseWheelUnit::Point,
+                                    modifiers: modifiers.into(),
+                                }
                             })
                         } else {
                             None
@@ -0,0 +1,168 @@
+//!
+//! Functionality for loading assets at runtime, for example textures or the [CpuModel] of a glTF file, which can then be used to construct a [Model].
+//! This is a re-export of [three_d_asset::io] with a few additional helper functions, see that module for more information.
+//!
+
+pub use three_d_asset::io::*;
+
+#[allow(unused_imports)]
+use crate::renderer::*;
+
+///
+/// Loads and parses the glTF file (`.gltf` or `.glb`) at the given path into a [CpuModel],
+/// which for example can be turned into a `Model<PhysicalMaterial>` using [Model::new].
+/// Buffers and textures embedded in the file as well as external buffers and textures next to the file are loaded.
+/// The transformations of the nodes in the glTF scene are applied to the transformation of each [CpuModel] geometry.
+///
+/// Returns an error if the file could not be loaded or parsed, for example if it requires an extension that is not supported.
+///
+#[cfg(all(feature = "gltf", not(target_arch = "wasm32")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+pub fn load_gltf(path: impl AsRef<std::path::Path>) -> three_d_asset::Result<CpuModel> {
+    load(&[path.as_ref()])?.deserialize(path)
+}
+
+///
+/// Async version of [load_gltf] which also works on web where the path is a relative or absolute url.
+///
+#[cfg(feature = "gltf")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+pub async fn load_gltf_async(path: impl AsRef<std::path::Path>) -> three_d_asset::Result<CpuModel> {
+    load_async(&[path.as_ref()]).await?.deserialize(path)
+}
+
+///
+/// Decodes the image in the given bytes, for example the content of a PNG file, into a [CpuTexture].
+/// The path is only used to determine the image format from the extension, for example `"sprite.png"`.
+/// Decoding an image requires that the corresponding feature of [three_d_asset] is enabled, for example `png` or `jpeg`,
+/// otherwise an error is returned.
+///
+/// If `premultiplied_alpha` is true, the color channels are multiplied with the alpha channel, see [premultiply_alpha].
+/// Such a texture should be rendered with [Blend::PREMULTIPLIED_ALPHA] blending.
+/// 8-bit color channels are assumed to be sRGB encoded, as is the case for most images, so they are decoded to linear sRGB before the multiplication
+/// and encoded again afterwards, which means that the texture can be used in exactly the same way as a texture without premultiplied alpha.
+///
+pub fn load_texture_from_bytes(
+    path: impl AsRef<std::path::Path>,
+    bytes: Vec<u8>,
+    premultiplied_alpha: bool,
+) -> three_d_asset::Result<CpuTexture> {
+    let mut raw_assets = RawAssets::new();
+    raw_assets.insert(path.as_ref(), bytes);
+    let mut cpu_texture: CpuTexture = raw_assets.deserialize(path)?;
+    if premultiplied_alpha {
+        premultiply_srgb_alpha(&mut cpu_texture);
+    }
+    Ok(cpu_texture)
+}
+
+///
+/// Loads and decodes the image at the given path into a [CpuTexture], optionally with premultiplied alpha.
+/// See [load_texture_from_bytes] for more information.
+///
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_texture(
+    path: impl AsRef<std::path::Path>,
+    premultiplied_alpha: bool,
+) -> three_d_asset::Result<CpuTexture> {
+    let bytes = load(&[path.as_ref()])?.remove(path.as_ref())?;
+    load_texture_from_bytes(path, bytes, premultiplied_alpha)
+}
+
+///
+/// Async version of [load_texture] which also works on web where the path is a relative or absolute url.
+///
+pub async fn load_texture_async(
+    path: impl AsRef<std::path::Path>,
+    premultiplied_alpha: bool,
+) -> three_d_asset::Result<CpuTexture> {
+    let bytes = load_async(&[path.as_ref()]).await?.remove(path.as_ref())?;
+    load_texture_from_bytes(path, bytes, premultiplied_alpha)
+}
+
+fn premultiply_srgb_alpha(cpu_texture: &mut CpuTexture) {
+    match cpu_texture.data {
+        TextureData::RgbaU8(ref mut data) => data.iter_mut().for_each(|c| {
+            let linear = Srgba::from(*c).to_linear_srgb();
+            let a = linear.w;
+            *c = srgba_from_linear(vec4(linear.x * a, linear.y * a, linear.z * a, a)).into();
+        }),
+        _ => premultiply_alpha(cpu_texture),
+    }
+}
+
+///
+/// Saves the given RGBA pixels as a PNG image at the given path, for example the pixels returned from [RenderTarget::read_color] with `[u8; 4]` as the data type.
+/// The pixels are expected to be ordered row by row from the top of the image, which is the order returned when reading the pixels of a render target,
+/// so the saved image has the same orientation as on the screen.
+///
+/// # Panics
+///
+/// Panics if the number of pixels does not match the given width and height.
+///
+#[cfg(all(feature = "image", not(target_arch = "wasm32")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+pub fn save_pixels(
+    path: impl AsRef<std::path::Path>,
+    width: u32,
+    height: u32,
+    pixels: &[[u8; 4]],
+) -> Result<(), image::ImageError> {
+    if pixels.len() != width as usize * height as usize {
+        panic!(
+            "Failed saving pixels: The number of pixels {} does not match the width {} times the height {}",
+            pixels.len(),
+            width,
+            height
+        );
+    }
+    image::save_buffer_with_format(
+        path,
+        &pixels.iter().flatten().copied().collect::<Vec<_>>(),
+        width,
+        height,
+        image::ColorType::Rgba8,
+        image::ImageFormat::Png,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_texture_with_premultiplied_alpha() {
+        let pixels = vec![[255, 128, 0, 255], [255, 128, 0, 128], [200, 100, 50, 0]];
+        let cpu_texture = CpuTexture {
+            data: TextureData::RgbaU8(pixels.clone()),
+            width: 3,
+            height: 1,
+            ..Default::default()
+        };
+        let bytes = cpu_texture
+            .serialize("sprite.png")
+            .unwrap()
+            .remove("sprite.png")
+            .unwrap();
+
+        let straight = load_texture_from_bytes("sprite.png", bytes.clone(), false).unwrap();
+        assert_eq!(straight.data, TextureData::RgbaU8(pixels));
+
+        let premultiplied = load_texture_from_bytes("sprite.png", bytes, true).unwrap();
+        let TextureData::RgbaU8(data) = premultiplied.data else {
+            panic!("expected 8-bit RGBA data");
+        };
+        assert_eq!(data[0], [255, 128, 0, 255]);
+        assert_eq!(data[2], [0, 0, 0, 0]);
+        // The color is scaled by alpha in linear color space
+        let expected = Srgba::new(255, 128, 0, 255).to_linear_srgb() * (128.0 / 255.0);
+        let result = Srgba::from(data[1]).to_linear_srgb();
+        assert_eq!(data[1][3], 128);
+        for i in 0..3 {
+            assert!(
+                (result[i] - expected[i]).abs() < 0.01,
+                "{result:?} != {expected:?}"
+            );
+        }
+    }
+}
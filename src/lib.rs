@@ -16,6 +16,11 @@ pub mod core;
 pub mod renderer;
 pub use renderer::*;
 
+pub mod io;
+
+mod error;
+pub use error::*;
+
 pub mod window;
 #[allow(unused_imports)]
 pub use window::*;
@@ -52,6 +52,30 @@ pub use object::*;
 pub mod control;
 pub use control::*;
 
+mod panorama_capture;
+pub use panorama_capture::*;
+
+mod raycast;
+pub use raycast::*;
+
+mod bvh;
+pub use bvh::*;
+
+mod mesh_edges;
+pub use mesh_edges::*;
+
+mod mesh_normals;
+pub use mesh_normals::*;
+
+mod mesh_primitives;
+pub use mesh_primitives::*;
+
+mod animation_track;
+pub use animation_track::*;
+
+mod uv_transform;
+pub use uv_transform::*;
+
 macro_rules! impl_render_target_extensions_body {
     () => {
         ///
@@ -151,6 +175,115 @@ macro_rules! impl_render_target_extensions_body {
             self
         }
 
+        ///
+        /// Render the objects using the given camera and lights into the part of this render target covered by the viewport of the camera.
+        /// This is the same as [Self::render_partially] with a scissor box equal to the camera viewport, which ensures that nothing is drawn outside the viewport,
+        /// so several cameras with different viewports can render into different regions of the same render target, for example for split-screen rendering.
+        /// Use [Self::clear_partially] with the same viewport to clear only the region of one camera.
+        ///
+        pub fn render_to_viewport(
+            &self,
+            camera: &Camera,
+            objects: impl IntoIterator<Item = impl Object>,
+            lights: &[&dyn Light],
+        ) -> &Self {
+            self.render_partially(camera.viewport().into(), camera, objects, lights)
+        }
+
+        ///
+        /// Render the objects using the given camera and lights into this render target like [Self::render], but with a depth pre-pass for the opaque objects.
+        /// First, the depth of the opaque objects is rendered with a cheap depth-only material (see [Self::render_depth]),
+        /// and then the opaque objects are rendered with their own material using [DepthTest::LessOrEqual] and without writing depth,
+        /// so the expensive fragment shader is only evaluated once for each pixel, even if many objects overlap.
+        /// Opaque objects with a material that discards fragments (see [Material::discards_fragments]), for example a [ColorMaterial] with an alpha cutout,
+        /// are rendered with their own material in the depth pre-pass as well, so the depth of the discarded fragments is not written.
+        /// The depth pre-pass uses the same [Cull] as the material of each object.
+        /// Transparent and deferred objects, and objects which do not expose their material (see [Object::material]), are rendered as in [Self::render] afterwards.
+        ///
+        /// This is only faster when the cost of the fragment shaders dominates, for example for a [PhysicalMaterial] with many lights and a lot of overlapping geometry,
+        /// since all opaque geometry is drawn twice. For simple materials or scenes with little overdraw, use [Self::render] instead.
+        ///
+        pub fn render_with_depth_pre_pass(
+            &self,
+            camera: &Camera,
+            objects: impl IntoIterator<Item = impl Object>,
+            lights: &[&dyn Light],
+        ) -> &Self {
+            let (mut opaque_objects, other_objects): (Vec<_>, Vec<_>) = objects
+                .into_iter()
+                .filter(|o| camera.in_frustum(&o.aabb()))
+                .partition(|o| o.material_type() == MaterialType::Opaque && o.material().is_some());
+            opaque_objects.sort_by(|a, b| cmp_render_order(camera, a, b));
+            self.write_partially::<RendererError>(self.scissor_box(), || {
+                for object in opaque_objects.iter() {
+                    let material = object.material().unwrap();
+                    let material_render_states = material.render_states();
+                    if material.discards_fragments() {
+                        object.render_with_material(
+                            &RenderStatesMaterial {
+                                material,
+                                render_states: RenderStates {
+                                    write_mask: WriteMask::DEPTH,
+                                    ..material_render_states
+                                },
+                            },
+                            camera,
+                            lights,
+                        );
+                    } else {
+                        object.render_with_material(
+                            &DepthMaterial {
+                                render_states: RenderStates {
+                                    write_mask: WriteMask::DEPTH,
+                                    cull: material_render_states.cull,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            camera,
+                            &[],
+                        );
+                    }
+                }
+                for object in opaque_objects.iter() {
+                    let material = object.material().unwrap();
+                    let mut render_states = material.render_states();
+                    render_states.depth_test = DepthTest::LessOrEqual;
+                    render_states.write_mask.depth = false;
+                    object.render_with_material(
+                        &RenderStatesMaterial {
+                            material,
+                            render_states,
+                        },
+                        camera,
+                        lights,
+                    );
+                }
+                Ok(())
+            })
+            .unwrap();
+            self.render_partially(self.scissor_box(), camera, other_objects, lights)
+        }
+
+        ///
+        /// Render the objects which belongs to at least one of the layers in the given layer mask (see [Object::layer]) using the given camera and lights into this render target.
+        /// Objects where `object.layer() & layer_mask == 0` are skipped, otherwise this is the same as [Self::render].
+        ///
+        pub fn render_layers(
+            &self,
+            camera: &Camera,
+            objects: impl IntoIterator<Item = impl Object>,
+            lights: &[&dyn Light],
+            layer_mask: u32,
+        ) -> &Self {
+            self.render_partially(
+                self.scissor_box(),
+                camera,
+                objects.into_iter().filter(|o| o.layer() & layer_mask != 0),
+                lights,
+            )
+        }
+
         ///
         /// Render the geometries with the given [Material] using the given camera and lights into this render target.
         /// Use an empty array for the `lights` argument, if the material does not require lights to be rendered.
@@ -196,6 +329,30 @@ macro_rules! impl_render_target_extensions_body {
             self
         }
 
+        ///
+        /// Render only the depth of the geometries using the given camera into this render target, ie. the color channels are not written to.
+        /// The geometries are rendered with a [DepthMaterial] which only requires the positions of the geometries, so it is a cheap way to fill a depth texture,
+        /// for example for shadow maps, a depth pre-pass or effects using the depth texture, like [SsaoEffect].
+        ///
+        pub fn render_depth(
+            &self,
+            camera: &Camera,
+            geometries: impl IntoIterator<Item = impl Geometry>,
+        ) -> &Self {
+            self.render_with_material(
+                &DepthMaterial {
+                    render_states: RenderStates {
+                        write_mask: WriteMask::DEPTH,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                camera,
+                geometries,
+                &[],
+            )
+        }
+
         ///
         /// Render the geometries with the given [Effect] using the given camera and lights into this render target.
         /// Use an empty array for the `lights` argument, if the effect does not require lights to be rendered.
@@ -375,6 +532,38 @@ impl_render_target_extensions!(
 impl_render_target_extensions!(ColorTargetMultisample<C: TextureDataType>);
 impl_render_target_extensions!(DepthTargetMultisample<D: DepthTextureDataType>);
 
+///
+/// A [Material] which renders with the given material, but with other render states.
+///
+struct RenderStatesMaterial<'a, M: Material + ?Sized> {
+    material: &'a M,
+    render_states: RenderStates,
+}
+
+impl<M: Material + ?Sized> Material for RenderStatesMaterial<'_, M> {
+    fn fragment_shader_source(&self, lights: &[&dyn Light]) -> String {
+        self.material.fragment_shader_source(lights)
+    }
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        self.material.fragment_attributes()
+    }
+    fn use_uniforms(&self, program: &Program, camera: &Camera, lights: &[&dyn Light]) {
+        self.material.use_uniforms(program, camera, lights)
+    }
+    fn render_states(&self) -> RenderStates {
+        self.render_states
+    }
+    fn material_type(&self) -> MaterialType {
+        self.material.material_type()
+    }
+    fn id(&self) -> u16 {
+        self.material.id()
+    }
+    fn discards_fragments(&self) -> bool {
+        self.material.discards_fragments()
+    }
+}
+
 ///
 /// Render the given [Geometry] with the given [Material].
 /// Must be called in the callback given as input to a [RenderTarget], [ColorTarget] or [DepthTarget] write method.
@@ -393,20 +582,50 @@ pub fn render_with_material(
     id.extend(lights.iter().map(|l| l.id()));
 
     let mut programs = context.programs.write().unwrap();
-    let program = programs.entry(id).or_insert_with(|| {
-        Program::from_source(
-            context,
-            &geometry.vertex_shader_source(fragment_attributes),
-            &material.fragment_shader_source(lights),
-        )
-        .expect("Failed compiling shader")
-    });
+    let program = programs
+        .entry(id)
+        .or_insert_with(|| material_program(context, &geometry, &material, lights));
+    render_with_program(camera, geometry, material, program, lights);
+}
+
+///
+/// Compiles the [Program] needed to render the given [Geometry] with the given [Material] and lights.
+/// The program can be reused with [render_with_program] for any geometry and material of the same type and with the same [Geometry::id] and [Material::id] as the given geometry and material,
+/// which avoids the program lookup done in [render_with_material] for each draw.
+///
+pub fn material_program(
+    context: &Context,
+    geometry: impl Geometry,
+    material: impl Material,
+    lights: &[&dyn Light],
+) -> Program {
+    let fragment_attributes = material.fragment_attributes();
+    Program::from_source(
+        context,
+        &geometry.vertex_shader_source(fragment_attributes),
+        &material.fragment_shader_source(lights),
+    )
+    .expect("Failed compiling shader")
+}
+
+///
+/// Render the given [Geometry] with the given [Material] using the given [Program] which is compiled once using [material_program] and then reused.
+/// Must be called in the callback given as input to a [RenderTarget], [ColorTarget] or [DepthTarget] write method.
+/// Use an empty array for the `lights` argument, if the material does not require lights to be rendered.
+///
+pub fn render_with_program(
+    camera: &Camera,
+    geometry: impl Geometry,
+    material: impl Material,
+    program: &Program,
+    lights: &[&dyn Light],
+) {
     material.use_uniforms(program, camera, lights);
     geometry.draw(
         camera,
         program,
         material.render_states(),
-        fragment_attributes,
+        material.fragment_attributes(),
     );
 }
 
@@ -517,6 +736,11 @@ pub fn apply_screen_effect(
 /// Compare function for sorting objects based on distance from the camera.
 /// The order is opaque objects from nearest to farthest away from the camera,
 /// then transparent objects from farthest away to closest to the camera.
+/// The distance is measured from the camera position to the center of the bounding box of each object along the view direction of the camera,
+/// ie. the depth of the center, which also gives the correct order for orthographic cameras.
+///
+/// Note that transparent objects are sorted per object and not per triangle,
+/// so intersecting transparent objects or transparent objects that surround each other might still be blended in the wrong order.
 ///
 pub fn cmp_render_order(
     camera: &Camera,
@@ -532,8 +756,9 @@ pub fn cmp_render_order(
     {
         std::cmp::Ordering::Less
     } else {
-        let distance_a = camera.position().distance2(obj0.aabb().center());
-        let distance_b = camera.position().distance2(obj1.aabb().center());
+        let view_direction = camera.view_direction();
+        let distance_a = view_direction.dot(obj0.aabb().center() - camera.position());
+        let distance_b = view_direction.dot(obj1.aabb().center() - camera.position());
         if distance_a.is_nan() || distance_b.is_nan() {
             distance_a.is_nan().cmp(&distance_b.is_nan()) // whatever - just save us from panicing on unwrap below
         } else if obj0.material_type() == MaterialType::Transparent {
@@ -640,3 +865,117 @@ pub fn ray_intersect(
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///
+    /// An object which only has a bounding box and a material type, since that is all [cmp_render_order] needs.
+    ///
+    struct BoxObject(Vec3, MaterialType);
+
+    impl Geometry for BoxObject {
+        fn draw(&self, _: &Camera, _: &Program, _: RenderStates, _: FragmentAttributes) {
+            unreachable!()
+        }
+        fn vertex_shader_source(&self, _: FragmentAttributes) -> String {
+            unreachable!()
+        }
+        fn id(&self, _: FragmentAttributes) -> u16 {
+            unreachable!()
+        }
+        fn render_with_material(&self, _: &dyn Material, _: &Camera, _: &[&dyn Light]) {
+            unreachable!()
+        }
+        fn render_with_effect(
+            &self,
+            _: &dyn Effect,
+            _: &Camera,
+            _: &[&dyn Light],
+            _: Option<ColorTexture>,
+            _: Option<DepthTexture>,
+        ) {
+            unreachable!()
+        }
+        fn aabb(&self) -> AxisAlignedBoundingBox {
+            AxisAlignedBoundingBox::new_with_positions(&[
+                self.0 - vec3(0.5, 0.5, 0.5),
+                self.0 + vec3(0.5, 0.5, 0.5),
+            ])
+        }
+    }
+
+    impl Object for BoxObject {
+        fn render(&self, _: &Camera, _: &[&dyn Light]) {
+            unreachable!()
+        }
+        fn material_type(&self) -> MaterialType {
+            self.1
+        }
+    }
+
+    fn camera() -> Camera {
+        Camera::new_perspective(
+            Viewport::new_at_origo(100, 100),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 0.0, -1.0),
+            vec3(0.0, 1.0, 0.0),
+            degrees(90.0),
+            0.1,
+            100.0,
+        )
+    }
+
+    fn sorted(mut objects: Vec<BoxObject>) -> Vec<Vec3> {
+        let camera = camera();
+        objects.sort_by(|a, b| cmp_render_order(&camera, a, b));
+        objects.into_iter().map(|o| o.0).collect()
+    }
+
+    #[test]
+    fn transparent_objects_are_sorted_back_to_front() {
+        let positions = sorted(vec![
+            BoxObject(vec3(0.0, 0.0, -5.0), MaterialType::Transparent),
+            BoxObject(vec3(0.0, 0.0, -10.0), MaterialType::Transparent),
+            BoxObject(vec3(0.0, 0.0, -2.0), MaterialType::Transparent),
+        ]);
+        assert_eq!(
+            positions,
+            vec![
+                vec3(0.0, 0.0, -10.0),
+                vec3(0.0, 0.0, -5.0),
+                vec3(0.0, 0.0, -2.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn opaque_objects_are_sorted_front_to_back_before_transparent_objects() {
+        let positions = sorted(vec![
+            BoxObject(vec3(0.0, 0.0, -10.0), MaterialType::Transparent),
+            BoxObject(vec3(0.0, 0.0, -8.0), MaterialType::Opaque),
+            BoxObject(vec3(0.0, 0.0, -2.0), MaterialType::Transparent),
+            BoxObject(vec3(0.0, 0.0, -4.0), MaterialType::Opaque),
+        ]);
+        assert_eq!(
+            positions,
+            vec![
+                vec3(0.0, 0.0, -4.0),
+                vec3(0.0, 0.0, -8.0),
+                vec3(0.0, 0.0, -10.0),
+                vec3(0.0, 0.0, -2.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn objects_are_sorted_by_depth_along_the_view_direction() {
+        // The first object is farther away from the camera position but closer along the view direction
+        let positions = sorted(vec![
+            BoxObject(vec3(10.0, 0.0, -5.0), MaterialType::Transparent),
+            BoxObject(vec3(0.0, 0.0, -8.0), MaterialType::Transparent),
+        ]);
+        assert_eq!(positions, vec![vec3(0.0, 0.0, -8.0), vec3(10.0, 0.0, -5.0)]);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::renderer::*;
+
+///
+/// Defines how the values in between the key frames of an [AnimationTrack] are computed.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AnimationInterpolation {
+    /// The value of the previous key frame is used until the next key frame.
+    Step,
+    /// Linear interpolation of translations and scales and spherical linear interpolation (slerp) of rotations.
+    #[default]
+    Linear,
+    /// Cubic Hermite spline interpolation as defined by glTF.
+    /// Each key frame is defined by three values, an in-tangent, the value and an out-tangent, in that order,
+    /// so each of the value vectors must contain three times as many values as there are key frames.
+    CubicSpline,
+}
+
+///
+/// Defines what happens when an [AnimationTrack] is sampled outside the time range of its key frames.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AnimationLoop {
+    /// The value of the first key frame is used before the first key frame and the value of the last key frame is used after the last key frame.
+    #[default]
+    Clamp,
+    /// The animation repeats itself in between the time of the first and the last key frame.
+    Repeat,
+}
+
+///
+/// A key frame animation of a translation, rotation and scale which can be sampled at any time, for example to animate the transformation of an object:
+/// `mesh.set_transformation(track.sample(frame_input.accumulated_time as f32))`.
+/// Each of the translations, rotations and scales is optional, if `None` then it is not part of the animation.
+/// See also [KeyFrames] which is the animation format used when loading a [Model] from a file.
+///
+#[derive(Clone, Debug, Default)]
+pub struct AnimationTrack {
+    /// The time of each key frame in increasing order.
+    pub times: Vec<f32>,
+    /// The translation at each key frame.
+    pub translations: Option<Vec<Vec3>>,
+    /// The rotation at each key frame.
+    pub rotations: Option<Vec<Quat>>,
+    /// The non-uniform scale at each key frame.
+    pub scales: Option<Vec<Vec3>>,
+    /// The interpolation in between the key frames.
+    pub interpolation: AnimationInterpolation,
+    /// Defines what happens outside the time range of the key frames.
+    pub looping: AnimationLoop,
+}
+
+impl AnimationTrack {
+    ///
+    /// Returns the time of the last key frame.
+    ///
+    pub fn duration(&self) -> f32 {
+        self.times.last().copied().unwrap_or(0.0)
+    }
+
+    ///
+    /// Returns the transformation at the given time, ie. the translation, rotation and scale applied in the order scale, rotation and then translation.
+    ///
+    /// # Panics
+    ///
+    /// If there are no key frames or if the number of translations, rotations or scales does not match the number of key frames,
+    /// which is three values per key frame for [AnimationInterpolation::CubicSpline] and one value per key frame otherwise.
+    ///
+    pub fn sample(&self, time: f32) -> Mat4 {
+        let mut transformation = Mat4::identity();
+        if let Some(translation) = self.translation(time) {
+            transformation = transformation * Mat4::from_translation(translation);
+        }
+        if let Some(rotation) = self.rotation(time) {
+            transformation = transformation * Mat4::from(rotation);
+        }
+        if let Some(scale) = self.scale(time) {
+            transformation =
+                transformation * Mat4::from_nonuniform_scale(scale.x, scale.y, scale.z);
+        }
+        transformation
+    }
+
+    ///
+    /// Returns the translation at the given time or `None` if the translation is not animated.
+    ///
+    /// # Panics
+    ///
+    /// If there are no key frames or if the number of translations does not match the number of key frames,
+    /// which is three values per key frame for [AnimationInterpolation::CubicSpline] and one value per key frame otherwise.
+    ///
+    pub fn translation(&self, time: f32) -> Option<Vec3> {
+        self.translations
+            .as_ref()
+            .map(|values| self.interpolate(time, values, |a, b, t| a.lerp(b, t)))
+    }
+
+    ///
+    /// Returns the rotation at the given time or `None` if the rotation is not animated.
+    ///
+    /// # Panics
+    ///
+    /// If there are no key frames or if the number of rotations does not match the number of key frames,
+    /// which is three values per key frame for [AnimationInterpolation::CubicSpline] and one value per key frame otherwise.
+    ///
+    pub fn rotation(&self, time: f32) -> Option<Quat> {
+        self.rotations.as_ref().map(|values| {
+            self.interpolate(time, values, |a, b, t| a.slerp(b, t))
+                .normalize()
+        })
+    }
+
+    ///
+    /// Returns the scale at the given time or `None` if the scale is not animated.
+    ///
+    /// # Panics
+    ///
+    /// If there are no key frames or if the number of scales does not match the number of key frames,
+    /// which is three values per key frame for [AnimationInterpolation::CubicSpline] and one value per key frame otherwise.
+    ///
+    pub fn scale(&self, time: f32) -> Option<Vec3> {
+        self.scales
+            .as_ref()
+            .map(|values| self.interpolate(time, values, |a, b, t| a.lerp(b, t)))
+    }
+
+    fn interpolate<T>(&self, time: f32, values: &[T], lerp: impl Fn(T, T, f32) -> T) -> T
+    where
+        T: Copy + std::ops::Mul<f32, Output = T> + std::ops::Add<T, Output = T>,
+    {
+        let stride = if self.interpolation == AnimationInterpolation::CubicSpline {
+            3
+        } else {
+            1
+        };
+        if self.times.is_empty() || values.len() != self.times.len() * stride {
+            panic!(
+                "an animation track must have at least one key frame and {} values per key frame",
+                stride
+            );
+        }
+        let value = |i: usize| values[i * stride + stride / 2];
+        let last = self.times.len() - 1;
+        let period = self.times[last] - self.times[0];
+        let time = match self.looping {
+            AnimationLoop::Repeat if period > 0.0 => {
+                self.times[0] + (time - self.times[0]).rem_euclid(period)
+            }
+            _ => time,
+        };
+        if time <= self.times[0] {
+            return value(0);
+        }
+        if time >= self.times[last] {
+            return value(last);
+        }
+        let i = self.times.partition_point(|t| *t <= time) - 1;
+        let dt = self.times[i + 1] - self.times[i];
+        let t = (time - self.times[i]) / dt;
+        match self.interpolation {
+            AnimationInterpolation::Step => value(i),
+            AnimationInterpolation::Linear => lerp(value(i), value(i + 1), t),
+            AnimationInterpolation::CubicSpline => {
+                let out_tangent = values[i * 3 + 2];
+                let in_tangent = values[(i + 1) * 3];
+                let t2 = t * t;
+                let t3 = t2 * t;
+                value(i) * (2.0 * t3 - 3.0 * t2 + 1.0)
+                    + out_tangent * ((t3 - 2.0 * t2 + t) * dt)
+                    + value(i + 1) * (-2.0 * t3 + 3.0 * t2)
+                    + in_tangent * ((t3 - t2) * dt)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(looping: AnimationLoop) -> AnimationTrack {
+        AnimationTrack {
+            times: vec![1.0, 2.0, 3.0],
+            translations: Some(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(2.0, 0.0, 0.0),
+                vec3(2.0, 4.0, 0.0),
+            ]),
+            looping,
+            ..Default::default()
+        }
+    }
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).magnitude() < 1e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn key_frame_boundaries() {
+        let track = track(AnimationLoop::Clamp);
+        assert_near(track.translation(0.0).unwrap(), vec3(0.0, 0.0, 0.0));
+        assert_near(track.translation(1.0).unwrap(), vec3(0.0, 0.0, 0.0));
+        assert_near(track.translation(1.5).unwrap(), vec3(1.0, 0.0, 0.0));
+        assert_near(track.translation(2.0).unwrap(), vec3(2.0, 0.0, 0.0));
+        assert_near(track.translation(2.25).unwrap(), vec3(2.0, 1.0, 0.0));
+        assert_near(track.translation(3.0).unwrap(), vec3(2.0, 4.0, 0.0));
+        assert_near(track.translation(10.0).unwrap(), vec3(2.0, 4.0, 0.0));
+        assert!(track.rotation(1.5).is_none());
+    }
+
+    #[test]
+    fn step_interpolation() {
+        let track = AnimationTrack {
+            interpolation: AnimationInterpolation::Step,
+            ..track(AnimationLoop::Clamp)
+        };
+        assert_near(track.translation(1.99).unwrap(), vec3(0.0, 0.0, 0.0));
+        assert_near(track.translation(2.0).unwrap(), vec3(2.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn repeat_wraps_over_the_key_frame_range() {
+        let track = track(AnimationLoop::Repeat);
+        assert_near(track.translation(1.5).unwrap(), vec3(1.0, 0.0, 0.0));
+        assert_near(track.translation(3.5).unwrap(), vec3(1.0, 0.0, 0.0));
+        assert_near(track.translation(5.5).unwrap(), vec3(1.0, 0.0, 0.0));
+        assert_near(track.translation(0.5).unwrap(), vec3(2.0, 2.0, 0.0));
+        assert_near(track.translation(4.25).unwrap(), vec3(2.0, 1.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_value_count() {
+        AnimationTrack {
+            times: vec![0.0, 1.0],
+            scales: Some(vec![vec3(1.0, 1.0, 1.0)]),
+            ..Default::default()
+        }
+        .sample(0.5);
+    }
+}
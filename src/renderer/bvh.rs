@@ -0,0 +1,214 @@
+use crate::renderer::*;
+
+///
+/// A bounding volume hierarchy over the triangles of a [CpuMesh] which accelerates ray intersection queries, see [Bvh::intersect_ray].
+/// Building the hierarchy takes some time, so construct it once and reuse it as long as the mesh does not change.
+/// Compared to [ray_mesh_intersection], which tests every triangle, a query only tests the triangles in the parts of the hierarchy that the ray passes through.
+///
+#[derive(Clone, Debug)]
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    triangles: Vec<[Vec3; 3]>,
+    triangle_indices: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct BvhNode {
+    aabb: AxisAlignedBoundingBox,
+    // The index of the first triangle if this is a leaf, otherwise the index of the second child (the first child is the next node).
+    offset: usize,
+    // The number of triangles if this is a leaf, otherwise zero.
+    count: usize,
+}
+
+const MAX_TRIANGLES_IN_LEAF: usize = 4;
+
+impl Bvh {
+    ///
+    /// Builds a bounding volume hierarchy over the triangles of the given mesh, in the local space of the mesh.
+    /// The hierarchy is built by recursively splitting the triangles at the median of their centers along the longest axis.
+    /// Degenerate triangles are ignored.
+    ///
+    pub fn new(cpu_mesh: &CpuMesh) -> Self {
+        let positions = cpu_mesh.positions.to_f32();
+        let mut triangles = Vec::new();
+        let mut triangle_indices = Vec::new();
+        let mut triangle_index = 0;
+        cpu_mesh.for_each_triangle(|i0, i1, i2| {
+            let triangle = [positions[i0], positions[i1], positions[i2]];
+            if (triangle[1] - triangle[0])
+                .cross(triangle[2] - triangle[0])
+                .magnitude2()
+                > 0.0
+            {
+                triangles.push(triangle);
+                triangle_indices.push(triangle_index);
+            }
+            triangle_index += 1;
+        });
+        let mut bvh = Self {
+            nodes: Vec::with_capacity(2 * triangles.len() / MAX_TRIANGLES_IN_LEAF + 1),
+            triangles,
+            triangle_indices,
+        };
+        if !bvh.triangles.is_empty() {
+            bvh.build(0, bvh.triangles.len());
+        }
+        bvh
+    }
+
+    fn build(&mut self, start: usize, end: usize) {
+        let aabb = AxisAlignedBoundingBox::new_with_positions(
+            &self.triangles[start..end]
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>(),
+        );
+        let node_index = self.nodes.len();
+        self.nodes.push(BvhNode {
+            aabb,
+            offset: start,
+            count: end - start,
+        });
+        if end - start <= MAX_TRIANGLES_IN_LEAF {
+            return;
+        }
+
+        let center = |t: &[Vec3; 3]| (t[0] + t[1] + t[2]) / 3.0;
+        let centers = AxisAlignedBoundingBox::new_with_positions(
+            &self.triangles[start..end]
+                .iter()
+                .map(center)
+                .collect::<Vec<_>>(),
+        );
+        let size = centers.size();
+        let axis = if size.x >= size.y && size.x >= size.z {
+            0
+        } else if size.y >= size.z {
+            1
+        } else {
+            2
+        };
+        let middle = (start + end) / 2;
+        let mut order = (start..end).collect::<Vec<_>>();
+        order.select_nth_unstable_by(middle - start, |a, b| {
+            center(&self.triangles[*a])[axis]
+                .partial_cmp(&center(&self.triangles[*b])[axis])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let triangles = order.iter().map(|i| self.triangles[*i]).collect::<Vec<_>>();
+        let triangle_indices = order
+            .iter()
+            .map(|i| self.triangle_indices[*i])
+            .collect::<Vec<_>>();
+        self.triangles[start..end].copy_from_slice(&triangles);
+        self.triangle_indices[start..end].copy_from_slice(&triangle_indices);
+
+        self.build(start, middle);
+        let second_child = self.nodes.len();
+        self.build(middle, end);
+        self.nodes[node_index].offset = second_child;
+        self.nodes[node_index].count = 0;
+    }
+
+    ///
+    /// Returns the bounding box of all of the triangles in the hierarchy.
+    ///
+    pub fn aabb(&self) -> AxisAlignedBoundingBox {
+        self.nodes
+            .first()
+            .map(|n| n.aabb)
+            .unwrap_or(AxisAlignedBoundingBox::EMPTY)
+    }
+
+    ///
+    /// Returns the distance along the ray to the nearest intersection with a triangle in the mesh and the index of that triangle in the mesh,
+    /// or `None` if the ray does not intersect the mesh.
+    /// The ray is given in the local space of the mesh, so transform it by the inverse of any transformation applied to the mesh.
+    /// The distance is measured in multiples of the length of the given direction, so use a normalized direction to get the distance in local units.
+    ///
+    pub fn intersect_ray(&self, origin: Vec3, direction: Vec3) -> Option<(f32, usize)> {
+        let mut nearest: Option<(f32, usize)> = None;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            match ray_aabb_intersection(origin, direction, &node.aabb) {
+                Some(distance) if nearest.map(|(d, _)| distance <= d).unwrap_or(true) => {}
+                _ => continue,
+            }
+            if node.count > 0 {
+                for i in node.offset..node.offset + node.count {
+                    if let Some((distance, _)) =
+                        ray_triangle_intersection(origin, direction, self.triangles[i])
+                    {
+                        if nearest.map(|(d, _)| distance < d).unwrap_or(true) {
+                            nearest = Some((distance, self.triangle_indices[i]));
+                        }
+                    }
+                }
+            } else {
+                let first = node_index + 1;
+                let second = node.offset;
+                let first_distance =
+                    ray_aabb_intersection(origin, direction, &self.nodes[first].aabb);
+                let second_distance =
+                    ray_aabb_intersection(origin, direction, &self.nodes[second].aabb);
+                // Push the farthest child first so that the nearest child is visited first.
+                if first_distance.unwrap_or(f32::INFINITY)
+                    < second_distance.unwrap_or(f32::INFINITY)
+                {
+                    stack.push(second);
+                    stack.push(first);
+                } else {
+                    stack.push(first);
+                    stack.push(second);
+                }
+            }
+        }
+        nearest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn same_result_as_brute_force() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut random_vec3 = |scale: f32| {
+            vec3(rng.gen(), rng.gen(), rng.gen()) * 2.0 * scale - vec3(scale, scale, scale)
+        };
+        let mut positions = Vec::new();
+        for _ in 0..500 {
+            let center = random_vec3(5.0);
+            for _ in 0..3 {
+                positions.push(center + random_vec3(0.5));
+            }
+        }
+        let rays = (0..1000)
+            .map(|_| {
+                let origin = random_vec3(8.0);
+                (origin, random_vec3(5.0) - origin)
+            })
+            .collect::<Vec<_>>();
+        let cpu_mesh = CpuMesh {
+            positions: Positions::F32(positions),
+            ..Default::default()
+        };
+
+        let bvh = Bvh::new(&cpu_mesh);
+        let mut hits = 0;
+        for (origin, direction) in rays {
+            let expected = ray_mesh_intersection(origin, direction, &cpu_mesh, Mat4::identity());
+            assert_eq!(bvh.intersect_ray(origin, direction), expected);
+            hits += expected.is_some() as usize;
+        }
+        assert!(hits > 100, "only {hits} rays hit the mesh");
+    }
+}
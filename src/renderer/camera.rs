@@ -4,6 +4,11 @@ pub use tone_mapping::*;
 mod color_space;
 pub use color_space::*;
 
+mod stereo_camera;
+pub use stereo_camera::*;
+
+pub use three_d_asset::ProjectionType;
+
 use crate::core::*;
 
 ///
@@ -16,6 +21,24 @@ pub struct Camera {
     pub tone_mapping: ToneMapping,
     /// This color mapping is applied to the final color of renders using this camera.
     pub color_mapping: ColorMapping,
+    /// The custom view matrix and the view matrix given by the camera parameters when it was set,
+    /// which is used to detect if the view has since been changed through the camera parameters.
+    custom_view: Option<(Mat4, Mat4)>,
+    custom_projection: Option<Mat4>,
+    orthographic_width: Option<f32>,
+    animation: Option<CameraAnimation>,
+}
+
+#[derive(Clone, Debug)]
+struct CameraAnimation {
+    start_position: Vec3,
+    start_orientation: Quat,
+    start_distance: f32,
+    end_position: Vec3,
+    end_orientation: Quat,
+    end_distance: f32,
+    duration: f32,
+    time: f32,
 }
 
 impl Camera {
@@ -37,6 +60,10 @@ impl Camera {
             ),
             tone_mapping: ToneMapping::default(),
             color_mapping: ColorMapping::default(),
+            custom_view: None,
+            custom_projection: None,
+            orthographic_width: None,
+            animation: None,
         }
     }
 
@@ -64,7 +91,243 @@ impl Camera {
             ),
             tone_mapping: ToneMapping::default(),
             color_mapping: ColorMapping::default(),
+            custom_view: None,
+            custom_projection: None,
+            orthographic_width: None,
+            animation: None,
+        }
+    }
+
+    ///
+    /// Specify the camera to use orthographic projection with the given height and depth.
+    /// The width of the camera volume is given by the height and the aspect ratio of the viewport,
+    /// so when the viewport changes, the height is kept fixed and the width is adjusted so that the projection is not stretched.
+    ///
+    pub fn set_orthographic_projection(&mut self, height: f32, z_near: f32, z_far: f32) {
+        self.orthographic_width = None;
+        self.camera
+            .set_orthographic_projection(height, z_near, z_far);
+    }
+
+    ///
+    /// Specify the camera to use orthographic projection with the given width and depth.
+    /// The height of the camera volume is given by the width and the aspect ratio of the viewport,
+    /// so when the viewport changes, the width is kept fixed and the height is adjusted so that the projection is not stretched.
+    /// This is for example useful for keeping the horizontal extent of a scene visible in a tall viewport.
+    ///
+    pub fn set_orthographic_projection_with_width(&mut self, width: f32, z_near: f32, z_far: f32) {
+        self.camera
+            .set_orthographic_projection(width / self.viewport().aspect(), z_near, z_far);
+        self.orthographic_width = Some(width);
+    }
+
+    ///
+    /// Returns the width of the camera volume if the camera uses orthographic projection with a fixed width (see [Camera::set_orthographic_projection_with_width]),
+    /// otherwise `None`, in which case the height is the fixed dimension for an orthographic projection.
+    ///
+    pub fn fixed_orthographic_width(&self) -> Option<f32> {
+        if let ProjectionType::Orthographic { .. } = self.projection_type() {
+            self.orthographic_width
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Returns the distance from the camera position to the camera target, which is the distance at which objects keep the same size on screen
+    /// when switching between perspective and orthographic projection, see [Camera::set_orthographic_from_perspective] and [Camera::set_perspective_from_orthographic].
+    ///
+    pub fn focus_distance(&self) -> f32 {
+        self.target().distance(*self.position())
+    }
+
+    ///
+    /// Switches from a perspective projection to an orthographic projection where the height of the camera volume is chosen such that
+    /// objects at the [Camera::focus_distance] have approximately the same size on screen as before, ie. the framing does not jump.
+    /// Objects closer or further away than the focus distance change size, since an orthographic projection has no perspective foreshortening.
+    /// Does nothing if the camera already uses an orthographic projection or if the focus distance is zero, ie. the camera position is equal to the target.
+    ///
+    pub fn set_orthographic_from_perspective(&mut self) {
+        if let ProjectionType::Perspective { field_of_view_y } = *self.projection_type() {
+            let focus_distance = self.focus_distance();
+            if focus_distance <= f32::EPSILON {
+                return;
+            }
+            let height = 2.0 * focus_distance * (0.5 * field_of_view_y.0).tan();
+            let (z_near, z_far) = (self.z_near(), self.z_far());
+            self.set_orthographic_projection(height, z_near, z_far);
+        }
+    }
+
+    ///
+    /// Switches from an orthographic projection to a perspective projection where the field of view is chosen such that
+    /// objects at the [Camera::focus_distance] have approximately the same size on screen as before, ie. the framing does not jump.
+    /// The camera position is not changed, so if the camera is far from the target, the field of view becomes narrow.
+    /// If the near plane of the orthographic projection is not in front of the camera, it is moved to a small fraction of the focus distance,
+    /// since a perspective projection requires a positive near plane.
+    /// Does nothing if the camera already uses a perspective projection or if the focus distance is zero, ie. the camera position is equal to the target,
+    /// since that would require a field of view of 180 degrees.
+    ///
+    pub fn set_perspective_from_orthographic(&mut self) {
+        if let ProjectionType::Orthographic { height } = *self.projection_type() {
+            let focus_distance = self.focus_distance();
+            if focus_distance <= f32::EPSILON {
+                return;
+            }
+            let field_of_view_y = radians(2.0 * (0.5 * height / focus_distance).atan());
+            let z_far = self.z_far();
+            let z_near = if self.z_near() > 0.0 {
+                self.z_near()
+            } else {
+                (0.01 * focus_distance).min(0.5 * z_far)
+            };
+            self.orthographic_width = None;
+            self.camera
+                .set_perspective_projection(field_of_view_y, z_near, z_far);
+        }
+    }
+
+    ///
+    /// Set the current viewport.
+    /// Returns whether or not the viewport actually changed.
+    /// For a perspective projection, the field of view in the vertical direction is kept fixed, and for an orthographic projection,
+    /// the height or, if specified using [Camera::set_orthographic_projection_with_width], the width is kept fixed, so the projection is never stretched.
+    ///
+    pub fn set_viewport(&mut self, viewport: Viewport) -> bool {
+        let changed = self.camera.set_viewport(viewport);
+        if changed {
+            if let Some(width) = self.fixed_orthographic_width() {
+                let (z_near, z_far) = (self.z_near(), self.z_far());
+                self.camera
+                    .set_orthographic_projection(width / viewport.aspect(), z_near, z_far);
+            }
+        }
+        changed
+    }
+
+    ///
+    /// New camera which uses the given view and projection matrices directly, for example to render with an off-axis projection for VR,
+    /// a sheared projection or a projection of a tile of a larger image. See [Camera::set_view_matrix] and [Camera::set_projection_matrix].
+    ///
+    /// **Panics:** If the matrices contain elements that are not finite.
+    ///
+    pub fn new_with_matrices(viewport: Viewport, view: Mat4, projection: Mat4) -> Self {
+        let mut camera = Self::new_orthographic(
+            viewport,
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 0.0, -1.0),
+            vec3(0.0, 1.0, 0.0),
+            1.0,
+            0.0,
+            1.0,
+        );
+        camera.set_view_matrix(view);
+        camera.set_projection_matrix(projection);
+        camera
+    }
+
+    ///
+    /// Sets a custom view matrix which is used instead of the view matrix given by the position, target and up direction of the camera.
+    /// The position, target and up direction are updated to match the view matrix, assuming it is a rigid transformation,
+    /// and the custom view matrix is used when rendering and for frustum culling until [Camera::clear_custom_matrices] is called
+    /// or the view is changed through the camera parameters, for example with [three_d_asset::Camera::set_view], [Camera::animate_to] or a camera control like [OrbitControl](crate::OrbitControl).
+    ///
+    /// **Panics:** If the matrix contains elements that are not finite.
+    ///
+    pub fn set_view_matrix(&mut self, view: Mat4) {
+        assert!(
+            is_finite(&view),
+            "the view matrix must only contain finite elements"
+        );
+        if let Some(inverse) = view.invert() {
+            let position = (inverse * vec4(0.0, 0.0, 0.0, 1.0)).truncate();
+            let direction = (inverse * vec4(0.0, 0.0, -1.0, 0.0)).truncate();
+            let up = (inverse * vec4(0.0, 1.0, 0.0, 0.0)).truncate();
+            if direction.magnitude2() > f32::EPSILON && up.magnitude2() > f32::EPSILON {
+                self.camera
+                    .set_view(position, position + direction.normalize(), up.normalize());
+            }
         }
+        self.custom_view = Some((view, *self.camera.view()));
+    }
+
+    ///
+    /// Sets a custom projection matrix which is used instead of the projection matrix given by the projection type of the camera.
+    /// The custom projection matrix is used when rendering and for frustum culling until [Camera::clear_custom_matrices] is called.
+    ///
+    /// **Note:** Methods that depend on the projection type, for example [three_d_asset::Camera::position_at_pixel], still use the projection type of the camera.
+    ///
+    /// **Panics:** If the matrix contains elements that are not finite.
+    ///
+    pub fn set_projection_matrix(&mut self, projection: Mat4) {
+        assert!(
+            is_finite(&projection),
+            "the projection matrix must only contain finite elements"
+        );
+        self.custom_projection = Some(projection);
+    }
+
+    ///
+    /// Removes the custom view and projection matrices, if any, so that the matrices are again given by the camera parameters.
+    ///
+    pub fn clear_custom_matrices(&mut self) {
+        self.custom_view = None;
+        self.custom_projection = None;
+    }
+
+    ///
+    /// Returns the view matrix, ie. the custom view matrix if specified and the view has not been changed through the camera parameters since (see [Camera::set_view_matrix]),
+    /// otherwise the view matrix given by the position, target and up direction of the camera.
+    ///
+    pub fn view(&self) -> &Mat4 {
+        self.custom_view().unwrap_or(self.camera.view())
+    }
+
+    fn custom_view(&self) -> Option<&Mat4> {
+        self.custom_view
+            .as_ref()
+            .filter(|(_, parameter_view)| parameter_view == self.camera.view())
+            .map(|(view, _)| view)
+    }
+
+    ///
+    /// Returns the projection matrix, ie. the custom projection matrix if specified (see [Camera::set_projection_matrix]),
+    /// otherwise the projection matrix given by the projection type of the camera.
+    ///
+    pub fn projection(&self) -> &Mat4 {
+        self.custom_projection
+            .as_ref()
+            .unwrap_or(self.camera.projection())
+    }
+
+    ///
+    /// Returns whether or not the given bounding box is within the camera frustum.
+    /// It returns false if it is fully outside and true if it is inside or intersects.
+    ///
+    pub fn in_frustum(&self, aabb: &AxisAlignedBoundingBox) -> bool {
+        if self.custom_view().is_none() && self.custom_projection.is_none() {
+            return self.camera.in_frustum(aabb);
+        }
+        aabb_in_frustum(aabb, &self.extract_frustum_planes())
+    }
+
+    ///
+    /// Returns the six planes of the view frustum of this camera in world space in the order left, right, bottom, top, near and far.
+    /// Each plane is given as `(a, b, c, d)` where `(a, b, c)` is the unit normal pointing into the frustum and `d` the signed distance to the origin,
+    /// ie. a point `p` is on the inside of the plane if `a * p.x + b * p.y + c * p.z + d >= 0`.
+    /// Use [aabb_in_frustum] to test a bounding box against the planes.
+    ///
+    pub fn extract_frustum_planes(&self) -> [Vec4; 6] {
+        let m = self.projection() * self.view();
+        [
+            m.row(3) + m.row(0),
+            m.row(3) - m.row(0),
+            m.row(3) + m.row(1),
+            m.row(3) - m.row(1),
+            m.row(3) + m.row(2),
+            m.row(3) - m.row(2),
+        ]
+        .map(|plane| plane / plane.truncate().magnitude())
     }
 
     ///
@@ -93,6 +356,125 @@ impl Camera {
         )
     }
 
+    ///
+    /// Returns the eight corners of the view frustum of this camera in world space.
+    /// The first four corners are on the near plane and the last four on the far plane, in the order
+    /// bottom left, bottom right, top left and top right as seen from the camera.
+    ///
+    pub fn frustum_corners(&self) -> [Vec3; 8] {
+        let inverse = (self.projection() * self.view())
+            .invert()
+            .unwrap_or(Mat4::identity());
+        let mut corners = [Vec3::zero(); 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let ndc = vec4(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+                1.0,
+            );
+            let p = inverse * ndc;
+            *corner = p.truncate() / p.w;
+        }
+        corners
+    }
+
+    ///
+    /// Returns the position in world space at the given pixel and depth, where the depth is the value stored in a depth texture or buffer,
+    /// ie. 0 at the near plane and 1 at the far plane.
+    /// This can for example be used for picking by reading the depth at the pixel below the mouse cursor.
+    /// Works for both perspective and orthographic cameras as well as custom view and projection matrices.
+    ///
+    pub fn world_position_at_pixel(
+        &self,
+        pixel: impl Into<crate::renderer::PhysicalPoint>,
+        depth: f32,
+    ) -> Vec3 {
+        let uv = self.uv_coordinates_at_pixel(pixel);
+        let inverse = (self.projection() * self.view())
+            .invert()
+            .unwrap_or(Mat4::identity());
+        let p = inverse * vec4(2.0 * uv.u - 1.0, 2.0 * uv.v - 1.0, 2.0 * depth - 1.0, 1.0);
+        p.truncate() / p.w
+    }
+
+    ///
+    /// Returns the ray through the given pixel as the origin of the ray on the near plane and the normalized direction of the ray.
+    /// For a perspective camera, all rays go through the camera position and for an orthographic camera, all rays have the same direction.
+    /// Also see [Camera::world_position_at_pixel].
+    ///
+    pub fn ray_at_pixel(&self, pixel: impl Into<crate::renderer::PhysicalPoint>) -> (Vec3, Vec3) {
+        let pixel = pixel.into();
+        let near = self.world_position_at_pixel(pixel, 0.0);
+        let far = self.world_position_at_pixel(pixel, 1.0);
+        (near, (far - near).normalize())
+    }
+
+    ///
+    /// Starts animating the camera from its current position, target and up direction to the given position, target and up direction over the given duration in seconds,
+    /// for example to focus on a selected object or for a cinematic transition.
+    /// The animation is advanced by calling [Camera::update] each frame. The position moves with an ease-in-out curve and the orientation is interpolated using
+    /// spherical linear interpolation, so the camera turns smoothly. A previously started animation is replaced by the new animation.
+    /// If the duration is not positive, the view is set immediately.
+    ///
+    /// Note that camera controls, for example an [OrbitControl](crate::OrbitControl), also change the view, so disable them while the camera is animating.
+    ///
+    pub fn animate_to(&mut self, position: Vec3, target: Vec3, up: Vec3, duration: f32) {
+        if duration <= 0.0 {
+            self.animation = None;
+            self.set_view(position, target, up);
+            return;
+        }
+        self.animation = Some(CameraAnimation {
+            start_position: *self.position(),
+            start_orientation: orientation(self.view_direction(), *self.up()),
+            start_distance: self.focus_distance(),
+            end_position: position,
+            end_orientation: orientation(target - position, up),
+            end_distance: target.distance(position),
+            duration,
+            time: 0.0,
+        });
+    }
+
+    ///
+    /// Advances the animation started with [Camera::animate_to] by the given elapsed time in seconds, ie. `frame_input.elapsed_time / 1000.0`,
+    /// and updates the view accordingly. Returns whether or not the animation is still running after the update,
+    /// which can be used to keep rendering new frames while the camera is moving. Returns false if the camera is not animating.
+    ///
+    pub fn update(&mut self, elapsed_time: f32) -> bool {
+        if let Some(animation) = &mut self.animation {
+            animation.time = (animation.time + elapsed_time).min(animation.duration);
+            let t = animation.time / animation.duration;
+            let t = t * t * (3.0 - 2.0 * t);
+            let position = animation.start_position.lerp(animation.end_position, t);
+            let rotation = animation
+                .start_orientation
+                .slerp(animation.end_orientation, t);
+            let distance =
+                animation.start_distance + (animation.end_distance - animation.start_distance) * t;
+            let finished = animation.time >= animation.duration;
+            self.set_view(
+                position,
+                position + rotation.rotate_vector(vec3(0.0, 0.0, -distance)),
+                rotation.rotate_vector(vec3(0.0, 1.0, 0.0)),
+            );
+            if finished {
+                self.animation = None;
+            }
+            !finished
+        } else {
+            false
+        }
+    }
+
+    ///
+    /// Returns whether or not the camera is currently animating, see [Camera::animate_to].
+    ///
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
     ///
     /// Disables the tone and color mapping so as to be ready for rendering into an intermediate render target with this camera.
     ///
@@ -123,3 +505,429 @@ impl std::ops::DerefMut for Camera {
         &mut self.camera
     }
 }
+
+// Returns the rotation from camera space to world space for a camera looking in the given direction with the given up direction.
+// If the up direction is parallel to the direction, the world axis which is most orthogonal to the direction is used as up direction instead.
+fn orientation(direction: Vec3, up: Vec3) -> Quat {
+    let z = if direction.magnitude2() > f32::EPSILON * f32::EPSILON {
+        -direction.normalize()
+    } else {
+        vec3(0.0, 0.0, 1.0)
+    };
+    let mut x = up.cross(z);
+    if x.magnitude2() <= f32::EPSILON * up.magnitude2() {
+        let fallback_up = if z.x.abs() < z.y.abs() && z.x.abs() < z.z.abs() {
+            vec3(1.0, 0.0, 0.0)
+        } else if z.y.abs() < z.z.abs() {
+            vec3(0.0, 1.0, 0.0)
+        } else {
+            vec3(0.0, 0.0, 1.0)
+        };
+        x = fallback_up.cross(z);
+    }
+    let x = x.normalize();
+    let y = z.cross(x);
+    Mat3::from_cols(x, y, z).into()
+}
+
+fn is_finite(matrix: &Mat4) -> bool {
+    (0..4).all(|c| (0..4).all(|r| matrix[c][r].is_finite()))
+}
+
+///
+/// Returns whether or not the given bounding box is inside or intersects the frustum defined by the given planes, see [Camera::extract_frustum_planes].
+/// For each plane, only the corner of the box furthest along the plane normal is tested, so the test is conservative,
+/// ie. it might return true for a box that is outside the frustum close to one of the frustum corners, but never false for a visible box.
+///
+pub fn aabb_in_frustum(aabb: &AxisAlignedBoundingBox, planes: &[Vec4; 6]) -> bool {
+    if aabb.is_empty() {
+        return false;
+    }
+    if aabb.is_infinite() {
+        return true;
+    }
+    let (min, max) = (aabb.min(), aabb.max());
+    planes.iter().all(|plane| {
+        let p = vec4(
+            if plane.x >= 0.0 { max.x } else { min.x },
+            if plane.y >= 0.0 { max.y } else { min.y },
+            if plane.z >= 0.0 { max.z } else { min.z },
+            1.0,
+        );
+        plane.dot(p) >= 0.0
+    })
+}
+
+///
+/// Returns the normalized device coordinates, ie. `(-1, -1)` at the bottom left corner and `(1, 1)` at the top right corner of the given viewport,
+/// of the given pixel coordinate. The pixel coordinate must be in physical pixels with the origin at the bottom left corner of the screen,
+/// which is the case for the positions in the events of a [FrameInput](crate::FrameInput),
+/// so pixel coordinates with the origin at the top left corner must have their y coordinate flipped (`screen_height - y`) first.
+/// The viewport origin is taken into account, so this also works for a viewport that only covers part of the screen.
+/// See [ndc_to_pixel] for the inverse.
+///
+pub fn pixel_to_ndc(viewport: Viewport, pixel: impl Into<crate::renderer::PhysicalPoint>) -> Vec2 {
+    let pixel = pixel.into();
+    vec2(
+        2.0 * (pixel.x - viewport.x as f32) / viewport.width as f32 - 1.0,
+        2.0 * (pixel.y - viewport.y as f32) / viewport.height as f32 - 1.0,
+    )
+}
+
+///
+/// Returns the pixel coordinate in physical pixels with the origin at the bottom left corner of the screen
+/// of the given normalized device coordinates in the given viewport. This is the inverse of [pixel_to_ndc].
+///
+pub fn ndc_to_pixel(viewport: Viewport, ndc: Vec2) -> crate::renderer::PhysicalPoint {
+    crate::renderer::PhysicalPoint {
+        x: viewport.x as f32 + 0.5 * (ndc.x + 1.0) * viewport.width as f32,
+        y: viewport.y as f32 + 0.5 * (ndc.y + 1.0) * viewport.height as f32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn perspective_camera(position: Vec3) -> Camera {
+        Camera::new_perspective(
+            Viewport::new_at_origo(100, 100),
+            position,
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            degrees(60.0),
+            0.1,
+            100.0,
+        )
+    }
+
+    #[test]
+    fn switch_projection_preserves_framing() {
+        let mut camera = perspective_camera(vec3(0.0, 0.0, 5.0));
+        camera.set_orthographic_from_perspective();
+        let ProjectionType::Orthographic { height } = *camera.projection_type() else {
+            panic!("expected an orthographic projection");
+        };
+        assert!((height - 10.0 * (30.0f32).to_radians().tan()).abs() < 1e-4);
+        camera.set_perspective_from_orthographic();
+        let ProjectionType::Perspective { field_of_view_y } = *camera.projection_type() else {
+            panic!("expected a perspective projection");
+        };
+        assert!((Deg::from(field_of_view_y).0 - 60.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn switch_projection_with_zero_focus_distance() {
+        let mut camera = perspective_camera(vec3(0.0, 0.0, 0.0));
+        camera.set_orthographic_from_perspective();
+        assert!(matches!(
+            camera.projection_type(),
+            ProjectionType::Perspective { .. }
+        ));
+
+        let mut camera = Camera::new_orthographic(
+            Viewport::new_at_origo(100, 100),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            2.0,
+            0.0,
+            100.0,
+        );
+        camera.set_perspective_from_orthographic();
+        assert!(matches!(
+            camera.projection_type(),
+            ProjectionType::Orthographic { .. }
+        ));
+    }
+
+    #[test]
+    fn orientation_with_up_parallel_to_direction() {
+        for (direction, up) in [
+            (vec3(0.0, -1.0, 0.0), vec3(0.0, 1.0, 0.0)),
+            (vec3(0.0, 0.0, -2.0), vec3(0.0, 0.0, 3.0)),
+            (vec3(1.0, 1.0, 0.0), vec3(1.0, 1.0, 0.0)),
+            (vec3(1.0, 0.0, 0.0), vec3(0.0, 0.0, 0.0)),
+        ] {
+            let rotation = orientation(direction, up);
+            let forward = rotation.rotate_vector(vec3(0.0, 0.0, -1.0));
+            let camera_up = rotation.rotate_vector(vec3(0.0, 1.0, 0.0));
+            assert!((forward - direction.normalize()).magnitude() < 1e-5);
+            assert!((camera_up.magnitude() - 1.0).abs() < 1e-5);
+            assert!(camera_up.dot(forward).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn animate_to_top_view() {
+        let mut camera = perspective_camera(vec3(0.0, 0.0, 5.0));
+        camera.animate_to(
+            vec3(0.0, 5.0, 0.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            1.0,
+        );
+        camera.update(0.5);
+        assert!(is_finite(camera.view()));
+        camera.update(0.5);
+        assert!(is_finite(camera.view()));
+        assert!((camera.position() - vec3(0.0, 5.0, 0.0)).magnitude() < 1e-4);
+    }
+
+    fn assert_matrix_eq(a: &Mat4, b: &Mat4) {
+        for c in 0..4 {
+            for r in 0..4 {
+                assert!((a[c][r] - b[c][r]).abs() < 1e-4, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn custom_matrices_round_trip() {
+        let view = Mat4::look_at_rh(
+            Point3::new(1.0, 2.0, 3.0),
+            Point3::new(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+        );
+        let projection = cgmath::frustum(-0.2, 0.1, -0.1, 0.1, 0.1, 10.0);
+        let mut camera =
+            Camera::new_with_matrices(Viewport::new_at_origo(100, 100), view, projection);
+        assert_eq!(*camera.view(), view);
+        assert_eq!(*camera.projection(), projection);
+        assert!((camera.position() - vec3(1.0, 2.0, 3.0)).magnitude() < 1e-5);
+        assert!((camera.view_direction() - vec3(-1.0, -2.0, -3.0).normalize()).magnitude() < 1e-5);
+
+        camera.clear_custom_matrices();
+        assert_matrix_eq(camera.view(), &view);
+        assert_ne!(*camera.projection(), projection);
+    }
+
+    #[test]
+    fn custom_view_is_replaced_by_camera_parameters() {
+        let mut camera = perspective_camera(vec3(0.0, 0.0, 5.0));
+        let view = Mat4::from_nonuniform_scale(1.0, 2.0, 1.0)
+            * Mat4::from_translation(vec3(0.0, 0.0, -5.0));
+        camera.set_view_matrix(view);
+        assert_eq!(*camera.view(), view);
+
+        camera.set_view(
+            vec3(0.0, 0.0, 10.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+        );
+        assert_matrix_eq(
+            camera.view(),
+            &Mat4::from_translation(vec3(0.0, 0.0, -10.0)),
+        );
+
+        camera.set_view_matrix(view);
+        camera.animate_to(
+            vec3(0.0, 0.0, 3.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            1.0,
+        );
+        camera.update(1.0);
+        assert_matrix_eq(camera.view(), &Mat4::from_translation(vec3(0.0, 0.0, -3.0)));
+    }
+
+    #[test]
+    fn custom_projection_is_used_for_frustum_culling() {
+        let mut camera = perspective_camera(vec3(0.0, 0.0, 5.0));
+        let aabb = AxisAlignedBoundingBox::new_with_positions(&[
+            vec3(-0.1, -0.1, -0.1),
+            vec3(0.1, 0.1, 0.1),
+        ]);
+        assert!(camera.in_frustum(&aabb));
+        // An off-axis projection which only sees the right half of the scene
+        camera.set_projection_matrix(cgmath::frustum(0.01, 0.1, -0.1, 0.1, 0.1, 100.0));
+        assert!(!camera.in_frustum(&aabb));
+        assert!(
+            camera.in_frustum(&AxisAlignedBoundingBox::new_with_positions(&[
+                vec3(1.0, -0.1, -0.1),
+                vec3(1.2, 0.1, 0.1),
+            ]))
+        );
+    }
+
+    fn aabb(min: Vec3, max: Vec3) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::new_with_positions(&[min, max])
+    }
+
+    #[test]
+    fn frustum_planes_contain_frustum_corners() {
+        for camera in [
+            perspective_camera(vec3(1.0, 2.0, 5.0)),
+            Camera::new_orthographic(
+                Viewport::new_at_origo(200, 100),
+                vec3(0.0, 3.0, 0.0),
+                vec3(0.0, 0.0, 0.0),
+                vec3(0.0, 0.0, -1.0),
+                4.0,
+                0.5,
+                20.0,
+            ),
+        ] {
+            let planes = camera.extract_frustum_planes();
+            for plane in planes.iter() {
+                assert!((plane.truncate().magnitude() - 1.0).abs() < 1e-5);
+            }
+            let center = camera.frustum_corners().iter().sum::<Vec3>() / 8.0;
+            for plane in planes.iter() {
+                assert!(plane.dot(center.extend(1.0)) > 0.0);
+            }
+            for corner in camera.frustum_corners() {
+                for plane in planes.iter() {
+                    let d = plane.dot(corner.extend(1.0));
+                    assert!(d > -1e-3 * corner.magnitude().max(1.0), "{:?}", d);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn aabb_frustum_containment() {
+        let camera = perspective_camera(vec3(0.0, 0.0, 5.0));
+        let planes = camera.extract_frustum_planes();
+        let inside = aabb(vec3(-0.5, -0.5, -0.5), vec3(0.5, 0.5, 0.5));
+        let behind = aabb(vec3(-0.5, -0.5, 6.0), vec3(0.5, 0.5, 7.0));
+        let left = aabb(vec3(-20.0, -0.5, -0.5), vec3(-10.0, 0.5, 0.5));
+        let beyond_far = aabb(vec3(-0.5, -0.5, -200.0), vec3(0.5, 0.5, -150.0));
+        let intersecting = aabb(vec3(-20.0, -0.5, -0.5), vec3(0.0, 0.5, 0.5));
+        assert!(aabb_in_frustum(&inside, &planes));
+        assert!(aabb_in_frustum(&intersecting, &planes));
+        assert!(!aabb_in_frustum(&behind, &planes));
+        assert!(!aabb_in_frustum(&left, &planes));
+        assert!(!aabb_in_frustum(&beyond_far, &planes));
+        assert!(!aabb_in_frustum(&AxisAlignedBoundingBox::EMPTY, &planes));
+        assert!(aabb_in_frustum(&AxisAlignedBoundingBox::INFINITE, &planes));
+        for aabb in [inside, behind, left, beyond_far, intersecting] {
+            assert_eq!(aabb_in_frustum(&aabb, &planes), camera.in_frustum(&aabb));
+        }
+    }
+
+    fn orthographic_size(camera: &Camera) -> (f32, f32) {
+        let ProjectionType::Orthographic { height } = *camera.projection_type() else {
+            panic!("expected an orthographic projection");
+        };
+        (height * camera.viewport().aspect(), height)
+    }
+
+    #[test]
+    fn orthographic_projection_with_fixed_width() {
+        let mut camera = perspective_camera(vec3(0.0, 0.0, 5.0));
+        camera.set_orthographic_projection_with_width(8.0, 0.1, 100.0);
+        assert_eq!(camera.fixed_orthographic_width(), Some(8.0));
+        let (width, height) = orthographic_size(&camera);
+        assert!((width - 8.0).abs() < 1e-5 && (height - 8.0).abs() < 1e-5);
+
+        camera.set_viewport(Viewport::new_at_origo(100, 400));
+        let (width, height) = orthographic_size(&camera);
+        assert!((width - 8.0).abs() < 1e-5);
+        assert!((height - 32.0).abs() < 1e-4);
+        // The projection is not stretched, ie. a square in world space is a square in pixels
+        let pixel_at = |p: Vec3| {
+            let p = camera.projection() * camera.view() * p.extend(1.0);
+            let pixel = ndc_to_pixel(camera.viewport(), p.truncate().truncate() / p.w);
+            vec2(pixel.x, pixel.y)
+        };
+        let origin = pixel_at(vec3(0.0, 0.0, 0.0));
+        let right = pixel_at(vec3(1.0, 0.0, 0.0)) - origin;
+        let up = pixel_at(vec3(0.0, 1.0, 0.0)) - origin;
+        assert!((right.magnitude() - up.magnitude()).abs() < 1e-3);
+        assert!((right.magnitude() - 12.5).abs() < 1e-3);
+
+        camera.set_orthographic_projection(2.0, 0.1, 100.0);
+        assert_eq!(camera.fixed_orthographic_width(), None);
+        camera.set_viewport(Viewport::new_at_origo(400, 100));
+        let (width, height) = orthographic_size(&camera);
+        assert!((height - 2.0).abs() < 1e-5);
+        assert!((width - 8.0).abs() < 1e-5);
+    }
+
+    fn orthographic_camera() -> Camera {
+        Camera::new_orthographic(
+            Viewport {
+                x: 10,
+                y: 20,
+                width: 300,
+                height: 200,
+            },
+            vec3(2.0, 3.0, 8.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            10.0,
+            0.1,
+            30.0,
+        )
+    }
+
+    // Returns the pixel and depth of the given world position.
+    fn project(camera: &Camera, p: Vec3) -> (crate::renderer::PhysicalPoint, f32) {
+        let p = camera.projection() * camera.view() * p.extend(1.0);
+        let ndc = p.truncate() / p.w;
+        (
+            ndc_to_pixel(camera.viewport(), ndc.truncate()),
+            0.5 * ndc.z + 0.5,
+        )
+    }
+
+    #[test]
+    fn world_position_at_pixel_round_trip() {
+        for camera in [
+            perspective_camera(vec3(1.0, 2.0, 5.0)),
+            orthographic_camera(),
+        ] {
+            for p in [
+                vec3(0.0, 0.0, 0.0),
+                vec3(0.5, -0.3, 1.0),
+                vec3(-1.0, 0.2, -2.0),
+            ] {
+                let (pixel, depth) = project(&camera, p);
+                let q = camera.world_position_at_pixel(pixel, depth);
+                assert!((p - q).magnitude() < 1e-3, "{:?} != {:?}", p, q);
+            }
+        }
+    }
+
+    #[test]
+    fn ray_at_pixel_goes_through_position() {
+        let p = vec3(0.5, -0.3, 1.0);
+        let camera = perspective_camera(vec3(1.0, 2.0, 5.0));
+        let (origin, direction) = camera.ray_at_pixel(project(&camera, p).0);
+        assert!((direction.magnitude() - 1.0).abs() < 1e-5);
+        assert!((direction - (p - camera.position()).normalize()).magnitude() < 1e-4);
+        assert!((origin - camera.position()).magnitude() < camera.z_near() * 1.5);
+
+        let camera = orthographic_camera();
+        let (origin, direction) = camera.ray_at_pixel(project(&camera, p).0);
+        assert!((direction - camera.view_direction()).magnitude() < 1e-4);
+        let to_point = p - origin;
+        assert!((to_point - direction * to_point.dot(direction)).magnitude() < 1e-3);
+        let (_, other_direction) = camera.ray_at_pixel((15.0, 25.0));
+        assert!((other_direction - direction).magnitude() < 1e-4);
+    }
+
+    #[test]
+    fn pixel_to_ndc_corners_and_center() {
+        let viewport = Viewport {
+            x: 10,
+            y: 20,
+            width: 300,
+            height: 200,
+        };
+        for (pixel, ndc) in [
+            ((10.0, 20.0), vec2(-1.0, -1.0)),
+            ((310.0, 20.0), vec2(1.0, -1.0)),
+            ((10.0, 220.0), vec2(-1.0, 1.0)),
+            ((310.0, 220.0), vec2(1.0, 1.0)),
+            ((160.0, 120.0), vec2(0.0, 0.0)),
+        ] {
+            assert!((pixel_to_ndc(viewport, pixel) - ndc).magnitude() < 1e-6);
+            let p = ndc_to_pixel(viewport, ndc);
+            assert!((p.x - pixel.0).abs() < 1e-4 && (p.y - pixel.1).abs() < 1e-4);
+        }
+        let p = ndc_to_pixel(viewport, pixel_to_ndc(viewport, (42.5, 77.25)));
+        assert!((p.x - 42.5).abs() < 1e-4 && (p.y - 77.25).abs() < 1e-4);
+    }
+}
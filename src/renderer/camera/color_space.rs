@@ -2,6 +2,8 @@ use crate::core::*;
 
 ///
 /// Color space mapping used for mapping to/from color spaces when rendering.
+/// All shading is computed in linear sRGB color space, where sRGB encoded input colors are decoded before use,
+/// for example using [Texture2D::new_srgb] for textures, and [ColorMapping::ComputeToSrgb] encodes the result to sRGB when writing to the screen.
 ///
 #[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum ColorMapping {
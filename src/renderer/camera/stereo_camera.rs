@@ -0,0 +1,172 @@
+use crate::renderer::*;
+
+///
+/// A camera which renders the scene from two eyes, for example for side-by-side output to a 3D display or a simple VR setup.
+/// The two eye cameras are placed half the interpupillary distance to the left and right of the center camera along its right direction.
+/// For a perspective center camera, the eye cameras use asymmetric (off-axis) projections that converge at the convergence distance,
+/// ie. objects at that distance appear at the same position in both eyes.
+///
+#[derive(Clone, Debug)]
+pub struct StereoCamera {
+    /// The center camera, ie. the camera placed between the two eyes. The viewport of this camera is split into a left and right half, one for each eye.
+    pub camera: Camera,
+    /// The distance between the two eyes.
+    pub interpupillary_distance: f32,
+    /// The distance from the eyes to the plane where the views of the two eyes converge.
+    pub convergence_distance: f32,
+}
+
+impl StereoCamera {
+    ///
+    /// Creates a new stereo camera from the given center camera, interpupillary distance and convergence distance.
+    ///
+    pub fn new(camera: Camera, interpupillary_distance: f32, convergence_distance: f32) -> Self {
+        Self {
+            camera,
+            interpupillary_distance,
+            convergence_distance,
+        }
+    }
+
+    ///
+    /// Returns the viewport of the left eye, ie. the left half of the viewport of the center camera.
+    ///
+    pub fn left_viewport(&self) -> Viewport {
+        let viewport = self.camera.viewport();
+        Viewport {
+            width: viewport.width / 2,
+            ..viewport
+        }
+    }
+
+    ///
+    /// Returns the viewport of the right eye, ie. the right half of the viewport of the center camera.
+    ///
+    pub fn right_viewport(&self) -> Viewport {
+        let viewport = self.camera.viewport();
+        Viewport {
+            x: viewport.x + (viewport.width / 2) as i32,
+            width: viewport.width - viewport.width / 2,
+            ..viewport
+        }
+    }
+
+    ///
+    /// Returns the camera for the left eye which renders into the [StereoCamera::left_viewport].
+    ///
+    pub fn left_camera(&self) -> Camera {
+        self.eye_camera(-0.5 * self.interpupillary_distance, self.left_viewport())
+    }
+
+    ///
+    /// Returns the camera for the right eye which renders into the [StereoCamera::right_viewport].
+    ///
+    pub fn right_camera(&self) -> Camera {
+        self.eye_camera(0.5 * self.interpupillary_distance, self.right_viewport())
+    }
+
+    ///
+    /// Renders the given objects with the given lights into the given render target, where the left eye is rendered into the left half
+    /// and the right eye into the right half of the viewport of the center camera.
+    /// To render each eye into a separate texture instead, use [StereoCamera::left_camera] and [StereoCamera::right_camera]
+    /// and set the viewport of the returned cameras to the viewport of the textures.
+    ///
+    pub fn render<'a>(
+        &self,
+        render_target: &'a RenderTarget<'a>,
+        objects: impl IntoIterator<Item = impl Object> + Clone,
+        lights: &[&dyn Light],
+    ) -> &'a RenderTarget<'a> {
+        render_target
+            .render(&self.left_camera(), objects.clone(), lights)
+            .render(&self.right_camera(), objects, lights)
+    }
+
+    fn eye_camera(&self, offset: f32, viewport: Viewport) -> Camera {
+        let mut camera = self.camera.clone();
+        camera.clear_custom_matrices();
+        camera.set_viewport(viewport);
+        let translation = offset * self.camera.right_direction().normalize();
+        let position = *self.camera.position() + translation;
+        let target = *self.camera.target() + translation;
+        let up = *self.camera.up();
+        camera.set_view(position, target, up);
+        if let ProjectionType::Perspective { field_of_view_y } = *self.camera.projection_type() {
+            let z_near = self.camera.z_near();
+            let top = z_near * (0.5 * field_of_view_y.0).tan();
+            let right = viewport.aspect() * top;
+            let shift = offset * z_near / self.convergence_distance.max(f32::EPSILON);
+            camera.set_projection_matrix(frustum(
+                -right - shift,
+                right - shift,
+                -top,
+                top,
+                z_near,
+                self.camera.z_far(),
+            ));
+        }
+        camera
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stereo_camera() -> StereoCamera {
+        StereoCamera::new(
+            Camera::new_perspective(
+                Viewport::new_at_origo(201, 100),
+                vec3(1.0, 2.0, 5.0),
+                vec3(0.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                degrees(60.0),
+                0.1,
+                100.0,
+            ),
+            0.064,
+            2.0,
+        )
+    }
+
+    fn ndc(camera: &Camera, p: Vec3) -> Vec3 {
+        let p = camera.projection() * camera.view() * p.extend(1.0);
+        p.truncate() / p.w
+    }
+
+    #[test]
+    fn eyes_are_offset_along_right_direction() {
+        let stereo = stereo_camera();
+        let (left, right) = (stereo.left_camera(), stereo.right_camera());
+        let right_direction = stereo.camera.right_direction().normalize();
+        let offset = right.position() - left.position();
+        assert!((offset - 0.064 * right_direction).magnitude() < 1e-5);
+        let center = 0.5 * (left.position() + right.position());
+        assert!((center - stereo.camera.position()).magnitude() < 1e-5);
+        assert!((left.view_direction() - stereo.camera.view_direction()).magnitude() < 1e-5);
+        assert!((right.view_direction() - stereo.camera.view_direction()).magnitude() < 1e-5);
+    }
+
+    #[test]
+    fn eye_viewports_split_the_viewport() {
+        let stereo = stereo_camera();
+        let (left, right) = (stereo.left_viewport(), stereo.right_viewport());
+        assert_eq!((left.x, left.width), (0, 100));
+        assert_eq!((right.x, right.width), (100, 101));
+        assert_eq!(stereo.left_camera().viewport(), left);
+        assert_eq!(stereo.right_camera().viewport(), right);
+    }
+
+    #[test]
+    fn eyes_converge_at_convergence_distance() {
+        let stereo = stereo_camera();
+        let (left, right) = (stereo.left_camera(), stereo.right_camera());
+        let p = *stereo.camera.position() + 2.0 * stereo.camera.view_direction().normalize();
+        let (left_ndc, right_ndc) = (ndc(&left, p), ndc(&right, p));
+        assert!((left_ndc.x - right_ndc.x).abs() < 1e-4);
+        assert!((left_ndc.y - right_ndc.y).abs() < 1e-4);
+        // Objects further away are shifted to the right in the right eye
+        let far = *stereo.camera.position() + 10.0 * stereo.camera.view_direction().normalize();
+        assert!(ndc(&right, far).x > ndc(&left, far).x);
+    }
+}
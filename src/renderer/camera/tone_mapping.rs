@@ -15,6 +15,8 @@ pub enum ToneMapping {
     Aces = 2,
     /// John Hables presentation "Uncharted 2 HDR Lighting", Page 142 to 143. `<http://www.gdcvault.com/play/1012459/Uncharted_2__HDR_Lighting>`
     Filmic = 3,
+    /// No tone mapping curve, the colors are only clamped to the range `[0,1]`, which is useful together with an exposure, see [ToneMappingEffect].
+    Clamp = 4,
 }
 
 impl ToneMapping {
@@ -45,6 +47,8 @@ impl ToneMapping {
                 x = ((x*(A*x+C*B)+D*E)/(x*(A*x+B)+D*F))-E/F;
                 color = x.xyz / x.w;
                 color = clamp(color, 0.0, 1.0);
+            } else if(toneMappingType == 4u) {
+                color = clamp(color, 0.0, 1.0);
             }
             return color;
         }
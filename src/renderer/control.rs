@@ -22,10 +22,31 @@ mod fly_control;
 #[doc(inline)]
 pub use fly_control::*;
 
+mod trackball_control;
+#[doc(inline)]
+pub use trackball_control::*;
+
+mod touch_gestures;
+#[doc(inline)]
+pub use touch_gestures::*;
+
+mod event_recording;
+#[doc(inline)]
+pub use event_recording::*;
+
 pub use three_d_asset::PixelPoint as PhysicalPoint;
 
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "PhysicalPoint")]
+struct PhysicalPointDef {
+    x: f32,
+    y: f32,
+}
+
 /// Type of mouse button.
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton {
     /// Left mouse button or one finger on touch.
     Left,
@@ -35,14 +56,31 @@ pub enum MouseButton {
     Middle,
 }
 
+/// The phase of a touch point, see [Event::Touch].
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TouchPhase {
+    /// A finger started touching the screen.
+    Start,
+    /// A finger touching the screen moved.
+    Move,
+    /// A finger stopped touching the screen.
+    End,
+    /// The touch was cancelled, for example because the window lost focus. Should be treated as [TouchPhase::End].
+    Cancel,
+}
+
 /// An input event (from mouse, keyboard or similar).
+/// Events can be serialized if the `serde` feature is enabled, for example to store an [EventRecording] in a file.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     /// Fired when a button is pressed or the screen is touched.
     MousePress {
         /// Type of button
         button: MouseButton,
         /// The screen position in physical pixels.
+        #[cfg_attr(feature = "serde", serde(with = "PhysicalPointDef"))]
         position: PhysicalPoint,
         /// The state of modifiers.
         modifiers: Modifiers,
@@ -54,6 +92,7 @@ pub enum Event {
         /// Type of button
         button: MouseButton,
         /// The screen position in physical pixels.
+        #[cfg_attr(feature = "serde", serde(with = "PhysicalPointDef"))]
         position: PhysicalPoint,
         /// The state of modifiers.
         modifiers: Modifiers,
@@ -67,6 +106,7 @@ pub enum Event {
         /// The relative movement of the mouse/finger since last [Event::MouseMotion] event in logical pixels.
         delta: (f32, f32),
         /// The screen position in physical pixels.
+        #[cfg_attr(feature = "serde", serde(with = "PhysicalPointDef"))]
         position: PhysicalPoint,
         /// The state of modifiers.
         modifiers: Modifiers,
@@ -74,16 +114,40 @@ pub enum Event {
         handled: bool,
     },
     /// Fired continuously when the mouse wheel or equivalent is applied.
+    /// A positive vertical delta means scrolling up, ie. rolling the mouse wheel away from the user or moving two fingers up on a trackpad with natural scrolling disabled,
+    /// and a positive horizontal delta means scrolling to the right.
     MouseWheel {
-        /// The relative scrolling since the last [Event::MouseWheel] event.
+        /// The relative scrolling since the last [Event::MouseWheel] event in logical pixels.
+        /// If the scrolling is reported in lines, see `lines`, this is the number of lines multiplied by a line height of 24 logical pixels.
         delta: (f32, f32),
+        /// The relative scrolling in lines if the device reports the scrolling in lines, which is typically the case for a mouse wheel,
+        /// and `None` if the scrolling is reported in pixels, which is typically the case for a trackpad or a pinch gesture on a touch screen.
+        /// On web, this corresponds to a wheel event with a `deltaMode` of `DOM_DELTA_LINE`.
+        lines: Option<(f32, f32)>,
         /// The screen position in physical pixels.
+        #[cfg_attr(feature = "serde", serde(with = "PhysicalPointDef"))]
         position: PhysicalPoint,
         /// The state of modifiers.
         modifiers: Modifiers,
         /// Whether or not this event already have been handled.
         handled: bool,
     },
+    /// Fired when a finger starts touching, moves on or stops touching the screen.
+    /// Each finger is identified by an id which is the same for all events from that finger from it starts touching the screen until it stops.
+    /// The first finger also generates [Event::MousePress], [Event::MouseMotion] and [Event::MouseRelease] events with the left mouse button
+    /// and moving two fingers apart or together generates [Event::MouseWheel] events, so controls which only handle mouse events also work on touch screens.
+    /// Use [TouchGestures] to recognize pinch, rotate and two-finger pan gestures from these events.
+    Touch {
+        /// The id of the finger.
+        id: u64,
+        /// The phase of the touch.
+        phase: TouchPhase,
+        /// The screen position in physical pixels.
+        #[cfg_attr(feature = "serde", serde(with = "PhysicalPointDef"))]
+        position: PhysicalPoint,
+        /// Whether or not this event already have been handled.
+        handled: bool,
+    },
     /// Fired when the mouse enters the window.
     MouseEnter,
     /// Fired when the mouse leaves the window.
@@ -118,6 +182,7 @@ pub enum Event {
 /// Keyboard key input.
 #[allow(missing_docs)]
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     ArrowDown,
     ArrowLeft,
@@ -186,8 +251,9 @@ pub enum Key {
     Z,
 }
 
-/// State of modifiers (alt, ctrl, shift and command).
+/// State of modifiers (alt, ctrl, shift, meta and command).
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Modifiers {
     /// Either of the alt keys are down (option ⌥ on Mac).
     pub alt: bool,
@@ -196,7 +262,26 @@ pub struct Modifiers {
     pub ctrl: bool,
     /// Either of the shift keys are down.
     pub shift: bool,
+    /// Either of the meta keys are down, ie. the Windows key on Windows, the Super key on Linux and the Command key ⌘ on Mac.
+    pub meta: bool,
     /// On Windows and Linux, set this to the same value as `ctrl`.
     /// On Mac, this should be set whenever one of the ⌘ Command keys are down.
     pub command: bool,
 }
+
+impl Modifiers {
+    ///
+    /// Creates the state of modifiers from which of the alt, control, shift and meta keys are down,
+    /// where [Modifiers::command] is set to the state of the meta keys on Mac and to the state of the control keys on other platforms.
+    /// Use this instead of a struct expression to avoid breaking changes if more modifiers are added.
+    ///
+    pub fn new(alt: bool, ctrl: bool, shift: bool, meta: bool, is_mac: bool) -> Self {
+        Self {
+            alt,
+            ctrl,
+            shift,
+            meta,
+            command: if is_mac { meta } else { ctrl },
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::renderer::*;
+
+///
+/// A recording of the [Event]s of a number of frames, which can be replayed later using [EventRecording::replay],
+/// for example to test interactive features in a reproducible way.
+/// If the `serde` feature is enabled, the recording can be serialized, for example to store it in a file.
+///
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventRecording {
+    /// The recorded events for each frame.
+    pub frames: Vec<Vec<Event>>,
+}
+
+impl EventRecording {
+    ///
+    /// Creates a new empty recording.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Records the given events as the events of the next frame. Should be called once each frame, also if there are no events.
+    ///
+    pub fn record(&mut self, events: &[Event]) {
+        self.frames.push(events.to_vec());
+    }
+
+    ///
+    /// Returns the number of recorded frames.
+    ///
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    ///
+    /// Returns whether or not no frames are recorded.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    ///
+    /// Returns a replay of this recording where each frame takes the given fixed time in milliseconds,
+    /// which makes the replay deterministic independent of the actual frame rate.
+    ///
+    pub fn replay(&self, frame_time: f64) -> EventReplay<'_> {
+        EventReplay {
+            recording: self,
+            frame: 0,
+            frame_time,
+        }
+    }
+}
+
+///
+/// A replay of an [EventRecording], see [EventRecording::replay].
+/// Iterating the replay returns the events of each frame in the recording.
+///
+pub struct EventReplay<'a> {
+    recording: &'a EventRecording,
+    frame: usize,
+    frame_time: f64,
+}
+
+impl EventReplay<'_> {
+    ///
+    /// Returns the fixed time in milliseconds of each frame.
+    ///
+    pub fn frame_time(&self) -> f64 {
+        self.frame_time
+    }
+
+    ///
+    /// Returns the accumulated time in milliseconds of the frames replayed so far.
+    ///
+    pub fn accumulated_time(&self) -> f64 {
+        self.frame as f64 * self.frame_time
+    }
+
+    ///
+    /// Returns whether or not all frames in the recording have been replayed.
+    ///
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.recording.len()
+    }
+}
+
+impl Iterator for EventReplay<'_> {
+    type Item = Vec<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let events = self.recording.frames.get(self.frame)?.clone();
+        self.frame += 1;
+        Some(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Vec<Vec<Event>> {
+        vec![
+            vec![Event::MouseMotion {
+                button: Some(MouseButton::Left),
+                delta: (1.0, -2.0),
+                position: PhysicalPoint { x: 10.0, y: 20.0 },
+                modifiers: Modifiers::default(),
+                handled: false,
+            }],
+            vec![],
+            vec![
+                Event::KeyPress {
+                    kind: Key::A,
+                    modifiers: Modifiers::default(),
+                    handled: false,
+                },
+                Event::Text("a".to_string()),
+            ],
+        ]
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut recording = EventRecording::new();
+        assert!(recording.is_empty());
+        for events in frames() {
+            recording.record(&events);
+        }
+        assert_eq!(recording.len(), 3);
+
+        let mut replay = recording.replay(16.0);
+        assert_eq!(replay.frame_time(), 16.0);
+        assert_eq!(replay.accumulated_time(), 0.0);
+        for (i, expected) in frames().iter().enumerate() {
+            assert!(!replay.is_finished());
+            let events = replay.next().unwrap();
+            assert_eq!(format!("{:?}", events), format!("{:?}", expected));
+            assert_eq!(replay.accumulated_time(), (i + 1) as f64 * 16.0);
+        }
+        assert!(replay.is_finished());
+        assert!(replay.next().is_none());
+    }
+}
@@ -2,9 +2,15 @@ use crate::renderer::*;
 
 ///
 /// A control that makes the camera fly through the 3D scene.
+/// If [FlyControl::touch_gestures] is enabled, pinching with two fingers moves the camera forward and backward and dragging with two fingers moves the camera sideways and up and down.
 ///
 pub struct FlyControl {
     control: CameraControl,
+    speed: f32,
+    /// Whether or not to use two-finger touch gestures recognized by [TouchGestures] for moving the camera. Disabled by default,
+    /// in which case a pinch still moves the camera forward and backward through the [Event::MouseWheel] events emulated from the touch events.
+    pub touch_gestures: bool,
+    gestures: TouchGestures,
 }
 
 impl FlyControl {
@@ -23,11 +29,26 @@ impl FlyControl {
                 right_drag_vertical: CameraAction::Up { speed },
                 ..Default::default()
             },
+            speed,
+            touch_gestures: false,
+            gestures: TouchGestures::new(),
         }
     }
 
     /// Handles the events. Must be called each frame.
     pub fn handle_events(&mut self, camera: &mut Camera, events: &mut [Event]) -> bool {
-        self.control.handle_events(camera, events)
+        let mut change = false;
+        if self.touch_gestures {
+            if let Some(gesture) = self.gestures.handle_events(events) {
+                let right = camera.right_direction();
+                let up = right.cross(camera.view_direction());
+                let translation = camera.view_direction() * self.speed * gesture.spread
+                    - right * self.speed * gesture.pan.x
+                    - up * self.speed * gesture.pan.y;
+                camera.translate(&translation);
+                change = true;
+            }
+        }
+        self.control.handle_events(camera, events) || change
     }
 }
@@ -0,0 +1,186 @@
+use crate::renderer::*;
+
+///
+/// A two-finger gesture recognized by [TouchGestures] during one frame.
+/// The gesture is a combination of a pan, a pinch and a rotation, since all three are usually performed at the same time.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchGesture {
+    /// The point in the middle between the two fingers at the end of the frame in physical pixels.
+    pub center: PhysicalPoint,
+    /// The movement of the point in the middle between the two fingers in physical pixels.
+    pub pan: Vec2,
+    /// The ratio between the distance between the two fingers at the end and at the start of the frame,
+    /// ie. larger than one when the fingers move apart and smaller than one when the fingers move together.
+    pub scale: f32,
+    /// The change in distance between the two fingers in physical pixels, positive when the fingers move apart.
+    pub spread: f32,
+    /// The angle the line between the two fingers has rotated counterclockwise.
+    pub rotation: Radians,
+}
+
+///
+/// Recognizes pinch, rotate and two-finger pan gestures from [Event::Touch] events, for example to zoom and pan the camera on touch screens.
+/// The recognizer keeps track of all fingers touching the screen by their id, and when at least two fingers are touching the screen,
+/// the movement of the first two fingers is reported as a [TouchGesture].
+///
+#[derive(Clone, Debug, Default)]
+pub struct TouchGestures {
+    touches: Vec<(u64, PhysicalPoint)>,
+}
+
+impl TouchGestures {
+    ///
+    /// Creates a new touch gesture recognizer with no fingers touching the screen.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Handles the events. Must be called each frame.
+    /// Returns the two-finger gesture performed during this frame if two fingers have been touching the screen during the whole frame, otherwise `None`.
+    /// While two or more fingers are touching the screen, the [Event::MouseWheel] events emulated from the touch events are marked as handled,
+    /// so they are not used for zooming by other controls as well.
+    ///
+    pub fn handle_events(&mut self, events: &mut [Event]) -> Option<TouchGesture> {
+        let start = self.two_fingers();
+        for event in events.iter_mut() {
+            match event {
+                Event::Touch {
+                    id,
+                    phase,
+                    position,
+                    ..
+                } => match phase {
+                    TouchPhase::Start | TouchPhase::Move => {
+                        if let Some(touch) = self.touches.iter_mut().find(|(i, _)| i == id) {
+                            touch.1 = *position;
+                        } else {
+                            self.touches.push((*id, *position));
+                        }
+                    }
+                    TouchPhase::End | TouchPhase::Cancel => {
+                        self.touches.retain(|(i, _)| i != id);
+                    }
+                },
+                Event::MouseWheel {
+                    lines: None,
+                    handled,
+                    ..
+                } if self.touches.len() >= 2 => {
+                    *handled = true;
+                }
+                _ => {}
+            }
+        }
+        let end = self.two_fingers();
+        match (start, end) {
+            (Some(((id0, p0), (id1, p1))), Some(((jd0, q0), (jd1, q1))))
+                if id0 == jd0 && id1 == jd1 =>
+            {
+                let (p0, p1) = (vec2(p0.x, p0.y), vec2(p1.x, p1.y));
+                let (q0, q1) = (vec2(q0.x, q0.y), vec2(q1.x, q1.y));
+                let (start_distance, end_distance) = ((p1 - p0).magnitude(), (q1 - q0).magnitude());
+                let center = 0.5 * (q0 + q1);
+                Some(TouchGesture {
+                    center: PhysicalPoint {
+                        x: center.x,
+                        y: center.y,
+                    },
+                    pan: center - 0.5 * (p0 + p1),
+                    scale: if start_distance > 0.0 {
+                        end_distance / start_distance
+                    } else {
+                        1.0
+                    },
+                    spread: end_distance - start_distance,
+                    rotation: (p1 - p0).angle(q1 - q0),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns the number of fingers currently touching the screen.
+    ///
+    pub fn touch_count(&self) -> usize {
+        self.touches.len()
+    }
+
+    fn two_fingers(&self) -> Option<((u64, PhysicalPoint), (u64, PhysicalPoint))> {
+        if self.touches.len() >= 2 {
+            Some((self.touches[0], self.touches[1]))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(id: u64, phase: TouchPhase, x: f32, y: f32) -> Event {
+        Event::Touch {
+            id,
+            phase,
+            position: PhysicalPoint { x, y },
+            handled: false,
+        }
+    }
+
+    #[test]
+    fn pinch_pan_and_rotate() {
+        let mut gestures = TouchGestures::new();
+        let mut events = vec![
+            touch(3, TouchPhase::Start, 0.0, 0.0),
+            touch(7, TouchPhase::Start, 10.0, 0.0),
+        ];
+        assert!(gestures.handle_events(&mut events).is_none());
+        assert_eq!(gestures.touch_count(), 2);
+
+        // Move the fingers apart to twice the distance, rotate them a quarter turn counterclockwise and move the center by (5, 5).
+        let mut events = vec![
+            touch(3, TouchPhase::Move, 10.0, -5.0),
+            touch(7, TouchPhase::Move, 10.0, 15.0),
+        ];
+        let gesture = gestures.handle_events(&mut events).unwrap();
+        assert!((gesture.scale - 2.0).abs() < 1e-5);
+        assert!((gesture.spread - 10.0).abs() < 1e-5);
+        assert!((gesture.pan - vec2(5.0, 5.0)).magnitude() < 1e-5);
+        assert!((gesture.rotation.0 - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+        assert_eq!(gesture.center, PhysicalPoint { x: 10.0, y: 5.0 });
+
+        let mut events = vec![touch(7, TouchPhase::End, 10.0, 15.0)];
+        assert!(gestures.handle_events(&mut events).is_none());
+        assert_eq!(gestures.touch_count(), 1);
+    }
+
+    #[test]
+    fn emulated_mouse_wheel_is_handled_while_two_fingers_touch() {
+        let mut gestures = TouchGestures::new();
+        let wheel = || Event::MouseWheel {
+            delta: (0.0, 1.0),
+            lines: None,
+            position: PhysicalPoint { x: 0.0, y: 0.0 },
+            modifiers: Modifiers::default(),
+            handled: false,
+        };
+        let mut events = vec![
+            touch(0, TouchPhase::Start, 0.0, 0.0),
+            touch(1, TouchPhase::Start, 10.0, 0.0),
+            wheel(),
+        ];
+        gestures.handle_events(&mut events);
+        assert!(matches!(events[2], Event::MouseWheel { handled: true, .. }));
+
+        let mut events = vec![touch(1, TouchPhase::Cancel, 10.0, 0.0), wheel()];
+        gestures.handle_events(&mut events);
+        assert!(matches!(
+            events[1],
+            Event::MouseWheel { handled: false, .. }
+        ));
+    }
+}
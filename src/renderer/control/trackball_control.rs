@@ -0,0 +1,260 @@
+use crate::renderer::*;
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// The duration in seconds of the frame that [TrackballControl::damping] is specified for.
+const DAMPING_FRAME_DURATION: f32 = 1.0 / 60.0;
+
+///
+/// A control that makes the camera rotate freely around a target like a trackball, ie. with no fixed up direction.
+/// Dragging with the left mouse button rotates the camera around the target, dragging with the right mouse button pans the camera and the target
+/// and scrolling zooms towards the target.
+/// If [TrackballControl::touch_gestures] is enabled, pinching with two fingers zooms towards the target and dragging with two fingers pans the camera and the target.
+/// If [TrackballControl::damping] is larger than zero, the rotation continues for a while after the mouse button is released.
+///
+pub struct TrackballControl {
+    /// The point the camera rotates around.
+    pub target: Vec3,
+    /// The angle in radians the camera rotates when dragging a distance equal to the height of the viewport.
+    pub rotation_speed: f32,
+    /// The fraction of the rotation speed that is kept every 1/60 second after the mouse button is released, in the range `[0, 1)`.
+    /// The rotation slows down at the same rate independent of the frame rate, since the damping is scaled by the time between calls to [TrackballControl::handle_events].
+    /// A value of zero means that the rotation stops immediately when the mouse button is released.
+    pub damping: f32,
+    /// The minimum distance to the target when zooming.
+    pub min_distance: f32,
+    /// The maximum distance to the target when zooming.
+    pub max_distance: f32,
+    /// Whether or not to use two-finger touch gestures recognized by [TouchGestures] for zooming and panning. Disabled by default,
+    /// in which case a pinch still zooms through the [Event::MouseWheel] events emulated from the touch events, but without panning.
+    pub touch_gestures: bool,
+    gestures: TouchGestures,
+    last_position: Option<PhysicalPoint>,
+    last_time: Option<Instant>,
+    /// The rotation axis and the angular speed in radians per second.
+    rotation: Option<(Vec3, f32)>,
+}
+
+impl TrackballControl {
+    /// Creates a new trackball control with the given target and rotation speed (see [TrackballControl::rotation_speed]).
+    pub fn new(target: Vec3, rotation_speed: f32) -> Self {
+        Self {
+            target,
+            rotation_speed,
+            damping: 0.0,
+            min_distance: 0.01,
+            max_distance: f32::MAX,
+            touch_gestures: false,
+            gestures: TouchGestures::new(),
+            last_position: None,
+            last_time: None,
+            rotation: None,
+        }
+    }
+
+    /// Handles the events. Must be called each frame.
+    pub fn handle_events(&mut self, camera: &mut Camera, events: &mut [Event]) -> bool {
+        let now = Instant::now();
+        let elapsed_time = self
+            .last_time
+            .map(|last_time| now.duration_since(last_time).as_secs_f32().min(0.1))
+            .unwrap_or(DAMPING_FRAME_DURATION);
+        self.last_time = Some(now);
+        let mut change = false;
+        let mut drag_rotation: Option<(Vec3, f32)> = None;
+        if self.touch_gestures {
+            if let Some(gesture) = self.gestures.handle_events(events) {
+                let distance = self.target.distance(*camera.position());
+                camera.zoom_towards(
+                    &self.target,
+                    distance * (1.0 - 1.0 / gesture.scale.max(f32::EPSILON)),
+                    self.min_distance,
+                    self.max_distance,
+                );
+                self.pan(camera, gesture.pan / camera.viewport().height.max(1) as f32);
+                change = true;
+            }
+        }
+        for event in events.iter_mut() {
+            match event {
+                Event::MousePress {
+                    position, handled, ..
+                } if !*handled => {
+                    self.last_position = Some(*position);
+                    self.rotation = None;
+                }
+                Event::MouseRelease { .. } => {
+                    self.last_position = None;
+                }
+                Event::MouseMotion {
+                    button: Some(button),
+                    position,
+                    handled,
+                    ..
+                } if !*handled => {
+                    if let Some(last_position) = self.last_position {
+                        let delta =
+                            vec2(position.x - last_position.x, position.y - last_position.y)
+                                / camera.viewport().height.max(1) as f32;
+                        match button {
+                            MouseButton::Left => {
+                                if let Some((axis, angle)) = self.rotate(camera, delta) {
+                                    let total_angle =
+                                        drag_rotation.map_or(0.0, |(_, angle)| angle) + angle;
+                                    drag_rotation = Some((axis, total_angle));
+                                }
+                            }
+                            MouseButton::Right => self.pan(camera, delta),
+                            MouseButton::Middle => {}
+                        }
+                        *handled = true;
+                        change = true;
+                    }
+                    self.last_position = Some(*position);
+                }
+                Event::MouseWheel { delta, handled, .. } if !*handled => {
+                    let distance = self.target.distance(*camera.position());
+                    camera.zoom_towards(
+                        &self.target,
+                        (0.01 * distance + 0.001) * delta.1,
+                        self.min_distance,
+                        self.max_distance,
+                    );
+                    *handled = true;
+                    change = true;
+                }
+                _ => {}
+            }
+        }
+        if let Some((axis, angle)) = drag_rotation {
+            self.rotation = Some((axis, angle / elapsed_time.max(f32::EPSILON)));
+        } else if self.last_position.is_some() {
+            // The mouse button is held without moving, so there should be no rotation when it is released.
+            self.rotation = None;
+        } else {
+            change |= self.apply_inertia(camera, elapsed_time);
+        }
+        change
+    }
+
+    ///
+    /// Continues the rotation after the mouse button is released, slowing it down according to the damping and the elapsed time in seconds.
+    ///
+    fn apply_inertia(&mut self, camera: &mut Camera, elapsed_time: f32) -> bool {
+        if let Some((axis, speed)) = self.rotation {
+            let speed = speed
+                * self
+                    .damping
+                    .clamp(0.0, 0.999)
+                    .powf(elapsed_time / DAMPING_FRAME_DURATION);
+            if (speed * DAMPING_FRAME_DURATION).abs() > 1.0e-4 {
+                self.apply_rotation(camera, axis, speed * elapsed_time);
+                self.rotation = Some((axis, speed));
+                return true;
+            }
+            self.rotation = None;
+        }
+        false
+    }
+
+    fn rotate(&mut self, camera: &mut Camera, delta: Vec2) -> Option<(Vec3, f32)> {
+        let direction = camera.view_direction();
+        let up = camera.up().normalize();
+        let right = direction.cross(up).normalize();
+        let drag = right * delta.x - up * delta.y;
+        let axis = drag.cross(direction);
+        if axis.magnitude2() > f32::EPSILON {
+            let axis = axis.normalize();
+            let angle = -delta.magnitude() * self.rotation_speed;
+            self.apply_rotation(camera, axis, angle);
+            Some((axis, angle))
+        } else {
+            None
+        }
+    }
+
+    fn apply_rotation(&self, camera: &mut Camera, axis: Vec3, angle: f32) {
+        let rotation = Quat::from_axis_angle(axis, radians(angle));
+        let position = self.target + rotation.rotate_vector(*camera.position() - self.target);
+        let target = self.target + rotation.rotate_vector(*camera.target() - self.target);
+        let up = rotation.rotate_vector(*camera.up());
+        camera.set_view(position, target, up);
+    }
+
+    fn pan(&mut self, camera: &mut Camera, delta: Vec2) {
+        let view_height = match camera.projection_type() {
+            ProjectionType::Orthographic { height } => *height,
+            ProjectionType::Perspective { field_of_view_y } => {
+                2.0 * self.target.distance(*camera.position()) * (0.5 * field_of_view_y.0).tan()
+            }
+        };
+        let direction = camera.view_direction();
+        let up = camera.up().normalize();
+        let right = direction.cross(up).normalize();
+        let translation = (right * -delta.x + up * delta.y) * view_height;
+        camera.translate(&translation);
+        self.target += translation;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Camera {
+        Camera::new_perspective(
+            Viewport::new_at_origo(100, 100),
+            vec3(0.0, 0.0, 5.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            degrees(45.0),
+            0.1,
+            100.0,
+        )
+    }
+
+    fn control(speed: f32) -> TrackballControl {
+        let mut control = TrackballControl::new(vec3(0.0, 0.0, 0.0), 1.0);
+        control.damping = 0.9;
+        control.rotation = Some((vec3(0.0, 1.0, 0.0), speed));
+        control
+    }
+
+    #[test]
+    fn inertia_is_independent_of_frame_rate() {
+        let mut camera = camera();
+        let mut fast = control(1.0);
+        for _ in 0..4 {
+            assert!(fast.apply_inertia(&mut camera, 1.0 / 120.0));
+        }
+        let mut slow = control(1.0);
+        assert!(slow.apply_inertia(&mut camera, 1.0 / 30.0));
+        let (fast_speed, slow_speed) = (fast.rotation.unwrap().1, slow.rotation.unwrap().1);
+        assert!((fast_speed - slow_speed).abs() < 1.0e-5);
+        assert!((slow_speed - 0.9f32.powi(2)).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn inertia_stops() {
+        let mut camera = camera();
+        let mut control = control(1.0);
+        let mut frames = 0;
+        while control.apply_inertia(&mut camera, DAMPING_FRAME_DURATION) {
+            frames += 1;
+            assert!(frames < 1000);
+        }
+        assert!(control.rotation.is_none());
+        assert_ne!(*camera.position(), vec3(0.0, 0.0, 5.0));
+    }
+
+    #[test]
+    fn no_inertia_without_damping() {
+        let mut camera = camera();
+        let mut control = control(1.0);
+        control.damping = 0.0;
+        assert!(!control.apply_inertia(&mut camera, DAMPING_FRAME_DURATION));
+        assert_eq!(*camera.position(), vec3(0.0, 0.0, 5.0));
+    }
+}
@@ -64,6 +64,34 @@ mod water;
 #[doc(inline)]
 pub use water::*;
 
+mod radial_blur;
+#[doc(inline)]
+pub use radial_blur::*;
+
+mod color_correction;
+#[doc(inline)]
+pub use color_correction::*;
+
+mod bloom;
+#[doc(inline)]
+pub use bloom::*;
+
+mod ssao;
+#[doc(inline)]
+pub use ssao::*;
+
+mod tone_mapping;
+#[doc(inline)]
+pub use tone_mapping::*;
+
+mod outline;
+#[doc(inline)]
+pub use outline::*;
+
+mod post_process_pass;
+#[doc(inline)]
+pub use post_process_pass::*;
+
 pub(crate) mod lighting_pass;
 
 use crate::renderer::*;
@@ -0,0 +1,264 @@
+use crate::renderer::*;
+
+///
+/// An effect that makes bright areas of the scene glow, ie. the light from bright pixels bleeds into the surrounding pixels.
+/// The bright pixels, ie. pixels where the brightest color channel is above [BloomEffect::threshold], are extracted from the color texture
+/// and repeatedly downsampled into a chain of smaller textures, which are then blurred and added together when upsampling back up the chain.
+/// Finally, the result is added to the color texture when the effect is applied.
+///
+/// The blurred textures must be computed by calling [BloomEffect::prepare] with the color texture before the effect is applied with the same color texture,
+/// for example using [RenderTarget::apply_screen_effect].
+/// The color texture should be a floating point texture containing HDR colors, ie. colors with values above one,
+/// otherwise a threshold below one is needed to get any glow.
+/// The tone and color mapping defined in the [Camera] is applied, so use this as the last effect in the pipeline, or disable the mapping on the camera.
+///
+pub struct BloomEffect {
+    context: Context,
+    /// The brightness above which pixels start to glow. Colors are in linear HDR space, so the default value of one means that only colors brighter than white glow.
+    pub threshold: f32,
+    /// The strength of the glow added to the color texture.
+    pub intensity: f32,
+    /// The radius of the blur in texels at each level of the downsampled chain. Larger values give a wider but less smooth glow.
+    pub radius: f32,
+    /// The maximum number of times the bright pixels are downsampled. More levels give a wider glow.
+    pub levels: u32,
+    textures: Vec<Texture2D>,
+}
+
+impl BloomEffect {
+    ///
+    /// Creates a new bloom effect with default parameters. The textures are allocated the first time [BloomEffect::prepare] is called.
+    ///
+    pub fn new(context: &Context) -> Self {
+        Self {
+            context: context.clone(),
+            threshold: 1.0,
+            intensity: 0.1,
+            radius: 1.0,
+            levels: 5,
+            textures: Vec::new(),
+        }
+    }
+
+    ///
+    /// Extracts and blurs the bright pixels of the given color texture.
+    /// Must be called before the effect is applied and each time the content of the color texture has changed, typically each frame.
+    ///
+    pub fn prepare(&mut self, color_texture: ColorTexture) {
+        self.resize(color_texture.width(), color_texture.height());
+
+        for i in 0..self.textures.len() {
+            let (read, write) = self.textures.split_at_mut(i);
+            let (input, input_width, input_height) = if i == 0 {
+                (color_texture, color_texture.width(), color_texture.height())
+            } else {
+                let input = &read[i - 1];
+                (ColorTexture::Single(input), input.width(), input.height())
+            };
+            let output = &mut write[0];
+            let camera = Camera::new_2d(Viewport::new_at_origo(output.width(), output.height()));
+            output
+                .as_color_target(None)
+                .clear(ClearState::color(0.0, 0.0, 0.0, 1.0))
+                .apply_screen_effect(
+                    &BloomPass::Downsample {
+                        texel_size: vec2(1.0 / input_width as f32, 1.0 / input_height as f32),
+                        threshold: if i == 0 { self.threshold.max(0.0) } else { 0.0 },
+                    },
+                    &camera,
+                    &[],
+                    Some(input),
+                    None,
+                );
+        }
+
+        for i in (0..self.textures.len().saturating_sub(1)).rev() {
+            let (write, read) = self.textures.split_at_mut(i + 1);
+            let input = &read[0];
+            let output = &mut write[i];
+            let camera = Camera::new_2d(Viewport::new_at_origo(output.width(), output.height()));
+            output.as_color_target(None).apply_screen_effect(
+                &BloomPass::Upsample {
+                    texel_size: vec2(1.0 / input.width() as f32, 1.0 / input.height() as f32),
+                    radius: self.radius,
+                },
+                &camera,
+                &[],
+                Some(ColorTexture::Single(input)),
+                None,
+            );
+        }
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        let mut sizes = Vec::new();
+        let (mut w, mut h) = (width / 2, height / 2);
+        while sizes.len() < self.levels.max(1) as usize && w > 0 && h > 0 {
+            sizes.push((w, h));
+            w /= 2;
+            h /= 2;
+        }
+        if sizes.is_empty() {
+            sizes.push((1, 1));
+        }
+        if sizes.len() != self.textures.len()
+            || sizes
+                .iter()
+                .zip(self.textures.iter())
+                .any(|((w, h), t)| *w != t.width() || *h != t.height())
+        {
+            self.textures = sizes
+                .into_iter()
+                .map(|(w, h)| {
+                    Texture2D::new_empty::<[f16; 4]>(
+                        &self.context,
+                        w,
+                        h,
+                        Interpolation::Linear,
+                        Interpolation::Linear,
+                        None,
+                        Wrapping::ClampToEdge,
+                        Wrapping::ClampToEdge,
+                    )
+                })
+                .collect();
+        }
+    }
+}
+
+impl Effect for BloomEffect {
+    fn fragment_shader_source(
+        &self,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) -> String {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a bloom effect");
+        format!(
+            "{}\n{}\n{}\n{}",
+            color_texture.fragment_shader_source(),
+            ToneMapping::fragment_shader_source(),
+            ColorMapping::fragment_shader_source(),
+            include_str!("shaders/bloom_effect.frag")
+        )
+    }
+
+    fn id(&self, color_texture: Option<ColorTexture>, _depth_texture: Option<DepthTexture>) -> u16 {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a bloom effect");
+        0b1u16 << 14 | 0b1u16 << 7 | color_texture.id()
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes {
+            uv: true,
+            ..FragmentAttributes::NONE
+        }
+    }
+
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        camera: &Camera,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) {
+        let bloom_texture = self
+            .textures
+            .first()
+            .expect("BloomEffect::prepare must be called before applying a bloom effect");
+        camera.tone_mapping.use_uniforms(program);
+        camera.color_mapping.use_uniforms(program);
+        color_texture
+            .expect("Must supply a color texture to apply a bloom effect")
+            .use_uniforms(program);
+        program.use_texture("bloomMap", bloom_texture);
+        program.use_uniform("intensity", self.intensity);
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            cull: Cull::Back,
+            ..Default::default()
+        }
+    }
+}
+
+enum BloomPass {
+    Downsample { texel_size: Vec2, threshold: f32 },
+    Upsample { texel_size: Vec2, radius: f32 },
+}
+
+impl Effect for BloomPass {
+    fn fragment_shader_source(
+        &self,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) -> String {
+        format!(
+            "{}\n{}",
+            color_texture.unwrap().fragment_shader_source(),
+            match self {
+                Self::Downsample { .. } => include_str!("shaders/bloom_downsample.frag"),
+                Self::Upsample { .. } => include_str!("shaders/bloom_upsample.frag"),
+            }
+        )
+    }
+
+    fn id(&self, color_texture: Option<ColorTexture>, _depth_texture: Option<DepthTexture>) -> u16 {
+        0b1u16 << 14
+            | match self {
+                Self::Downsample { .. } => 0b1u16 << 9,
+                Self::Upsample { .. } => 0b1u16 << 8,
+            }
+            | color_texture.unwrap().id()
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes {
+            uv: true,
+            ..FragmentAttributes::NONE
+        }
+    }
+
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        _camera: &Camera,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) {
+        color_texture.unwrap().use_uniforms(program);
+        match self {
+            Self::Downsample {
+                texel_size,
+                threshold,
+            } => {
+                program.use_uniform("texelSize", texel_size);
+                program.use_uniform("threshold", threshold);
+            }
+            Self::Upsample { texel_size, radius } => {
+                program.use_uniform("texelSize", texel_size);
+                program.use_uniform("radius", radius);
+            }
+        }
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            blend: match self {
+                Self::Downsample { .. } => Blend::Disabled,
+                Self::Upsample { .. } => Blend::ADD,
+            },
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::renderer::*;
+
+///
+/// An effect that applies simple color adjustments to the color texture, ie. exposure, brightness, contrast, saturation and gamma.
+/// The adjustments are applied in that order. The default values do not change the colors.
+/// Apply this effect after any tone mapping, for example after rendering to an intermediate texture with [Camera::disable_tone_and_color_mapping]
+/// and then applying the tone and color mapping using [ScreenEffect].
+///
+#[derive(Clone, Debug)]
+pub struct ColorCorrectionEffect {
+    /// The exposure in stops, ie. the color is multiplied by `2^exposure`.
+    pub exposure: f32,
+    /// The brightness which is added to the color.
+    pub brightness: f32,
+    /// The contrast, ie. how much the color is scaled away from (or towards) middle gray. A value of 1 gives no change.
+    pub contrast: f32,
+    /// The saturation, where 0 gives a grayscale image and 1 gives no change.
+    pub saturation: f32,
+    /// The gamma, ie. the color is raised to the power of `1 / gamma`. A value of 1 gives no change.
+    pub gamma: f32,
+}
+
+impl Default for ColorCorrectionEffect {
+    fn default() -> Self {
+        Self {
+            exposure: 0.0,
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl ColorCorrectionEffect {
+    ///
+    /// Applies the color correction to the given color on the CPU using the same computations as when the effect is applied on the GPU,
+    /// for example to preview the result for a single color. The alpha channel is left unchanged.
+    ///
+    pub fn apply(&self, color: Vec4) -> Vec4 {
+        let mut rgb = color.truncate() * self.exposure.exp2();
+        rgb += vec3(self.brightness, self.brightness, self.brightness);
+        rgb = (rgb - vec3(0.5, 0.5, 0.5)) * self.contrast + vec3(0.5, 0.5, 0.5);
+        let luminance = rgb.dot(vec3(0.2126, 0.7152, 0.0722));
+        rgb = vec3(luminance, luminance, luminance).lerp(rgb, self.saturation);
+        let exponent = 1.0 / self.gamma.max(0.001);
+        vec4(
+            rgb.x.max(0.0).powf(exponent),
+            rgb.y.max(0.0).powf(exponent),
+            rgb.z.max(0.0).powf(exponent),
+            color.w,
+        )
+    }
+}
+
+impl Effect for ColorCorrectionEffect {
+    fn fragment_shader_source(
+        &self,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) -> String {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a color correction effect");
+        format!(
+            "{}\n{}",
+            color_texture.fragment_shader_source(),
+            include_str!("shaders/color_correction_effect.frag")
+        )
+    }
+
+    fn id(&self, color_texture: Option<ColorTexture>, _depth_texture: Option<DepthTexture>) -> u16 {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a color correction effect");
+        0b1u16 << 14 | 0b1u16 << 10 | color_texture.id()
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes {
+            uv: true,
+            ..FragmentAttributes::NONE
+        }
+    }
+
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        _camera: &Camera,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a color correction effect");
+        color_texture.use_uniforms(program);
+        program.use_uniform("exposure", self.exposure);
+        program.use_uniform("brightness", self.brightness);
+        program.use_uniform("contrast", self.contrast);
+        program.use_uniform("saturation", self.saturation);
+        program.use_uniform("gamma", self.gamma.max(0.001));
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            cull: Cull::Back,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Vec4, b: Vec4) {
+        assert!((a - b).magnitude() < 1e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn default_does_not_change_colors() {
+        let color = vec4(0.2, 0.5, 0.9, 0.7);
+        assert_near(ColorCorrectionEffect::default().apply(color), color);
+    }
+
+    #[test]
+    fn zero_saturation_gives_grayscale() {
+        let effect = ColorCorrectionEffect {
+            saturation: 0.0,
+            ..Default::default()
+        };
+        let result = effect.apply(vec4(1.0, 0.5, 0.0, 1.0));
+        let luminance = 0.2126 + 0.7152 * 0.5;
+        assert_near(result, vec4(luminance, luminance, luminance, 1.0));
+    }
+
+    #[test]
+    fn exposure_and_gamma() {
+        let effect = ColorCorrectionEffect {
+            exposure: 1.0,
+            gamma: 2.0,
+            ..Default::default()
+        };
+        assert_near(
+            effect.apply(vec4(0.125, 0.0, 0.5, 1.0)),
+            vec4(0.5, 0.0, 1.0, 1.0),
+        );
+    }
+}
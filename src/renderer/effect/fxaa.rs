@@ -3,9 +3,29 @@ use crate::renderer::*;
 ///
 /// A simple anti-aliasing approach which smooths otherwise jagged edges (for example lines) but also
 /// smooths the rest of the image.
+/// The edges are detected using the luma of the colors, so this effect should be applied after tone and color mapping,
+/// ie. to a color texture with colors in sRGB color space, for example as the last effect in the pipeline after a [ScreenEffect].
+/// The viewport size is not needed, the resolution is read from the given color texture.
 ///
-#[derive(Clone, Default, Debug)]
-pub struct FxaaEffect {}
+#[derive(Clone, Debug)]
+pub struct FxaaEffect {
+    /// The maximum length in pixels of the search along an edge. Higher values smooth longer edges at the cost of more blur.
+    pub span_max: f32,
+    /// Reduces the length of the search along an edge relative to the local luma, ie. lower values give more smoothing of small details (sub-pixel aliasing).
+    pub reduce_mul: f32,
+    /// The minimum reduction of the length of the search along an edge, which avoids over-smoothing in dark areas.
+    pub reduce_min: f32,
+}
+
+impl Default for FxaaEffect {
+    fn default() -> Self {
+        Self {
+            span_max: 8.0,
+            reduce_mul: 1.0 / 8.0,
+            reduce_min: 1.0 / 128.0,
+        }
+    }
+}
 
 impl Effect for FxaaEffect {
     fn fragment_shader_source(
@@ -50,6 +70,9 @@ impl Effect for FxaaEffect {
         let h = color_texture.height();
         color_texture.use_uniforms(program);
         program.use_uniform("resolution", vec2(w as f32, h as f32));
+        program.use_uniform("spanMax", self.span_max);
+        program.use_uniform("reduceMul", self.reduce_mul);
+        program.use_uniform("reduceMin", self.reduce_min);
     }
 
     fn render_states(&self) -> RenderStates {
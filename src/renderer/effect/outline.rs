@@ -0,0 +1,129 @@
+use crate::renderer::*;
+
+///
+/// An effect that draws an outline with the given color and thickness around a set of geometries, for example to highlight selected objects in an editor.
+/// The geometries are first rendered into a mask by calling [OutlineEffect::prepare] and then the outline is drawn on top of the scene
+/// where the pixels are close to, but not covered by, the geometries when the effect is applied, for example using [RenderTarget::apply_screen_effect].
+/// No color or depth texture is needed when applying the effect, so the outline is drawn on top of any other objects.
+///
+pub struct OutlineEffect {
+    context: Context,
+    /// The color of the outline.
+    pub color: Srgba,
+    /// The thickness of the outline in pixels.
+    pub thickness: f32,
+    mask: Option<Texture2D>,
+}
+
+impl OutlineEffect {
+    ///
+    /// Creates a new outline effect with the given color and thickness in pixels.
+    ///
+    pub fn new(context: &Context, color: Srgba, thickness: f32) -> Self {
+        Self {
+            context: context.clone(),
+            color,
+            thickness,
+            mask: None,
+        }
+    }
+
+    ///
+    /// Renders the geometries that should be outlined into the mask using the given camera.
+    /// Must be called before the effect is applied and each time the geometries or the camera has changed, typically each frame.
+    ///
+    pub fn prepare(
+        &mut self,
+        camera: &Camera,
+        geometries: impl IntoIterator<Item = impl Geometry>,
+    ) {
+        let viewport = camera.viewport();
+        if self
+            .mask
+            .as_ref()
+            .map(|t| t.width() != viewport.width || t.height() != viewport.height)
+            .unwrap_or(true)
+        {
+            self.mask = Some(Texture2D::new_empty::<[u8; 4]>(
+                &self.context,
+                viewport.width,
+                viewport.height,
+                Interpolation::Nearest,
+                Interpolation::Nearest,
+                None,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+            ));
+        }
+        let mut mask_camera = camera.clone();
+        mask_camera.set_viewport(Viewport::new_at_origo(viewport.width, viewport.height));
+        let material = ColorMaterial {
+            color: Srgba::WHITE,
+            ..Default::default()
+        };
+        self.mask
+            .as_mut()
+            .unwrap()
+            .as_color_target(None)
+            .clear(ClearState::color(0.0, 0.0, 0.0, 0.0))
+            .render_with_material(&material, &mask_camera, geometries, &[]);
+    }
+}
+
+impl Effect for OutlineEffect {
+    fn fragment_shader_source(
+        &self,
+        _lights: &[&dyn Light],
+        _color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) -> String {
+        format!(
+            "{}\n{}",
+            ColorMapping::fragment_shader_source(),
+            include_str!("shaders/outline_effect.frag")
+        )
+    }
+
+    fn id(
+        &self,
+        _color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) -> u16 {
+        0b1u16 << 14 | 0b1u16 << 10 | 0b1u16 << 7
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes {
+            uv: true,
+            ..FragmentAttributes::NONE
+        }
+    }
+
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        camera: &Camera,
+        _lights: &[&dyn Light],
+        _color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) {
+        let mask = self
+            .mask
+            .as_ref()
+            .expect("OutlineEffect::prepare must be called before applying an outline effect");
+        camera.color_mapping.use_uniforms(program);
+        program.use_texture("maskTexture", mask);
+        program.use_uniform("color", self.color.to_linear_srgb());
+        program.use_uniform("thickness", self.thickness.max(0.0));
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            blend: Blend::TRANSPARENCY,
+            cull: Cull::Back,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::renderer::*;
+
+///
+/// Applies a sequence of [Effect]s to a color and/or depth texture, for example the result of rendering a scene into a [TextureRenderTarget].
+/// Each effect, except the last, is rendered into an intermediate color texture which is used as the color input to the next effect,
+/// while the last effect is rendered into the given render target. The depth texture is given as input to all of the effects.
+/// The intermediate textures are owned by the pass and are only reallocated when the size of the render target changes.
+///
+/// Note that effects which apply the tone and color mapping defined in the [Camera], for example [ScreenEffect], should only be applied once,
+/// so either disable the tone and color mapping on the camera for all but the last effect or use an effect without mapping, for example [CopyEffect].
+///
+pub struct PostProcessPass {
+    context: Context,
+    textures: Vec<Texture2D>,
+}
+
+impl PostProcessPass {
+    ///
+    /// Creates a new post-processing pass. The intermediate textures are allocated the first time they are needed.
+    ///
+    pub fn new(context: &Context) -> Self {
+        Self {
+            context: context.clone(),
+            textures: Vec::new(),
+        }
+    }
+
+    ///
+    /// Applies the effects in the given order and writes the result of the last effect to the given render target.
+    /// Use an empty array for the `lights` argument, if the effects do not require lights to be rendered.
+    /// Nothing is written if no effects are given.
+    ///
+    pub fn apply(
+        &mut self,
+        render_target: &RenderTarget,
+        effects: &[&dyn Effect],
+        camera: &Camera,
+        lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        depth_texture: Option<DepthTexture>,
+    ) {
+        if effects.len() > 1 {
+            self.resize(render_target.width(), render_target.height(), effects.len());
+        }
+        for (i, effect) in effects.iter().enumerate() {
+            if i + 1 == effects.len() {
+                let input = if i == 0 {
+                    color_texture
+                } else {
+                    Some(ColorTexture::Single(&self.textures[(i - 1) % 2]))
+                };
+                render_target.apply_screen_effect(*effect, camera, lights, input, depth_texture);
+            } else {
+                let (first, second) = self.textures.split_at_mut(1);
+                let (write, read) = if i % 2 == 0 {
+                    (&mut first[0], second.first())
+                } else {
+                    (&mut second[0], first.first())
+                };
+                let input = if i == 0 {
+                    color_texture
+                } else {
+                    read.map(ColorTexture::Single)
+                };
+                write
+                    .as_color_target(None)
+                    .clear(ClearState::color(0.0, 0.0, 0.0, 0.0))
+                    .apply_screen_effect(*effect, camera, lights, input, depth_texture);
+            }
+        }
+    }
+
+    fn resize(&mut self, width: u32, height: u32, effect_count: usize) {
+        let count = (effect_count - 1).min(2);
+        if self.textures.len() < count
+            || self
+                .textures
+                .first()
+                .map(|t| t.width() != width || t.height() != height)
+                .unwrap_or(false)
+        {
+            self.textures = (0..count)
+                .map(|_| {
+                    Texture2D::new_empty::<[f16; 4]>(
+                        &self.context,
+                        width,
+                        height,
+                        Interpolation::Linear,
+                        Interpolation::Linear,
+                        None,
+                        Wrapping::ClampToEdge,
+                        Wrapping::ClampToEdge,
+                    )
+                })
+                .collect();
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::renderer::*;
+
+///
+/// An effect that blurs the color texture radially from a center point, ie. the colors are smeared towards the center.
+/// This can be used to simulate speed and, if applied to a texture with only the bright parts of the scene, as a cheap approximation of light shafts.
+///
+#[derive(Clone, Debug)]
+pub struct RadialBlurEffect {
+    /// The center of the blur in uv coordinates, ie. `(0, 0)` is the bottom left corner and `(1, 1)` is the top right corner of the screen.
+    pub center: Vec2,
+    /// The strength of the blur, ie. the fraction of the distance towards the center that the colors are smeared.
+    pub strength: f32,
+    /// The number of samples used for each pixel. Higher gives a smoother blur but is more expensive.
+    pub samples: u32,
+}
+
+impl Default for RadialBlurEffect {
+    fn default() -> Self {
+        Self {
+            center: vec2(0.5, 0.5),
+            strength: 0.1,
+            samples: 16,
+        }
+    }
+}
+
+impl Effect for RadialBlurEffect {
+    fn fragment_shader_source(
+        &self,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) -> String {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a radial blur effect");
+        format!(
+            "{}\n{}",
+            color_texture.fragment_shader_source(),
+            include_str!("shaders/radial_blur_effect.frag")
+        )
+    }
+
+    fn id(&self, color_texture: Option<ColorTexture>, _depth_texture: Option<DepthTexture>) -> u16 {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a radial blur effect");
+        0b1u16 << 14 | 0b1u16 << 11 | color_texture.id()
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes {
+            uv: true,
+            ..FragmentAttributes::NONE
+        }
+    }
+
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        _camera: &Camera,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a radial blur effect");
+        color_texture.use_uniforms(program);
+        program.use_uniform("center", self.center);
+        program.use_uniform("strength", self.strength);
+        program.use_uniform("samples", self.samples.max(1) as i32);
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            cull: Cull::Back,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,19 @@
+uniform vec2 texelSize;
+uniform float threshold;
+
+in vec2 uvs;
+
+layout (location = 0) out vec4 outColor;
+
+void main()
+{
+    vec3 color = 0.25 * (sample_color(uvs + texelSize * vec2(-1.0, -1.0)).rgb
+        + sample_color(uvs + texelSize * vec2(1.0, -1.0)).rgb
+        + sample_color(uvs + texelSize * vec2(-1.0, 1.0)).rgb
+        + sample_color(uvs + texelSize * vec2(1.0, 1.0)).rgb);
+    if (threshold > 0.0) {
+        float brightness = max(color.r, max(color.g, color.b));
+        color *= max(brightness - threshold, 0.0) / max(brightness, 0.0001);
+    }
+    outColor = vec4(color, 1.0);
+}
@@ -0,0 +1,15 @@
+uniform sampler2D bloomMap;
+uniform float intensity;
+
+in vec2 uvs;
+
+layout (location = 0) out vec4 outColor;
+
+void main()
+{
+    vec4 color = sample_color(uvs);
+    color.rgb += intensity * texture(bloomMap, uvs).rgb;
+    outColor.rgb = tone_mapping(color.rgb);
+    outColor.rgb = color_mapping(outColor.rgb);
+    outColor.a = color.a;
+}
@@ -0,0 +1,21 @@
+uniform vec2 texelSize;
+uniform float radius;
+
+in vec2 uvs;
+
+layout (location = 0) out vec4 outColor;
+
+void main()
+{
+    vec2 d = texelSize * radius;
+    vec3 color = 4.0 * sample_color(uvs).rgb;
+    color += 2.0 * (sample_color(uvs + vec2(d.x, 0.0)).rgb
+        + sample_color(uvs - vec2(d.x, 0.0)).rgb
+        + sample_color(uvs + vec2(0.0, d.y)).rgb
+        + sample_color(uvs - vec2(0.0, d.y)).rgb);
+    color += sample_color(uvs + d).rgb
+        + sample_color(uvs - d).rgb
+        + sample_color(uvs + vec2(d.x, -d.y)).rgb
+        + sample_color(uvs + vec2(-d.x, d.y)).rgb;
+    outColor = vec4(color / 16.0, 1.0);
+}
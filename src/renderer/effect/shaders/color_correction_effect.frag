@@ -0,0 +1,22 @@
+
+uniform float exposure;
+uniform float brightness;
+uniform float contrast;
+uniform float saturation;
+uniform float gamma;
+
+in vec2 uvs;
+
+layout (location = 0) out vec4 color;
+
+void main()
+{
+    // Keep in sync with ColorCorrectionEffect::apply
+    color = sample_color(uvs);
+    vec3 rgb = color.rgb * exp2(exposure);
+    rgb += brightness;
+    rgb = (rgb - 0.5) * contrast + 0.5;
+    float luminance = dot(rgb, vec3(0.2126, 0.7152, 0.0722));
+    rgb = mix(vec3(luminance), rgb, saturation);
+    color.rgb = pow(max(rgb, vec3(0.0)), vec3(1.0 / gamma));
+}
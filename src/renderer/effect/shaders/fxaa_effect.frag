@@ -41,15 +41,9 @@ THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
 OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 */
 
-#ifndef FXAA_REDUCE_MIN
-    #define FXAA_REDUCE_MIN   (1.0/ 128.0)
-#endif
-#ifndef FXAA_REDUCE_MUL
-    #define FXAA_REDUCE_MUL   (1.0 / 8.0)
-#endif
-#ifndef FXAA_SPAN_MAX
-    #define FXAA_SPAN_MAX     8.0
-#endif
+uniform float reduceMin;
+uniform float reduceMul;
+uniform float spanMax;
 
 //optimized version for mobile, where dependent
 //texture reads can be a bottleneck
@@ -79,11 +73,11 @@ vec4 fxaa(vec2 fragCoord, vec2 resolution,
     dir.y =  ((lumaNW + lumaSW) - (lumaNE + lumaSE));
 
     float dirReduce = max((lumaNW + lumaNE + lumaSW + lumaSE) *
-                          (0.25 * FXAA_REDUCE_MUL), FXAA_REDUCE_MIN);
+                          (0.25 * reduceMul), reduceMin);
 
     float rcpDirMin = 1.0 / (min(abs(dir.x), abs(dir.y)) + dirReduce);
-    dir = min(vec2(FXAA_SPAN_MAX, FXAA_SPAN_MAX),
-              max(vec2(-FXAA_SPAN_MAX, -FXAA_SPAN_MAX),
+    dir = min(vec2(spanMax, spanMax),
+              max(vec2(-spanMax, -spanMax),
               dir * rcpDirMin)) * inverseVP;
 
     vec3 rgbA = 0.5 * (
@@ -0,0 +1,31 @@
+
+uniform sampler2D maskTexture;
+uniform vec4 color;
+uniform float thickness;
+
+in vec2 uvs;
+
+layout (location = 0) out vec4 outColor;
+
+void main()
+{
+    if (texture(maskTexture, uvs).a > 0.5) {
+        discard;
+    }
+    vec2 texelSize = 1.0 / vec2(textureSize(maskTexture, 0));
+    int radius = int(ceil(thickness));
+    float coverage = 0.0;
+    for (int x = -radius; x <= radius; x++) {
+        for (int y = -radius; y <= radius; y++) {
+            vec2 offset = vec2(float(x), float(y));
+            if (length(offset) <= thickness) {
+                coverage = max(coverage, texture(maskTexture, uvs + offset * texelSize).a);
+            }
+        }
+    }
+    if (coverage < 0.5) {
+        discard;
+    }
+    outColor.rgb = color_mapping(color.rgb);
+    outColor.a = color.a;
+}
@@ -0,0 +1,18 @@
+
+uniform vec2 center;
+uniform float strength;
+uniform int samples;
+
+in vec2 uvs;
+
+layout (location = 0) out vec4 color;
+
+void main()
+{
+    vec2 offset = (center - uvs) * strength / float(samples);
+    vec4 sum = vec4(0.0);
+    for (int i = 0; i < samples; i++) {
+        sum += sample_color(uvs + float(i) * offset);
+    }
+    color = sum / float(samples);
+}
@@ -0,0 +1,72 @@
+uniform mat4 projection;
+uniform mat4 projectionInverse;
+uniform float radius;
+uniform float bias;
+uniform float strength;
+uniform int samples;
+
+#ifdef USE_NORMAL_TEXTURE
+uniform sampler2D normalTexture;
+#endif
+
+in vec2 uvs;
+
+layout (location = 0) out vec4 outColor;
+
+vec3 view_position(vec2 uv) {
+    return world_pos_from_depth(projectionInverse, sample_depth(uv), uv);
+}
+
+float random(vec2 p) {
+    return fract(sin(dot(p, vec2(12.9898, 78.233))) * 43758.5453);
+}
+
+float ambient_occlusion(vec3 position) {
+#ifdef USE_NORMAL_TEXTURE
+    vec3 normal = decode_normal(texture(normalTexture, uvs).rgb);
+#else
+    vec3 normal = normalize(cross(dFdx(position), dFdy(position)));
+#endif
+
+    // Rotate the sampling kernel randomly for each pixel to trade banding for noise.
+    float angle = 6.2831853 * random(gl_FragCoord.xy);
+    vec3 r = vec3(cos(angle), sin(angle), 0.0);
+    if (abs(dot(r, normal)) > 0.99) {
+        r = vec3(0.0, 0.0, 1.0);
+    }
+    vec3 tangent = normalize(r - normal * dot(r, normal));
+    mat3 tbn = mat3(tangent, cross(normal, tangent), normal);
+
+    float occlusion = 0.0;
+    for (int i = 0; i < samples; i++) {
+        vec2 h = Hammersley(uint(i), uint(samples));
+        float phi = 6.2831853 * h.y;
+        float sin_theta = sqrt(h.x);
+        vec3 direction = vec3(cos(phi) * sin_theta, sin(phi) * sin_theta, sqrt(1.0 - h.x));
+        // Distribute the samples such that more samples are close to the position.
+        float t = (float(i) + 0.5) / float(samples);
+        vec3 sample_position = position + tbn * direction * radius * mix(0.1, 1.0, t * t);
+
+        vec4 offset = projection * vec4(sample_position, 1.0);
+        vec2 uv = offset.xy / offset.w * 0.5 + 0.5;
+        if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+            continue;
+        }
+        float depth = view_position(uv).z;
+        float range = smoothstep(0.0, 1.0, radius / max(abs(position.z - depth), 0.0001));
+        occlusion += (depth >= sample_position.z + bias ? 1.0 : 0.0) * range;
+    }
+    return 1.0 - strength * occlusion / float(max(samples, 1));
+}
+
+void main()
+{
+    float depth = sample_depth(uvs);
+    float factor = depth < 1.0 ? clamp(ambient_occlusion(view_position(uvs)), 0.0, 1.0) : 1.0;
+#ifdef USE_COLOR_TEXTURE
+    vec4 color = sample_color(uvs);
+    outColor = vec4(color.rgb * factor, color.a);
+#else
+    outColor = vec4(factor, factor, factor, 1.0);
+#endif
+}
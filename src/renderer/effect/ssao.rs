@@ -0,0 +1,124 @@
+use crate::renderer::*;
+use std::sync::Arc;
+
+///
+/// An effect that approximates ambient occlusion, ie. the darkening of creases, corners and other places where nearby geometry blocks the ambient light,
+/// using only the depth texture (screen space ambient occlusion).
+/// The position and normal of each pixel is reconstructed from the depth texture and a number of samples in the hemisphere around the normal
+/// are tested against the depth texture to estimate how occluded the pixel is.
+/// The normals reconstructed from the depth texture are inaccurate at the edges of objects, so for a better result,
+/// render the view space normals into a texture and set it as [SsaoEffect::normal_texture].
+///
+/// If a color texture is given, the colors are multiplied by the occlusion factor, which is an approximation since the occlusion should only affect the ambient lighting.
+/// If only a depth texture is given, the occlusion factor is written to all color channels, where one means no occlusion and zero means fully occluded,
+/// for example to render it into a texture which can be blurred or combined with the scene in a later effect.
+/// The effect should be applied before tone and color mapping, for example before a [ScreenEffect] or a [BloomEffect].
+///
+#[derive(Clone)]
+pub struct SsaoEffect {
+    /// The radius in world units of the hemisphere in which occluding geometry is searched for.
+    pub radius: f32,
+    /// A small distance in world units which a sample must be behind the depth texture to count as occluded, which avoids self-occlusion on flat surfaces.
+    pub bias: f32,
+    /// The strength of the occlusion, where zero means no occlusion and one means full occlusion.
+    pub strength: f32,
+    /// The number of samples used for each pixel. Higher gives a smoother result but is more expensive.
+    pub samples: u32,
+    /// An optional texture with the view space normals of the scene, encoded as colors, which is used instead of reconstructing the normals from the depth texture.
+    /// Render it using a [NormalMaterial] with [NormalMaterial::view_space] enabled into a texture with the same size as the depth texture.
+    pub normal_texture: Option<Arc<Texture2D>>,
+}
+
+impl Default for SsaoEffect {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            bias: 0.025,
+            strength: 1.0,
+            samples: 16,
+            normal_texture: None,
+        }
+    }
+}
+
+impl Effect for SsaoEffect {
+    fn fragment_shader_source(
+        &self,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        depth_texture: Option<DepthTexture>,
+    ) -> String {
+        format!(
+            "{}\n{}{}\n{}\n{}",
+            include_str!("../../core/shared.frag"),
+            if self.normal_texture.is_some() {
+                "#define USE_NORMAL_TEXTURE\n"
+            } else {
+                ""
+            },
+            color_texture
+                .map(|t| format!("#define USE_COLOR_TEXTURE\n{}", t.fragment_shader_source()))
+                .unwrap_or_default(),
+            depth_texture
+                .expect("Must supply a depth texture to apply a ssao effect")
+                .fragment_shader_source(),
+            include_str!("shaders/ssao_effect.frag")
+        )
+    }
+
+    fn id(&self, color_texture: Option<ColorTexture>, depth_texture: Option<DepthTexture>) -> u16 {
+        0b1u16 << 14
+            | 0b1u16 << 10
+            | 0b1u16 << 9
+            | if self.normal_texture.is_some() {
+                0b1u16 << 8
+            } else {
+                0
+            }
+            | color_texture.map(|t| t.id()).unwrap_or(0u16)
+            | depth_texture
+                .expect("Must supply a depth texture to apply a ssao effect")
+                .id()
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes {
+            uv: true,
+            ..FragmentAttributes::NONE
+        }
+    }
+
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        camera: &Camera,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        depth_texture: Option<DepthTexture>,
+    ) {
+        if let Some(color_texture) = color_texture {
+            color_texture.use_uniforms(program);
+        }
+        depth_texture
+            .expect("Must supply a depth texture to apply a ssao effect")
+            .use_uniforms(program);
+        if let Some(ref normal_texture) = self.normal_texture {
+            program.use_texture("normalTexture", normal_texture);
+        }
+        program.use_uniform("projection", camera.projection());
+        program.use_uniform("projectionInverse", camera.projection().invert().unwrap());
+        program.use_uniform("radius", self.radius);
+        program.use_uniform("bias", self.bias);
+        program.use_uniform("strength", self.strength);
+        program.use_uniform("samples", self.samples as i32);
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            cull: Cull::Back,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::renderer::*;
+
+///
+/// An effect that maps the HDR colors in a color texture to LDR colors that can be displayed on the screen,
+/// by first multiplying the colors with the [ToneMappingEffect::exposure] and then applying the [ToneMappingEffect::tone_mapping] operator.
+/// Finally, the color mapping defined in the [Camera] is applied, while the tone mapping defined in the [Camera] is ignored.
+///
+/// The color texture should contain linear HDR colors, ie. be rendered with a floating point format and with the tone and color mapping on the camera disabled,
+/// see [Camera::disable_tone_and_color_mapping].
+/// Effects that work on HDR colors, for example [SsaoEffect] and [BloomEffect] (with the tone mapping on the camera disabled), should be applied before this effect,
+/// while effects that work on display colors, for example [FxaaEffect], should be applied after this effect.
+///
+#[derive(Clone, Debug)]
+pub struct ToneMappingEffect {
+    /// The tone mapping operator.
+    pub tone_mapping: ToneMapping,
+    /// The colors are multiplied by this value before the tone mapping, so higher values give a brighter image.
+    pub exposure: f32,
+}
+
+impl Default for ToneMappingEffect {
+    fn default() -> Self {
+        Self {
+            tone_mapping: ToneMapping::default(),
+            exposure: 1.0,
+        }
+    }
+}
+
+impl Effect for ToneMappingEffect {
+    fn fragment_shader_source(
+        &self,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) -> String {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a tone mapping effect");
+        format!(
+            "{}\n{}\n{}\n
+            uniform float exposure;
+
+            in vec2 uvs;
+            layout (location = 0) out vec4 outColor;
+
+            void main()
+            {{
+                outColor = sample_color(uvs);
+                outColor.rgb = tone_mapping(exposure * outColor.rgb);
+                outColor.rgb = color_mapping(outColor.rgb);
+            }}
+            ",
+            color_texture.fragment_shader_source(),
+            ToneMapping::fragment_shader_source(),
+            ColorMapping::fragment_shader_source(),
+        )
+    }
+
+    fn id(&self, color_texture: Option<ColorTexture>, _depth_texture: Option<DepthTexture>) -> u16 {
+        let color_texture =
+            color_texture.expect("Must supply a color texture to apply a tone mapping effect");
+        0b1u16 << 14 | 0b1u16 << 10 | 0b1u16 << 8 | color_texture.id()
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes {
+            uv: true,
+            ..FragmentAttributes::NONE
+        }
+    }
+
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        camera: &Camera,
+        _lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        _depth_texture: Option<DepthTexture>,
+    ) {
+        color_texture
+            .expect("Must supply a color texture to apply a tone mapping effect")
+            .use_uniforms(program);
+        self.tone_mapping.use_uniforms(program);
+        camera.color_mapping.use_uniforms(program);
+        program.use_uniform("exposure", self.exposure);
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            cull: Cull::Back,
+            ..Default::default()
+        }
+    }
+}
@@ -57,6 +57,10 @@ mod mesh;
 #[doc(inline)]
 pub use mesh::*;
 
+mod skinned_mesh;
+#[doc(inline)]
+pub use skinned_mesh::*;
+
 mod instanced_mesh;
 #[doc(inline)]
 pub use instanced_mesh::*;
@@ -69,6 +73,10 @@ mod particles;
 #[doc(inline)]
 pub use particles::*;
 
+mod cpu_particle_system;
+#[doc(inline)]
+pub use cpu_particle_system::*;
+
 mod bounding_box;
 #[doc(inline)]
 pub use bounding_box::*;
@@ -77,6 +85,14 @@ mod line;
 #[doc(inline)]
 pub use line::*;
 
+mod line_segments;
+#[doc(inline)]
+pub use line_segments::*;
+
+mod grid;
+#[doc(inline)]
+pub use grid::*;
+
 mod rectangle;
 #[doc(inline)]
 pub use rectangle::*;
@@ -85,6 +101,10 @@ mod circle;
 #[doc(inline)]
 pub use circle::*;
 
+mod label;
+#[doc(inline)]
+pub use label::*;
+
 use crate::core::*;
 use crate::renderer::*;
 
@@ -0,0 +1,262 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// The shape of the area that the particles of a [CpuParticleSystem] are emitted from and the directions they are emitted in.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmitterShape {
+    /// The particles are emitted from the emitter position in random directions.
+    Point,
+    /// The particles are emitted from random positions inside a sphere with the given radius centered at the emitter position and move away from the center.
+    Sphere {
+        /// The radius of the sphere.
+        radius: f32,
+    },
+    /// The particles are emitted from the emitter position in random directions inside a cone.
+    Cone {
+        /// The direction of the axis of the cone.
+        direction: Vec3,
+        /// The angle between the axis and the side of the cone.
+        angle: Radians,
+    },
+}
+
+///
+/// Defines how the particles of a [CpuParticleSystem] are emitted.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParticleEmitter {
+    /// The position of the emitter in world coordinates.
+    pub position: Vec3,
+    /// The shape of the emitter.
+    pub shape: EmitterShape,
+    /// The number of particles emitted per second.
+    pub rate: f32,
+    /// The initial speed of the particles in world units per second.
+    pub speed: f32,
+    /// The time in seconds from a particle is emitted until it dies.
+    pub lifetime: f32,
+    /// The color of a particle when it is emitted.
+    pub start_color: Srgba,
+    /// The color of a particle when it dies. The color is linearly interpolated from the start color during the lifetime of the particle.
+    pub end_color: Srgba,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            position: vec3(0.0, 0.0, 0.0),
+            shape: EmitterShape::Point,
+            rate: 10.0,
+            speed: 1.0,
+            lifetime: 1.0,
+            start_color: Srgba::WHITE,
+            end_color: Srgba::WHITE,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Particle {
+    position: Vec3,
+    velocity: Vec3,
+    age: f32,
+    alive: bool,
+}
+
+///
+/// A particle system where the state of each particle (position, velocity, age and color) is simulated on the CPU, see [CpuParticleSystem::update],
+/// and the live particles are rendered as [Sprites], ie. quads that always face the camera.
+/// Particles are emitted continuously by the [CpuParticleSystem::emitter] and moves according to their velocity and [CpuParticleSystem::gravity] until they die,
+/// after which they are recycled when new particles are emitted.
+/// Compared to [ParticleSystem], which computes the position of each particle in the vertex shader, this is more flexible but more expensive for a large number of particles.
+///
+/// The sprites can be configured, for example their size, using the [Sprites] methods available through dereferencing,
+/// but note that the centers and colors are overwritten by each call to [CpuParticleSystem::update].
+///
+pub struct CpuParticleSystem {
+    sprites: Sprites,
+    particles: Vec<Particle>,
+    dead: Vec<usize>,
+    emission: f32,
+    random_state: u32,
+    /// Defines how the particles are emitted.
+    pub emitter: ParticleEmitter,
+    /// The acceleration applied to all particles in world units per second squared.
+    pub gravity: Vec3,
+}
+
+impl CpuParticleSystem {
+    ///
+    /// Creates a new particle system without any particles, particles are emitted from the given emitter when calling [CpuParticleSystem::update].
+    ///
+    pub fn new(context: &Context, emitter: ParticleEmitter) -> Self {
+        Self {
+            sprites: Sprites::new(context, &[], None),
+            particles: Vec::new(),
+            dead: Vec::new(),
+            emission: 0.0,
+            random_state: 0x9E37_79B9,
+            emitter,
+            gravity: vec3(0.0, -9.82, 0.0),
+        }
+    }
+
+    ///
+    /// Advances the simulation the given time in seconds, ie. moves and ages all live particles, emits new particles according to the emission rate
+    /// and uploads the positions and colors of the live particles to the GPU. Should be called each frame, for example with the elapsed time of the frame.
+    ///
+    pub fn update(&mut self, elapsed_time: f32) {
+        for (i, particle) in self.particles.iter_mut().enumerate() {
+            if particle.alive {
+                particle.age += elapsed_time;
+                if particle.age >= self.emitter.lifetime {
+                    particle.alive = false;
+                    self.dead.push(i);
+                } else {
+                    particle.velocity += self.gravity * elapsed_time;
+                    particle.position += particle.velocity * elapsed_time;
+                }
+            }
+        }
+
+        self.emission += self.emitter.rate.max(0.0) * elapsed_time;
+        let count = self.emission.floor();
+        self.emission -= count;
+        self.emit(count as u32);
+
+        let lifetime = self.emitter.lifetime.max(f32::EPSILON);
+        let (start_color, end_color) = (self.emitter.start_color, self.emitter.end_color);
+        let (centers, colors): (Vec<_>, Vec<_>) = self
+            .particles
+            .iter()
+            .filter(|p| p.alive)
+            .map(|p| {
+                let t = (p.age / lifetime).min(1.0);
+                let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+                (
+                    p.position,
+                    Srgba::new(
+                        lerp(start_color.r, end_color.r),
+                        lerp(start_color.g, end_color.g),
+                        lerp(start_color.b, end_color.b),
+                        lerp(start_color.a, end_color.a),
+                    ),
+                )
+            })
+            .unzip();
+        self.sprites.set_centers(&centers);
+        self.sprites.set_colors(Some(&colors));
+    }
+
+    ///
+    /// Emits the given number of particles immediately, for example to create a burst of particles.
+    /// The particles are visible after the next call to [CpuParticleSystem::update].
+    ///
+    pub fn emit(&mut self, count: u32) {
+        for _ in 0..count {
+            let particle = self.new_particle();
+            if let Some(i) = self.dead.pop() {
+                self.particles[i] = particle;
+            } else {
+                self.particles.push(particle);
+            }
+        }
+    }
+
+    ///
+    /// Returns the number of live particles.
+    ///
+    pub fn particle_count(&self) -> usize {
+        self.particles.len() - self.dead.len()
+    }
+
+    ///
+    /// Removes all particles.
+    ///
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.dead.clear();
+        self.emission = 0.0;
+        self.sprites.set_centers(&[]);
+        self.sprites.set_colors(None);
+    }
+
+    fn new_particle(&mut self) -> Particle {
+        let (offset, direction) = match self.emitter.shape {
+            EmitterShape::Point => (vec3(0.0, 0.0, 0.0), self.random_direction()),
+            EmitterShape::Sphere { radius } => {
+                let direction = self.random_direction();
+                (direction * radius * self.random().cbrt(), direction)
+            }
+            EmitterShape::Cone { direction, angle } => {
+                let axis = direction.normalize();
+                let cos_theta = 1.0 - self.random() * (1.0 - angle.0.cos());
+                let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+                let phi = 2.0 * std::f32::consts::PI * self.random();
+                let tangent = if axis.x.abs() < 0.9 {
+                    axis.cross(vec3(1.0, 0.0, 0.0))
+                } else {
+                    axis.cross(vec3(0.0, 1.0, 0.0))
+                }
+                .normalize();
+                let bitangent = axis.cross(tangent);
+                (
+                    vec3(0.0, 0.0, 0.0),
+                    axis * cos_theta + (tangent * phi.cos() + bitangent * phi.sin()) * sin_theta,
+                )
+            }
+        };
+        Particle {
+            position: self.emitter.position + offset,
+            velocity: direction * self.emitter.speed,
+            age: 0.0,
+            alive: true,
+        }
+    }
+
+    fn random_direction(&mut self) -> Vec3 {
+        let z = 2.0 * self.random() - 1.0;
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        let phi = 2.0 * std::f32::consts::PI * self.random();
+        vec3(r * phi.cos(), r * phi.sin(), z)
+    }
+
+    // Returns a pseudo-random number in the range [0, 1) using a xorshift generator.
+    fn random(&mut self) -> f32 {
+        let mut x = self.random_state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.random_state = x;
+        (x >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+use std::ops::Deref;
+impl Deref for CpuParticleSystem {
+    type Target = Sprites;
+    fn deref(&self) -> &Self::Target {
+        &self.sprites
+    }
+}
+
+impl std::ops::DerefMut for CpuParticleSystem {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.sprites
+    }
+}
+
+impl<'a> IntoIterator for &'a CpuParticleSystem {
+    type Item = &'a dyn Geometry;
+    type IntoIter = std::iter::Once<&'a dyn Geometry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self)
+    }
+}
+
+impl Geometry for CpuParticleSystem {
+    impl_geometry_body!(deref);
+}
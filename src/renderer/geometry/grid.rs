@@ -0,0 +1,82 @@
+use crate::renderer::*;
+
+///
+/// A reference grid in the xz-plane centered at the origin, which is drawn as [LineSegments] in a single draw call.
+/// The line along the x-axis is red, the line along the z-axis is blue and all other lines have the color given at construction.
+/// Use it together with a material that does not require normals, tangents or uv coordinates, for example a [ColorMaterial].
+///
+pub struct Grid {
+    line_segments: LineSegments,
+    size: f32,
+    cell_size: f32,
+}
+
+impl Grid {
+    ///
+    /// Constructs a new grid which extends `size` world units from the origin in both the x and z direction and where each cell is `cell_size` world units wide.
+    ///
+    pub fn new(context: &Context, size: f32, cell_size: f32, color: Srgba) -> Self {
+        if cell_size <= 0.0 {
+            panic!(
+                "the cell size of a grid must be positive, got {}",
+                cell_size
+            );
+        }
+        let count = (size / cell_size).floor() as i32;
+        let mut segments = Vec::new();
+        let mut colors = Vec::new();
+        for i in -count..=count {
+            let offset = i as f32 * cell_size;
+            segments.push((vec3(-size, 0.0, offset), vec3(size, 0.0, offset)));
+            colors.push(if i == 0 { Srgba::RED } else { color });
+            segments.push((vec3(offset, 0.0, -size), vec3(offset, 0.0, size)));
+            colors.push(if i == 0 { Srgba::BLUE } else { color });
+        }
+        Self {
+            line_segments: LineSegments::new(context, &segments, Some(&colors)),
+            size,
+            cell_size,
+        }
+    }
+
+    ///
+    /// Returns the distance from the origin to the edge of the grid in world units.
+    ///
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    ///
+    /// Returns the width of each cell in world units.
+    ///
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+}
+
+impl<'a> IntoIterator for &'a Grid {
+    type Item = &'a dyn Geometry;
+    type IntoIter = std::iter::Once<&'a dyn Geometry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self)
+    }
+}
+
+use std::ops::Deref;
+impl Deref for Grid {
+    type Target = LineSegments;
+    fn deref(&self) -> &Self::Target {
+        &self.line_segments
+    }
+}
+
+impl std::ops::DerefMut for Grid {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.line_segments
+    }
+}
+
+impl Geometry for Grid {
+    impl_geometry_body!(deref);
+}
@@ -73,6 +73,13 @@ impl InstancedMesh {
         self.instances.count()
     }
 
+    ///
+    /// Returns the instances that are rendered, for example to modify a copy and update the instances using [Self::set_instances].
+    ///
+    pub fn instances(&self) -> &Instances {
+        &self.instances
+    }
+
     ///
     /// Update the instances.
     ///
@@ -433,6 +440,15 @@ impl Instances {
     }
 }
 
+impl From<Vec<Mat4>> for Instances {
+    fn from(transformations: Vec<Mat4>) -> Self {
+        Self {
+            transformations,
+            ..Default::default()
+        }
+    }
+}
+
 impl From<PointCloud> for Instances {
     fn from(points: PointCloud) -> Self {
         Self {
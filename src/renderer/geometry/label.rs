@@ -0,0 +1,177 @@
+use crate::renderer::*;
+
+///
+/// A monospaced bitmap font, ie. a texture atlas where the glyphs of consecutive characters are laid out in a grid of equally sized cells,
+/// row by row starting from the top left corner. Used to construct a [Label].
+/// The atlas itself is not part of the font, instead render the label with a material that samples the atlas texture,
+/// for example a [ColorMaterial] with the atlas as [ColorMaterial::texture] and [ColorMaterial::alpha_cutout] enabled.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BitmapFont {
+    /// The number of glyphs in each row of the atlas.
+    pub columns: u32,
+    /// The number of rows of glyphs in the atlas.
+    pub rows: u32,
+    /// The character of the glyph in the top left cell of the atlas.
+    pub first_character: char,
+    /// The width divided by the height of each glyph.
+    pub aspect: f32,
+}
+
+impl BitmapFont {
+    ///
+    /// Returns the uv coordinates of the top left and bottom right corner of the glyph for the given character
+    /// or `None` if the atlas does not contain the character.
+    ///
+    fn uv_coordinates(&self, character: char) -> Option<(Vec2, Vec2)> {
+        let index = (character as u32).checked_sub(self.first_character as u32)?;
+        if index >= self.columns * self.rows {
+            return None;
+        }
+        let column = (index % self.columns) as f32;
+        let row = (index / self.columns) as f32;
+        let size = vec2(1.0 / self.columns as f32, 1.0 / self.rows as f32);
+        Some((
+            vec2(column * size.x, row * size.y),
+            vec2((column + 1.0) * size.x, (row + 1.0) * size.y),
+        ))
+    }
+}
+
+///
+/// The horizontal alignment of each line of text in a [Label] relative to the origin of the label.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TextAlignment {
+    /// The lines start at the origin.
+    #[default]
+    Left,
+    /// The lines are centered around the origin.
+    Center,
+    /// The lines end at the origin.
+    Right,
+}
+
+///
+/// Defines the size and layout of the text in a [Label].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextLayout {
+    /// The height of each glyph in world units.
+    pub size: f32,
+    /// The distance between the baselines of two consecutive lines relative to [TextLayout::size].
+    pub line_height: f32,
+    /// The horizontal alignment of each line.
+    pub alignment: TextAlignment,
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self {
+            size: 1.0,
+            line_height: 1.2,
+            alignment: TextAlignment::Left,
+        }
+    }
+}
+
+///
+/// A text string in world space, where each character is a quad in the xy-plane textured with a glyph from a [BitmapFont].
+/// The text starts at the origin of the label and the first line is above the x-axis while the following lines, separated by `\n`, continue downwards.
+/// Use [Mesh::set_transformation], available through dereferencing, to position the label in the scene.
+///
+pub struct Label {
+    mesh: Mesh,
+    text: String,
+}
+
+impl Label {
+    ///
+    /// Constructs a new label with the given text using the glyphs of the given font and the given layout.
+    /// Characters not contained in the font are rendered as spaces.
+    ///
+    pub fn new(context: &Context, font: &BitmapFont, text: &str, layout: TextLayout) -> Self {
+        let glyph_width = layout.size * font.aspect;
+        let mut positions = Vec::new();
+        let mut uvs = Vec::new();
+        let mut indices = Vec::new();
+        for (line_index, line) in text.lines().enumerate() {
+            let line_width = line.chars().count() as f32 * glyph_width;
+            let x0 = match layout.alignment {
+                TextAlignment::Left => 0.0,
+                TextAlignment::Center => -0.5 * line_width,
+                TextAlignment::Right => -line_width,
+            };
+            let y0 = -(line_index as f32) * layout.size * layout.line_height;
+            for (i, character) in line.chars().enumerate() {
+                if let Some((top_left, bottom_right)) = font.uv_coordinates(character) {
+                    let x = x0 + i as f32 * glyph_width;
+                    let start = positions.len() as u32;
+                    positions.extend([
+                        vec3(x, y0, 0.0),
+                        vec3(x + glyph_width, y0, 0.0),
+                        vec3(x + glyph_width, y0 + layout.size, 0.0),
+                        vec3(x, y0 + layout.size, 0.0),
+                    ]);
+                    uvs.extend([
+                        vec2(top_left.x, bottom_right.y),
+                        bottom_right,
+                        vec2(bottom_right.x, top_left.y),
+                        top_left,
+                    ]);
+                    indices.extend([start, start + 1, start + 2, start, start + 2, start + 3]);
+                }
+            }
+        }
+        let normals = vec![vec3(0.0, 0.0, 1.0); positions.len()];
+        let cpu_mesh = CpuMesh {
+            positions: Positions::F32(positions),
+            indices: Indices::U32(indices),
+            normals: Some(normals),
+            uvs: Some(uvs),
+            ..Default::default()
+        };
+        Self {
+            mesh: Mesh::new(context, &cpu_mesh),
+            text: text.to_string(),
+        }
+    }
+
+    ///
+    /// Returns the text of this label.
+    ///
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl<'a> IntoIterator for &'a Label {
+    type Item = &'a dyn Geometry;
+    type IntoIter = std::iter::Once<&'a dyn Geometry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self)
+    }
+}
+
+use std::ops::Deref;
+impl Deref for Label {
+    type Target = Mesh;
+    fn deref(&self) -> &Self::Target {
+        &self.mesh
+    }
+}
+
+impl std::ops::DerefMut for Label {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.mesh
+    }
+}
+
+impl Geometry for Label {
+    impl_geometry_body!(deref);
+
+    fn animate(&mut self, time: f32) {
+        self.mesh.animate(time)
+    }
+}
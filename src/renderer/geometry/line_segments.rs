@@ -0,0 +1,192 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// A set of line segments, each defined by two end points and a color, which is drawn as lines with a width of one pixel.
+/// Useful for debugging, for example to visualize normals, velocities or bounding boxes (see [LineSegments::from_aabb]).
+/// Use it together with a material that does not require normals, tangents or uv coordinates, for example a [ColorMaterial].
+///
+pub struct LineSegments {
+    context: Context,
+    positions: VertexBuffer,
+    colors: Option<VertexBuffer>,
+    aabb: AxisAlignedBoundingBox,
+    transformation: Mat4,
+}
+
+impl LineSegments {
+    ///
+    /// Creates a new set of line segments from the given pairs of end points.
+    /// If colors are given, the number of colors must be equal to the number of line segments and each line segment is drawn with its color
+    /// multiplied by the color of the material, otherwise only the color of the material is used.
+    ///
+    pub fn new(context: &Context, segments: &[(Vec3, Vec3)], colors: Option<&[Srgba]>) -> Self {
+        if let Some(colors) = colors {
+            if colors.len() != segments.len() {
+                panic!(
+                    "the number of colors ({}) must be equal to the number of line segments ({})",
+                    colors.len(),
+                    segments.len()
+                );
+            }
+        }
+        let positions = segments
+            .iter()
+            .flat_map(|(p0, p1)| [*p0, *p1])
+            .collect::<Vec<_>>();
+        Self {
+            context: context.clone(),
+            aabb: AxisAlignedBoundingBox::new_with_positions(&positions),
+            positions: VertexBuffer::new_with_data(context, &positions),
+            colors: colors.map(|colors| {
+                VertexBuffer::new_with_data(
+                    context,
+                    &colors
+                        .iter()
+                        .flat_map(|c| {
+                            let c = c.to_linear_srgb();
+                            [c, c]
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            }),
+            transformation: Mat4::identity(),
+        }
+    }
+
+    ///
+    /// Creates the twelve line segments that outline the given [AxisAlignedBoundingBox], all with the given color.
+    ///
+    pub fn from_aabb(context: &Context, aabb: AxisAlignedBoundingBox, color: Srgba) -> Self {
+        let min = aabb.min();
+        let max = aabb.max();
+        let corner = |x: bool, y: bool, z: bool| {
+            vec3(
+                if x { max.x } else { min.x },
+                if y { max.y } else { min.y },
+                if z { max.z } else { min.z },
+            )
+        };
+        let mut segments = Vec::with_capacity(12);
+        for a in [false, true] {
+            for b in [false, true] {
+                segments.push((corner(false, a, b), corner(true, a, b)));
+                segments.push((corner(a, false, b), corner(a, true, b)));
+                segments.push((corner(a, b, false), corner(a, b, true)));
+            }
+        }
+        Self::new(context, &segments, Some(&[color; 12]))
+    }
+
+    ///
+    /// Returns the number of line segments.
+    ///
+    pub fn segment_count(&self) -> u32 {
+        self.positions.vertex_count() / 2
+    }
+
+    ///
+    /// Returns the local to world transformation applied to all line segments.
+    ///
+    pub fn transformation(&self) -> Mat4 {
+        self.transformation
+    }
+
+    ///
+    /// Set the local to world transformation applied to all line segments.
+    ///
+    pub fn set_transformation(&mut self, transformation: Mat4) {
+        self.transformation = transformation;
+    }
+}
+
+impl<'a> IntoIterator for &'a LineSegments {
+    type Item = &'a dyn Geometry;
+    type IntoIter = std::iter::Once<&'a dyn Geometry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self)
+    }
+}
+
+impl Geometry for LineSegments {
+    fn draw(
+        &self,
+        camera: &Camera,
+        program: &Program,
+        render_states: RenderStates,
+        attributes: FragmentAttributes,
+    ) {
+        if attributes.normal || attributes.tangents || attributes.uv {
+            panic!("line segments only support materials that do not require normals, tangents or uv coordinates");
+        }
+        program.use_uniform("viewProjection", camera.projection() * camera.view());
+        program.use_uniform("modelMatrix", self.transformation);
+        program.use_vertex_attribute("position", &self.positions);
+        if attributes.color {
+            if let Some(colors) = &self.colors {
+                program.use_vertex_attribute("color", colors);
+            }
+        }
+        program.draw_lines(
+            render_states,
+            camera.viewport(),
+            self.positions.vertex_count(),
+        );
+    }
+
+    fn vertex_shader_source(&self, required_attributes: FragmentAttributes) -> String {
+        format!(
+            "{}{}{}",
+            if required_attributes.color && self.colors.is_some() {
+                "#define USE_VERTEX_COLORS\n"
+            } else {
+                ""
+            },
+            include_str!("../../core/shared.frag"),
+            include_str!("shaders/mesh.vert"),
+        )
+    }
+
+    fn id(&self, required_attributes: FragmentAttributes) -> u16 {
+        if required_attributes.color && self.colors.is_some() {
+            0b1u16 << 15 | 0b111u16
+        } else {
+            0b1u16 << 15 | 0b110u16
+        }
+    }
+
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) {
+        render_with_material(&self.context, camera, &self, material, lights);
+    }
+
+    fn render_with_effect(
+        &self,
+        material: &dyn Effect,
+        camera: &Camera,
+        lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        depth_texture: Option<DepthTexture>,
+    ) {
+        render_with_effect(
+            &self.context,
+            camera,
+            self,
+            material,
+            lights,
+            color_texture,
+            depth_texture,
+        )
+    }
+
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        let mut aabb = self.aabb;
+        aabb.transform(&self.transformation);
+        aabb
+    }
+}
@@ -3,6 +3,30 @@ use crate::renderer::*;
 
 use super::BaseMesh;
 
+///
+/// The maximum number of morph targets of a [Mesh], see [Mesh::set_morph_targets].
+///
+pub const MAX_MORPH_TARGETS: usize = 4;
+
+///
+/// A morph target, also called a blend shape, of a [Mesh], ie. an offset of each vertex position and optionally normal
+/// which is added to the mesh scaled by the weight of the morph target, see [Mesh::set_morph_targets] and [Mesh::set_morph_weights].
+///
+#[derive(Clone, Debug, Default)]
+pub struct MorphTarget {
+    /// The offset of the position of each vertex.
+    pub positions: Vec<Vec3>,
+    /// The offset of the normal of each vertex.
+    pub normals: Option<Vec<Vec3>>,
+}
+
+struct MorphTargets {
+    positions: Vec<VertexBuffer>,
+    normals: Option<Vec<VertexBuffer>>,
+    weights: Vec<f32>,
+    aabb: AxisAlignedBoundingBox,
+}
+
 ///
 /// A triangle mesh [Geometry].
 ///
@@ -13,6 +37,9 @@ pub struct Mesh {
     transformation: Mat4,
     current_transformation: Mat4,
     animation: Option<Box<dyn Fn(f32) -> Mat4 + Send + Sync>>,
+    vertex_shader: Option<(String, u16)>,
+    vertex_shader_uniforms: Box<dyn Fn(&Program) + Send + Sync>,
+    morph_targets: Option<MorphTargets>,
 }
 
 impl Mesh {
@@ -29,7 +56,49 @@ impl Mesh {
             transformation: Mat4::identity(),
             current_transformation: Mat4::identity(),
             animation: None,
+            vertex_shader: None,
+            vertex_shader_uniforms: Box::new(|_| {}),
+            morph_targets: None,
+        }
+    }
+
+    ///
+    /// Creates a new triangle mesh from the given [CpuMesh] which is rendered using the given vertex shader source instead of the default vertex shader.
+    /// The source is prefixed with the same defines as the default vertex shader (`USE_NORMALS`, `USE_TANGENTS`, `USE_UVS` and `USE_VERTEX_COLORS`)
+    /// depending on the attributes required by the material, so the shader must handle the same attributes, uniforms and outputs as the default vertex shader,
+    /// ie. the `position`, `normal`, `tangent`, `uv_coordinates` and `color` attributes, the `viewProjection`, `modelMatrix` and `normalMatrix` uniforms
+    /// and the `pos`, `nor`, `tang`, `bitang`, `uvs` and `col` outputs. Any additional uniforms can be set using [Self::set_vertex_shader_uniforms].
+    ///
+    /// The given id must be unique for each different vertex shader source and smaller than `0b1u16 << 11`.
+    /// Also, it is shared with the ids of any [Geometry] implemented outside of this crate (see [Geometry::id]), so make sure they do not overlap.
+    ///
+    pub fn new_with_vertex_shader(
+        context: &Context,
+        cpu_mesh: &CpuMesh,
+        vertex_shader_source: impl Into<String>,
+        id: u16,
+    ) -> Self {
+        if id >= 0b1u16 << 11 {
+            panic!(
+                "The vertex shader id {} must be smaller than {}",
+                id,
+                0b1u16 << 11
+            );
         }
+        let mut mesh = Self::new(context, cpu_mesh);
+        mesh.vertex_shader = Some((vertex_shader_source.into(), id));
+        mesh
+    }
+
+    ///
+    /// Specifies a function which is called each time this mesh is drawn to set any additional uniforms used in a custom vertex shader,
+    /// see [Self::new_with_vertex_shader].
+    ///
+    pub fn set_vertex_shader_uniforms(
+        &mut self,
+        uniforms: impl Fn(&Program) + Send + Sync + 'static,
+    ) {
+        self.vertex_shader_uniforms = Box::new(uniforms);
     }
 
     pub(in crate::renderer) fn set_transformation_2d(&mut self, transformation: Mat3) {
@@ -114,6 +183,114 @@ impl Mesh {
             self.base_mesh.normals = Some(VertexBuffer::new_with_data(&self.context, normals));
         }
     }
+
+    ///
+    /// Sets the morph targets of this mesh, which are blended in the vertex shader according to the weights set by [Self::set_morph_weights].
+    /// All weights are initially zero. The normal offsets are only used if all morph targets contain normal offsets.
+    /// Morph targets are not supported in combination with a custom vertex shader, see [Self::new_with_vertex_shader].
+    /// Use an empty slice to remove the morph targets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [MAX_MORPH_TARGETS] morph targets are given
+    /// or if the number of position or normal offsets of a morph target does not match the number of vertices in the mesh.
+    pub fn set_morph_targets(&mut self, morph_targets: &[MorphTarget]) {
+        if morph_targets.len() > MAX_MORPH_TARGETS {
+            panic!(
+                "A mesh supports at most {} morph targets, but {} was given",
+                MAX_MORPH_TARGETS,
+                morph_targets.len()
+            );
+        }
+        for morph_target in morph_targets {
+            if morph_target.positions.len() as u32 != self.vertex_count()
+                || morph_target
+                    .normals
+                    .as_ref()
+                    .map(|normals| normals.len() as u32 != self.vertex_count())
+                    .unwrap_or(false)
+            {
+                panic!(
+                    "The number of offsets in a morph target must match the number of vertices {} in the mesh",
+                    self.vertex_count()
+                );
+            }
+        }
+        if morph_targets.is_empty() {
+            self.morph_targets = None;
+            return;
+        }
+
+        // Expand the bounding box so it contains the mesh for all weights between zero and one
+        let (mut min, mut max) = (self.aabb.min(), self.aabb.max());
+        for morph_target in morph_targets {
+            let offsets = AxisAlignedBoundingBox::new_with_positions(&morph_target.positions);
+            min += offsets.min().zip(vec3(0.0, 0.0, 0.0), f32::min);
+            max += offsets.max().zip(vec3(0.0, 0.0, 0.0), f32::max);
+        }
+        self.morph_targets = Some(MorphTargets {
+            positions: morph_targets
+                .iter()
+                .map(|t| VertexBuffer::new_with_data(&self.context, &t.positions))
+                .collect(),
+            normals: morph_targets
+                .iter()
+                .map(|t| {
+                    t.normals
+                        .as_ref()
+                        .map(|normals| VertexBuffer::new_with_data(&self.context, normals))
+                })
+                .collect(),
+            weights: vec![0.0; morph_targets.len()],
+            aabb: AxisAlignedBoundingBox::new_with_positions(&[min, max]),
+        });
+    }
+
+    ///
+    /// Sets the weight of each of the morph targets set by [Self::set_morph_targets].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of weights does not match the number of morph targets.
+    pub fn set_morph_weights(&mut self, weights: &[f32]) {
+        let count = self
+            .morph_targets
+            .as_ref()
+            .map(|m| m.positions.len())
+            .unwrap_or(0);
+        if weights.len() != count {
+            panic!(
+                "The number of weights {} does not match the number of morph targets {}",
+                weights.len(),
+                count
+            );
+        }
+        if let Some(morph_targets) = &mut self.morph_targets {
+            morph_targets.weights = weights.to_vec();
+        }
+    }
+
+    ///
+    /// Returns the weight of each of the morph targets set by [Self::set_morph_targets].
+    ///
+    pub fn morph_weights(&self) -> &[f32] {
+        self.morph_targets
+            .as_ref()
+            .map(|m| m.weights.as_slice())
+            .unwrap_or(&[])
+    }
+
+    fn morph_targets(&self, required_attributes: FragmentAttributes) -> Option<(usize, bool)> {
+        if self.vertex_shader.is_some() {
+            return None;
+        }
+        self.morph_targets.as_ref().map(|m| {
+            (
+                m.positions.len(),
+                required_attributes.normal && m.normals.is_some(),
+            )
+        })
+    }
 }
 
 impl<'a> IntoIterator for &'a Mesh {
@@ -127,7 +304,11 @@ impl<'a> IntoIterator for &'a Mesh {
 
 impl Geometry for Mesh {
     fn aabb(&self) -> AxisAlignedBoundingBox {
-        let mut aabb = self.aabb;
+        let mut aabb = self
+            .morph_targets
+            .as_ref()
+            .map(|m| m.aabb)
+            .unwrap_or(self.aabb);
         aabb.transform(&self.current_transformation);
         aabb
     }
@@ -156,6 +337,21 @@ impl Geometry for Mesh {
 
         program.use_uniform("viewProjection", camera.projection() * camera.view());
         program.use_uniform("modelMatrix", self.current_transformation);
+        (self.vertex_shader_uniforms)(program);
+        if let Some((count, use_normals)) = self.morph_targets(attributes) {
+            let morph_targets = self.morph_targets.as_ref().unwrap();
+            let mut weights = [0.0; MAX_MORPH_TARGETS];
+            weights[..count].copy_from_slice(&morph_targets.weights);
+            program.use_uniform("morphWeights", Vec4::from(weights));
+            for (i, positions) in morph_targets.positions.iter().enumerate() {
+                program.use_vertex_attribute(&format!("morph_position{}", i), positions);
+            }
+            if use_normals {
+                for (i, normals) in morph_targets.normals.as_ref().unwrap().iter().enumerate() {
+                    program.use_vertex_attribute(&format!("morph_normal{}", i), normals);
+                }
+            }
+        }
 
         self.base_mesh
             .draw(program, render_states, camera, attributes);
@@ -163,7 +359,17 @@ impl Geometry for Mesh {
 
     fn vertex_shader_source(&self, required_attributes: FragmentAttributes) -> String {
         format!(
-            "{}{}{}{}{}{}",
+            "{}{}{}{}{}{}{}",
+            match self.morph_targets(required_attributes) {
+                Some((count, true)) => format!(
+                    "#define USE_MORPH_TARGETS\n#define USE_MORPH_NORMALS\n#define MORPH_TARGETS {}\n",
+                    count
+                ),
+                Some((count, false)) => {
+                    format!("#define USE_MORPH_TARGETS\n#define MORPH_TARGETS {}\n", count)
+                }
+                None => String::new(),
+            },
             if required_attributes.normal {
                 "#define USE_NORMALS\n"
             } else {
@@ -185,12 +391,19 @@ impl Geometry for Mesh {
                 ""
             },
             include_str!("../../core/shared.frag"),
-            include_str!("shaders/mesh.vert"),
+            self.vertex_shader
+                .as_ref()
+                .map(|(source, _)| source.as_str())
+                .unwrap_or(include_str!("shaders/mesh.vert")),
         )
     }
 
     fn id(&self, required_attributes: FragmentAttributes) -> u16 {
-        let mut id = 0b1u16 << 15 | 0b1u16 << 4;
+        let mut id = if let Some((_, id)) = &self.vertex_shader {
+            id << 4
+        } else {
+            0b1u16 << 15 | 0b1u16 << 4
+        };
         if required_attributes.normal {
             id |= 0b1u16;
         }
@@ -203,6 +416,12 @@ impl Geometry for Mesh {
         if required_attributes.color && self.base_mesh.colors.is_some() {
             id |= 0b1u16 << 3;
         }
+        if let Some((count, use_normals)) = self.morph_targets(required_attributes) {
+            id |= 0b1u16 << 8 | ((count as u16 - 1) << 9);
+            if use_normals {
+                id |= 0b1u16 << 11;
+            }
+        }
         id
     }
 
@@ -3,6 +3,26 @@ uniform mat4 viewProjection;
 uniform mat4 modelMatrix;
 in vec3 position;
 
+#ifdef USE_MORPH_TARGETS
+uniform vec4 morphWeights;
+in vec3 morph_position0;
+#if MORPH_TARGETS > 1
+in vec3 morph_position1;
+#endif
+#if MORPH_TARGETS > 2
+in vec3 morph_position2;
+#endif
+#if MORPH_TARGETS > 3
+in vec3 morph_position3;
+#endif
+#endif
+
+#ifdef USE_SKINNING
+uniform mat4 boneMatrices[MAX_BONES];
+in vec4 joint_indices;
+in vec4 joint_weights;
+#endif
+
 #ifdef PARTICLES
 in vec3 start_position;
 in vec3 start_velocity;
@@ -27,6 +47,19 @@ uniform mat4 normalMatrix;
 in vec3 normal;
 out vec3 nor;
 
+#ifdef USE_MORPH_NORMALS
+in vec3 morph_normal0;
+#if MORPH_TARGETS > 1
+in vec3 morph_normal1;
+#endif
+#if MORPH_TARGETS > 2
+in vec3 morph_normal2;
+#endif
+#if MORPH_TARGETS > 3
+in vec3 morph_normal3;
+#endif
+#endif
+
 #ifdef USE_TANGENTS 
 in vec4 tangent;
 out vec3 tang;
@@ -68,7 +101,29 @@ void main()
     local2World *= transform;
 #endif
 
-    vec4 worldPosition = local2World * vec4(position, 1.);
+    vec3 localPosition = position;
+#ifdef USE_MORPH_TARGETS
+    localPosition += morphWeights.x * morph_position0;
+#if MORPH_TARGETS > 1
+    localPosition += morphWeights.y * morph_position1;
+#endif
+#if MORPH_TARGETS > 2
+    localPosition += morphWeights.z * morph_position2;
+#endif
+#if MORPH_TARGETS > 3
+    localPosition += morphWeights.w * morph_position3;
+#endif
+#endif
+
+#ifdef USE_SKINNING
+    mat4 skinMatrix = joint_weights.x * boneMatrices[int(joint_indices.x)]
+        + joint_weights.y * boneMatrices[int(joint_indices.y)]
+        + joint_weights.z * boneMatrices[int(joint_indices.z)]
+        + joint_weights.w * boneMatrices[int(joint_indices.w)];
+    localPosition = (skinMatrix * vec4(localPosition, 1.0)).xyz;
+#endif
+
+    vec4 worldPosition = local2World * vec4(localPosition, 1.);
     worldPosition /= worldPosition.w;
 #ifdef PARTICLES
     worldPosition.xyz += start_position + start_velocity * time + 0.5 * acceleration * time * time;
@@ -87,10 +142,30 @@ void main()
 #else
     mat3 normalMat = mat3(normalMatrix);
 #endif
-    nor = normalize(normalMat * normal);
+    vec3 localNormal = normal;
+#ifdef USE_MORPH_NORMALS
+    localNormal += morphWeights.x * morph_normal0;
+#if MORPH_TARGETS > 1
+    localNormal += morphWeights.y * morph_normal1;
+#endif
+#if MORPH_TARGETS > 2
+    localNormal += morphWeights.z * morph_normal2;
+#endif
+#if MORPH_TARGETS > 3
+    localNormal += morphWeights.w * morph_normal3;
+#endif
+#endif
+#ifdef USE_SKINNING
+    localNormal = mat3(skinMatrix) * localNormal;
+#endif
+    nor = normalize(normalMat * localNormal);
 
 #ifdef USE_TANGENTS 
+#ifdef USE_SKINNING
+    tang = normalize(normalMat * mat3(skinMatrix) * tangent.xyz);
+#else
     tang = normalize(normalMat * tangent.xyz);
+#endif
     bitang = normalize(cross(nor, tang) * tangent.w);
 #endif
 
@@ -2,12 +2,18 @@ uniform mat4 viewProjection;
 uniform vec3 eye;
 uniform mat4 transformation;
 uniform vec3 direction;
+uniform vec2 size;
+uniform vec2 anchor;
 
 in vec3 center;
 
 in vec3 position;
 in vec2 uv_coordinate;
 
+#ifdef USE_INSTANCE_COLORS
+in vec4 instance_color;
+#endif
+
 out vec2 uvs;
 out vec4 col;
 out vec3 pos;
@@ -16,6 +22,9 @@ void main()
 {
     uvs = uv_coordinate;
     col = vec4(1.0);
+#ifdef USE_INSTANCE_COLORS
+    col = instance_color;
+#endif
 
     vec3 z = normalize(eye - center);
     vec3 y = direction;
@@ -35,7 +44,9 @@ void main()
                 y, 0.0,
                 z, 0.0,
                 center.x, center.y, center.z, 1.0);
-    vec4 world_pos = instanced_transform * transformation * vec4(position, 1.);
+    float scale = size.x + size.y * (viewProjection * vec4(center, 1.0)).w;
+    vec3 local_pos = vec3((position.xy - anchor) * scale, position.z);
+    vec4 world_pos = instanced_transform * transformation * vec4(local_pos, 1.);
     pos = world_pos.xyz / world_pos.w;
     gl_Position = viewProjection * world_pos;
 }
@@ -0,0 +1,232 @@
+use crate::core::*;
+use crate::renderer::*;
+
+use super::BaseMesh;
+
+///
+/// The maximum number of bones, ie. the maximum length of the bone matrix palette, of a [SkinnedMesh].
+///
+pub const MAX_BONES: usize = 32;
+
+///
+/// A triangle mesh [Geometry] which is deformed by a skeleton using linear blend skinning, for example to animate a character.
+/// Each vertex is attached to up to four bones, given by joint indices and weights, and the position and normal of the vertex is
+/// the weighted sum of the vertex transformed by the matrices of these bones, see [SkinnedMesh::set_bone_matrices].
+/// The bone matrices are uploaded as a uniform array, so at most [MAX_BONES] bones are supported.
+///
+/// Note that the bounding box, which is for example used for frustum culling, is computed from the mesh without any deformation.
+///
+pub struct SkinnedMesh {
+    base_mesh: BaseMesh,
+    context: Context,
+    joint_indices: VertexBuffer,
+    joint_weights: VertexBuffer,
+    bone_matrices: Vec<Mat4>,
+    aabb: AxisAlignedBoundingBox,
+    transformation: Mat4,
+}
+
+impl SkinnedMesh {
+    ///
+    /// Creates a new skinned mesh from the given [CpuMesh] and the indices of the (up to four) bones each vertex is attached to together with the weight of each bone.
+    /// The weights of each vertex should sum to one. All bone matrices are initially the identity matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of joint indices or weights does not match the number of vertices
+    /// or if a joint index is not smaller than [MAX_BONES].
+    ///
+    pub fn new(
+        context: &Context,
+        cpu_mesh: &CpuMesh,
+        joint_indices: &[[u16; 4]],
+        joint_weights: &[Vec4],
+    ) -> Self {
+        let vertex_count = cpu_mesh.vertex_count();
+        if joint_indices.len() != vertex_count || joint_weights.len() != vertex_count {
+            panic!(
+                "The number of joint indices {} and joint weights {} must match the number of vertices {} in the mesh",
+                joint_indices.len(),
+                joint_weights.len(),
+                vertex_count
+            );
+        }
+        if let Some(index) = joint_indices
+            .iter()
+            .flatten()
+            .find(|i| **i as usize >= MAX_BONES)
+        {
+            panic!(
+                "The joint index {} must be smaller than the maximum number of bones {}",
+                index, MAX_BONES
+            );
+        }
+        Self {
+            context: context.clone(),
+            base_mesh: BaseMesh::new(context, cpu_mesh),
+            joint_indices: VertexBuffer::new_with_data(
+                context,
+                &joint_indices
+                    .iter()
+                    .map(|j| vec4(j[0] as f32, j[1] as f32, j[2] as f32, j[3] as f32))
+                    .collect::<Vec<_>>(),
+            ),
+            joint_weights: VertexBuffer::new_with_data(context, joint_weights),
+            bone_matrices: vec![Mat4::identity(); MAX_BONES],
+            aabb: cpu_mesh.compute_aabb(),
+            transformation: Mat4::identity(),
+        }
+    }
+
+    ///
+    /// Sets the bone matrix palette, ie. the matrix of each bone which transforms a vertex from the bind pose to the animated pose in the local space of the mesh,
+    /// typically the global transformation of the joint multiplied by its inverse bind matrix.
+    /// Bones without a given matrix use the identity matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [MAX_BONES] matrices are given.
+    ///
+    pub fn set_bone_matrices(&mut self, bone_matrices: &[Mat4]) {
+        if bone_matrices.len() > MAX_BONES {
+            panic!(
+                "A skinned mesh supports at most {} bones, but {} bone matrices was given",
+                MAX_BONES,
+                bone_matrices.len()
+            );
+        }
+        self.bone_matrices[..bone_matrices.len()].copy_from_slice(bone_matrices);
+        self.bone_matrices[bone_matrices.len()..].fill(Mat4::identity());
+    }
+
+    ///
+    /// Returns the local to world transformation applied to this mesh after the skinning.
+    ///
+    pub fn transformation(&self) -> Mat4 {
+        self.transformation
+    }
+
+    ///
+    /// Set the local to world transformation applied to this mesh after the skinning.
+    ///
+    pub fn set_transformation(&mut self, transformation: Mat4) {
+        self.transformation = transformation;
+    }
+}
+
+impl<'a> IntoIterator for &'a SkinnedMesh {
+    type Item = &'a dyn Geometry;
+    type IntoIter = std::iter::Once<&'a dyn Geometry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self)
+    }
+}
+
+impl Geometry for SkinnedMesh {
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        let mut aabb = self.aabb;
+        aabb.transform(&self.transformation);
+        aabb
+    }
+
+    fn draw(
+        &self,
+        camera: &Camera,
+        program: &Program,
+        render_states: RenderStates,
+        attributes: FragmentAttributes,
+    ) {
+        if attributes.normal {
+            if let Some(inverse) = self.transformation.invert() {
+                program.use_uniform_if_required("normalMatrix", inverse.transpose());
+            } else {
+                // determinant is float zero
+                return;
+            }
+        }
+
+        program.use_uniform("viewProjection", camera.projection() * camera.view());
+        program.use_uniform("modelMatrix", self.transformation);
+        program.use_uniform_array("boneMatrices", &self.bone_matrices);
+        program.use_vertex_attribute("joint_indices", &self.joint_indices);
+        program.use_vertex_attribute("joint_weights", &self.joint_weights);
+
+        self.base_mesh
+            .draw(program, render_states, camera, attributes);
+    }
+
+    fn vertex_shader_source(&self, required_attributes: FragmentAttributes) -> String {
+        format!(
+            "#define USE_SKINNING\n#define MAX_BONES {}\n{}{}{}{}{}{}",
+            MAX_BONES,
+            if required_attributes.normal {
+                "#define USE_NORMALS\n"
+            } else {
+                ""
+            },
+            if required_attributes.tangents {
+                "#define USE_TANGENTS\n"
+            } else {
+                ""
+            },
+            if required_attributes.uv {
+                "#define USE_UVS\n"
+            } else {
+                ""
+            },
+            if required_attributes.color && self.base_mesh.colors.is_some() {
+                "#define USE_VERTEX_COLORS\n"
+            } else {
+                ""
+            },
+            include_str!("../../core/shared.frag"),
+            include_str!("shaders/mesh.vert"),
+        )
+    }
+
+    fn id(&self, required_attributes: FragmentAttributes) -> u16 {
+        let mut id = 0b1u16 << 15 | 0b1u16 << 12;
+        if required_attributes.normal {
+            id |= 0b1u16;
+        }
+        if required_attributes.tangents {
+            id |= 0b1u16 << 1;
+        }
+        if required_attributes.uv {
+            id |= 0b1u16 << 2;
+        }
+        if required_attributes.color && self.base_mesh.colors.is_some() {
+            id |= 0b1u16 << 3;
+        }
+        id
+    }
+
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) {
+        render_with_material(&self.context, camera, &self, material, lights);
+    }
+
+    fn render_with_effect(
+        &self,
+        material: &dyn Effect,
+        camera: &Camera,
+        lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        depth_texture: Option<DepthTexture>,
+    ) {
+        render_with_effect(
+            &self.context,
+            camera,
+            self,
+            material,
+            lights,
+            color_texture,
+            depth_texture,
+        )
+    }
+}
@@ -7,14 +7,71 @@ use crate::renderer::*;
 /// The sprites will always orient themselves towards the camera, but if a direction is specified, the sprite normals will also always be orthogonal to that direction.
 /// For example, if the up direction is specified, the sprites will rotate around the up direction trying to face the camera.
 /// Sprites are also known as billboards in the case where no direction is specified.
+/// The size of the sprites can be given in world units or in pixels (see [SpriteSize]) and the point of the sprite which is placed at the center can be changed using [Sprites::set_anchor],
+/// for example to place the bottom of map pins at their location.
+/// Use a material with a transparent texture, for example a [ColorMaterial], to render cutout icons.
 ///
 pub struct Sprites {
     context: Context,
     position_buffer: VertexBuffer,
     uv_buffer: VertexBuffer,
     center_buffer: InstanceBuffer,
+    color_buffer: Option<InstanceBuffer>,
     transformation: Mat4,
     direction: Option<Vec3>,
+    size: SpriteSize,
+    anchor: SpriteAnchor,
+}
+
+///
+/// The size of the [Sprites], see [Sprites::set_size].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpriteSize {
+    /// The height of each sprite in world units, which means that the sprites become smaller on the screen when moving away from the camera.
+    World(f32),
+    /// The height of each sprite in pixels, which means that the sprites keep the same size on the screen independent of the distance to the camera.
+    Pixels(f32),
+}
+
+impl Default for SpriteSize {
+    fn default() -> Self {
+        Self::World(2.0)
+    }
+}
+
+///
+/// The point of each sprite which is placed at the center of the sprite, see [Sprites::set_anchor].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum SpriteAnchor {
+    #[default]
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl SpriteAnchor {
+    fn offset(&self) -> Vec2 {
+        match self {
+            Self::Center => vec2(0.0, 0.0),
+            Self::Top => vec2(0.0, 1.0),
+            Self::Bottom => vec2(0.0, -1.0),
+            Self::Left => vec2(-1.0, 0.0),
+            Self::Right => vec2(1.0, 0.0),
+            Self::TopLeft => vec2(-1.0, 1.0),
+            Self::TopRight => vec2(1.0, 1.0),
+            Self::BottomLeft => vec2(-1.0, -1.0),
+            Self::BottomRight => vec2(1.0, -1.0),
+        }
+    }
 }
 
 impl Sprites {
@@ -50,8 +107,11 @@ impl Sprites {
             position_buffer,
             uv_buffer,
             center_buffer: InstanceBuffer::new_with_data(context, centers),
+            color_buffer: None,
             transformation: Mat4::identity(),
             direction,
+            size: SpriteSize::default(),
+            anchor: SpriteAnchor::default(),
         }
     }
 
@@ -76,6 +136,36 @@ impl Sprites {
         self.direction = direction;
     }
 
+    ///
+    /// Set the size of the sprites, either in world units or in pixels. The default is a height of two world units.
+    /// The transformation (see [Sprites::set_transformation]) is applied after the size, so a scale in the transformation also scales the size.
+    ///
+    pub fn set_size(&mut self, size: SpriteSize) {
+        self.size = size;
+    }
+
+    ///
+    /// Returns the size of the sprites.
+    ///
+    pub fn size(&self) -> SpriteSize {
+        self.size
+    }
+
+    ///
+    /// Set the point of each sprite which is placed at the center of the sprite, for example [SpriteAnchor::Bottom] to make the sprites stand on their centers.
+    /// The default is [SpriteAnchor::Center].
+    ///
+    pub fn set_anchor(&mut self, anchor: SpriteAnchor) {
+        self.anchor = anchor;
+    }
+
+    ///
+    /// Returns the point of each sprite which is placed at the center of the sprite.
+    ///
+    pub fn anchor(&self) -> SpriteAnchor {
+        self.anchor
+    }
+
     ///
     /// Set the centers of the sprites. The centers also determines the number of sprites.
     ///
@@ -83,14 +173,56 @@ impl Sprites {
         self.center_buffer.fill(centers);
     }
 
-    fn draw(&self, program: &Program, render_states: RenderStates, camera: &Camera) {
+    ///
+    /// Set a color for each sprite which is multiplied with the color of the material, if the material uses colors.
+    /// The number of colors must be equal to the number of sprites, ie. the number of centers, use `None` to remove the colors.
+    ///
+    pub fn set_colors(&mut self, colors: Option<&[Srgba]>) {
+        let colors = colors.map(|colors| {
+            colors
+                .iter()
+                .map(|c| c.to_linear_srgb())
+                .collect::<Vec<_>>()
+        });
+        match (&mut self.color_buffer, colors) {
+            (Some(buffer), Some(colors)) => buffer.fill(&colors),
+            (buffer, colors) => {
+                *buffer = colors.map(|colors| InstanceBuffer::new_with_data(&self.context, &colors))
+            }
+        }
+    }
+
+    fn draw(
+        &self,
+        program: &Program,
+        render_states: RenderStates,
+        camera: &Camera,
+        attributes: FragmentAttributes,
+    ) {
         program.use_uniform("eye", camera.position());
         program.use_uniform("viewProjection", camera.projection() * camera.view());
         program.use_uniform("transformation", self.transformation);
         program.use_vertex_attribute("position", &self.position_buffer);
-        program.use_vertex_attribute("uv_coordinate", &self.uv_buffer);
+        if attributes.uv {
+            program.use_vertex_attribute("uv_coordinate", &self.uv_buffer);
+        }
         program.use_instance_attribute("center", &self.center_buffer);
+        if attributes.color {
+            if let Some(color_buffer) = &self.color_buffer {
+                program.use_instance_attribute("instance_color", color_buffer);
+            }
+        }
         program.use_uniform("direction", self.direction.unwrap_or(vec3(0.0, 0.0, 0.0)));
+        // The half size is computed as size.x + size.y * w, where w is the clip space w coordinate of the center.
+        let size = match self.size {
+            SpriteSize::World(height) => vec2(0.5 * height, 0.0),
+            SpriteSize::Pixels(height) => vec2(
+                0.0,
+                height / (camera.projection()[1][1] * camera.viewport().height.max(1) as f32),
+            ),
+        };
+        program.use_uniform("size", size);
+        program.use_uniform("anchor", self.anchor.offset());
         program.draw_arrays_instanced(
             render_states,
             camera.viewport(),
@@ -117,21 +249,29 @@ impl Geometry for Sprites {
         render_states: RenderStates,
         attributes: FragmentAttributes,
     ) {
-        if !attributes.uv {
-            todo!()
-        }
         if attributes.normal || attributes.tangents {
             todo!()
         }
-        self.draw(program, render_states, camera);
+        self.draw(program, render_states, camera, attributes);
     }
 
-    fn vertex_shader_source(&self, _required_attributes: FragmentAttributes) -> String {
-        include_str!("shaders/sprites.vert").to_owned()
+    fn vertex_shader_source(&self, required_attributes: FragmentAttributes) -> String {
+        if required_attributes.color && self.color_buffer.is_some() {
+            format!(
+                "#define USE_INSTANCE_COLORS\n{}",
+                include_str!("shaders/sprites.vert")
+            )
+        } else {
+            include_str!("shaders/sprites.vert").to_owned()
+        }
     }
 
-    fn id(&self, _required_attributes: FragmentAttributes) -> u16 {
-        0b1u16 << 15 | 0b100u16
+    fn id(&self, required_attributes: FragmentAttributes) -> u16 {
+        if required_attributes.color && self.color_buffer.is_some() {
+            0b1u16 << 15 | 0b1000u16
+        } else {
+            0b1u16 << 15 | 0b100u16
+        }
     }
 
     fn render_with_material(
@@ -57,6 +57,20 @@ pub struct Attenuation {
     pub quadratic: f32,
 }
 
+impl Attenuation {
+    ///
+    /// Returns an attenuation where the light intensity is reduced to approximately 1% at the given distance from the light,
+    /// using the commonly used linear and quadratic factors `4.5 / range` and `75 / range²`.
+    ///
+    pub fn from_range(range: f32) -> Self {
+        Self {
+            constant: 1.0,
+            linear: 4.5 / range,
+            quadratic: 75.0 / (range * range),
+        }
+    }
+}
+
 impl Default for Attenuation {
     fn default() -> Self {
         Self {
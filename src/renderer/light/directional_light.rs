@@ -16,6 +16,9 @@ pub struct DirectionalLight {
     pub color: Srgba,
     /// The direction the light shines.
     pub direction: Vec3,
+    /// The radius in shadow map texels of the percentage-closer filtering (PCF) used to soften the edges of the shadow.
+    /// A value of zero gives hard shadow edges. The default is one texel.
+    pub shadow_filter_radius: f32,
 }
 
 impl DirectionalLight {
@@ -33,6 +36,7 @@ impl DirectionalLight {
             intensity,
             color,
             direction: *direction,
+            shadow_filter_radius: 1.0,
         }
     }
 
@@ -70,7 +74,7 @@ impl DirectionalLight {
         let position = target - aabb.max().distance(aabb.min()) * self.direction;
         let z_far = aabb.distance_max(&position);
         let z_near = aabb.distance(&position);
-        let frustum_height = aabb.max().distance(aabb.min()); // TODO: more tight fit
+        let frustum_height = aabb.max().distance(aabb.min());
         let shadow_camera = Camera::new_orthographic(
             viewport,
             position,
@@ -80,6 +84,38 @@ impl DirectionalLight {
             z_near,
             z_far,
         );
+        self.render_shadow_map(texture_size, shadow_camera, geometries);
+    }
+
+    ///
+    /// Generate a shadow map like [DirectionalLight::generate_shadow_map], except that the shadow map only covers the part of the scene
+    /// that is visible from the given view camera (see [directional_light_camera]), which gives a much higher shadow resolution
+    /// when only a small part of a large scene is visible. The shadow map needs to be generated again whenever the view camera changes.
+    ///
+    pub fn generate_shadow_map_for_view(
+        &mut self,
+        texture_size: u32,
+        view_camera: &Camera,
+        geometries: impl IntoIterator<Item = impl Geometry> + Clone,
+    ) {
+        let mut aabb = AxisAlignedBoundingBox::EMPTY;
+        for geometry in geometries.clone() {
+            aabb.expand_with_aabb(&geometry.aabb());
+        }
+        if aabb.is_empty() {
+            return;
+        }
+        let shadow_camera =
+            directional_light_camera(self.direction, &aabb, view_camera, texture_size);
+        self.render_shadow_map(texture_size, shadow_camera, geometries);
+    }
+
+    fn render_shadow_map(
+        &mut self,
+        texture_size: u32,
+        shadow_camera: Camera,
+        geometries: impl IntoIterator<Item = impl Geometry>,
+    ) {
         let mut shadow_texture = DepthTexture2D::new::<f32>(
             &self.context,
             texture_size,
@@ -87,32 +123,10 @@ impl DirectionalLight {
             Wrapping::ClampToEdge,
             Wrapping::ClampToEdge,
         );
-        let depth_material = DepthMaterial {
-            render_states: RenderStates {
-                write_mask: WriteMask::DEPTH,
-                ..Default::default()
-            },
-            ..Default::default()
-        };
         shadow_texture
             .as_depth_target()
             .clear(ClearState::default())
-            .write::<RendererError>(|| {
-                for geometry in geometries
-                    .into_iter()
-                    .filter(|g| shadow_camera.in_frustum(&g.aabb()))
-                {
-                    render_with_material(
-                        &self.context,
-                        &shadow_camera,
-                        &geometry,
-                        &depth_material,
-                        &[],
-                    );
-                }
-                Ok(())
-            })
-            .unwrap();
+            .render_depth(&shadow_camera, geometries);
         self.shadow_texture = Some(shadow_texture);
         self.shadow_matrix = shadow_matrix(&shadow_camera);
     }
@@ -132,6 +146,7 @@ impl Light for DirectionalLight {
                 "
                     uniform sampler2D shadowMap{};
                     uniform mat4 shadowMVP{};
+                    uniform float shadowFilterRadius{};
 
                     uniform vec3 color{};
                     uniform vec3 direction{};
@@ -139,10 +154,10 @@ impl Light for DirectionalLight {
                     vec3 calculate_lighting{}(vec3 surface_color, vec3 position, vec3 normal, vec3 view_direction, float metallic, float roughness, float occlusion)
                     {{
                         return calculate_light(color{}, -direction{}, surface_color, view_direction, normal, metallic, roughness)
-                            * calculate_shadow(-direction{}, normal, shadowMap{}, shadowMVP{}, position);
+                            * calculate_shadow_filtered(-direction{}, normal, shadowMap{}, shadowMVP{}, position, shadowFilterRadius{});
                     }}
 
-                ", i, i, i, i, i, i, i, i, i, i)
+                ", i, i, i, i, i, i, i, i, i, i, i, i)
         } else {
             format!(
                 "
@@ -161,6 +176,10 @@ impl Light for DirectionalLight {
         if let Some(ref tex) = self.shadow_texture {
             program.use_depth_texture(&format!("shadowMap{}", i), tex);
             program.use_uniform(&format!("shadowMVP{}", i), self.shadow_matrix);
+            program.use_uniform(
+                &format!("shadowFilterRadius{}", i),
+                self.shadow_filter_radius.max(0.0),
+            );
         }
         program.use_uniform(
             &format!("color{}", i),
@@ -177,3 +196,166 @@ impl Light for DirectionalLight {
         }
     }
 }
+
+///
+/// Returns an orthographic camera looking in the given light direction which tightly bounds the part of the scene, given by the scene bounding box,
+/// that is visible from the given view camera, ie. the intersection between the view frustum and the scene bounding box when seen from the light.
+/// In the light direction, the camera bounds the entire scene bounding box so that geometry outside the view frustum can still cast shadows into it.
+/// The returned camera has a square viewport with the given texture size, for example the size of a shadow map.
+///
+/// To reduce shimmering when the view camera moves, the size of the camera volume is rounded up so that the size of a texel is a power of two
+/// and the camera is moved in increments of whole texels.
+///
+pub fn directional_light_camera(
+    light_direction: Vec3,
+    scene_aabb: &AxisAlignedBoundingBox,
+    view_frustum: &Camera,
+    texture_size: u32,
+) -> Camera {
+    let direction = light_direction.normalize();
+    let right = direction.cross(compute_up_direction(direction)).normalize();
+    let up = right.cross(direction);
+    let to_light_space = |p: Vec3| vec3(p.dot(right), p.dot(up), p.dot(direction));
+    let bounds = |points: &[Vec3]| {
+        points.iter().fold(
+            (
+                vec3(f32::MAX, f32::MAX, f32::MAX),
+                vec3(f32::MIN, f32::MIN, f32::MIN),
+            ),
+            |(min, max), p| {
+                let p = to_light_space(*p);
+                (
+                    vec3(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                    vec3(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+                )
+            },
+        )
+    };
+
+    let (mut min, mut max) = bounds(&view_frustum.frustum_corners());
+    if !scene_aabb.is_empty() && !scene_aabb.is_infinite() {
+        let (aabb_min, aabb_max) = (scene_aabb.min(), scene_aabb.max());
+        let corners = [
+            aabb_min,
+            vec3(aabb_max.x, aabb_min.y, aabb_min.z),
+            vec3(aabb_min.x, aabb_max.y, aabb_min.z),
+            vec3(aabb_max.x, aabb_max.y, aabb_min.z),
+            vec3(aabb_min.x, aabb_min.y, aabb_max.z),
+            vec3(aabb_max.x, aabb_min.y, aabb_max.z),
+            vec3(aabb_min.x, aabb_max.y, aabb_max.z),
+            aabb_max,
+        ];
+        let (scene_min, scene_max) = bounds(&corners);
+        if scene_min.x <= max.x
+            && scene_max.x >= min.x
+            && scene_min.y <= max.y
+            && scene_max.y >= min.y
+        {
+            min.x = min.x.max(scene_min.x);
+            min.y = min.y.max(scene_min.y);
+            max.x = max.x.min(scene_max.x);
+            max.y = max.y.min(scene_max.y);
+        }
+        min.z = scene_min.z;
+        max.z = scene_max.z;
+    }
+
+    let texture_size = texture_size.max(1);
+    let size = (max.x - min.x).max(max.y - min.y).max(f32::EPSILON);
+    let texel_size = 2.0f32.powf((size / texture_size as f32).log2().ceil());
+    let size = texel_size * texture_size as f32;
+    let center_x = (0.5 * (min.x + max.x) / texel_size).round() * texel_size;
+    let center_y = (0.5 * (min.y + max.y) / texel_size).round() * texel_size;
+    let margin = 0.01 * (max.z - min.z) + 0.01;
+
+    let position = center_x * right + center_y * up + (min.z - margin) * direction;
+    Camera::new_orthographic(
+        Viewport::new_at_origo(texture_size, texture_size),
+        position,
+        position + direction,
+        up,
+        size,
+        0.0,
+        max.z - min.z + 2.0 * margin,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corners(aabb: &AxisAlignedBoundingBox) -> Vec<Vec3> {
+        let (min, max) = (aabb.min(), aabb.max());
+        (0..8)
+            .map(|i| {
+                vec3(
+                    if i & 1 == 0 { min.x } else { max.x },
+                    if i & 2 == 0 { min.y } else { max.y },
+                    if i & 4 == 0 { min.z } else { max.z },
+                )
+            })
+            .collect()
+    }
+
+    fn to_clip_space(camera: &Camera, p: Vec3) -> Vec3 {
+        let p = camera.projection() * camera.view() * p.extend(1.0);
+        p.truncate() / p.w
+    }
+
+    #[test]
+    fn light_camera_contains_scene() {
+        let scene_aabb = AxisAlignedBoundingBox::new_with_positions(&[
+            vec3(-3.0, -1.0, -2.0),
+            vec3(4.0, 2.0, 5.0),
+        ]);
+        let view_camera = Camera::new_perspective(
+            Viewport::new_at_origo(200, 100),
+            vec3(10.0, 8.0, 20.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            degrees(60.0),
+            0.1,
+            100.0,
+        );
+        for light_direction in [
+            vec3(0.0, -1.0, 0.0),
+            vec3(1.0, -1.0, 0.5),
+            vec3(-0.3, -0.2, -1.0),
+        ] {
+            let camera = directional_light_camera(light_direction, &scene_aabb, &view_camera, 1024);
+            for corner in corners(&scene_aabb) {
+                let p = to_clip_space(&camera, corner);
+                assert!(
+                    p.x.abs() <= 1.0 && p.y.abs() <= 1.0 && p.z.abs() <= 1.0,
+                    "corner {:?} is outside the light camera for direction {:?}: {:?}",
+                    corner,
+                    light_direction,
+                    p
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn light_camera_depth_contains_scene_outside_view() {
+        let scene_aabb = AxisAlignedBoundingBox::new_with_positions(&[
+            vec3(-50.0, -50.0, -50.0),
+            vec3(50.0, 50.0, 50.0),
+        ]);
+        let view_camera = Camera::new_perspective(
+            Viewport::new_at_origo(100, 100),
+            vec3(0.0, 0.0, 5.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            degrees(45.0),
+            0.1,
+            10.0,
+        );
+        let camera = directional_light_camera(vec3(0.0, -1.0, 0.0), &scene_aabb, &view_camera, 512);
+        // Geometry above the view frustum must still be inside the depth range to cast shadows into it
+        for y in [-50.0, 50.0] {
+            let p = to_clip_space(&camera, vec3(0.0, y, 0.0));
+            assert!(p.z.abs() <= 1.0);
+        }
+    }
+}
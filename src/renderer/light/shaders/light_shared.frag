@@ -162,10 +162,12 @@ float is_visible(vec3 lightDirection, vec3 normal, sampler2D shadowMap, vec4 sha
     return shadow_cast_distance > true_distance ? 1.0 : 0.0;
 }
 
-float calculate_shadow(vec3 lightDirection, vec3 normal, sampler2D shadowMap, mat4 shadowMVP, vec3 position)
+float calculate_shadow_filtered(vec3 lightDirection, vec3 normal, sampler2D shadowMap, mat4 shadowMVP, vec3 position, float radius)
 {
     vec4 shadow_coord = shadowMVP * vec4(position, 1.);
-    float visibility = 0.0;
+    // The offsets are applied before the perspective division, so they are scaled to be the same size in texels for perspective projections
+    vec2 texel_size = radius * shadow_coord.w / vec2(textureSize(shadowMap, 0));
+    float visibility = is_visible(lightDirection, normal, shadowMap, shadow_coord, vec2(0.0));
     vec2 poissonDisk[4] = vec2[](
                                  vec2( -0.94201624, -0.39906216 ),
                                  vec2( 0.94558609, -0.76890725 ),
@@ -174,9 +176,9 @@ float calculate_shadow(vec3 lightDirection, vec3 normal, sampler2D shadowMap, ma
                                  );
     for (int i=0;i<4;i++)
     {
-        visibility += is_visible(lightDirection, normal, shadowMap, shadow_coord, poissonDisk[i] * 0.001f);
+        visibility += is_visible(lightDirection, normal, shadowMap, shadow_coord, poissonDisk[i] * texel_size);
     }
-    return visibility * 0.25;
+    return visibility * 0.2;
 }
 
 vec3 ImportanceSampleGGX(vec2 Xi, vec3 N, float roughness)
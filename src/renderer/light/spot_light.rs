@@ -18,10 +18,16 @@ pub struct SpotLight {
     pub position: Vec3,
     /// The direction the light shines.
     pub direction: Vec3,
-    /// The cutoff angle for the light.
+    /// The cutoff angle for the light, ie. the outer angle of the cone where the light intensity reaches zero.
     pub cutoff: Radians,
+    /// The inner angle of the cone where the light intensity starts to fall off towards the [SpotLight::cutoff] angle.
+    /// If `None`, the fall off starts at 75% of the cutoff angle.
+    pub inner_cutoff: Option<Radians>,
     /// The [Attenuation] of the light.
     pub attenuation: Attenuation,
+    /// The radius in shadow map texels of the percentage-closer filtering (PCF) used to soften the edges of the shadow.
+    /// A value of zero gives hard shadow edges. The default is one texel.
+    pub shadow_filter_radius: f32,
 }
 
 impl SpotLight {
@@ -43,8 +49,10 @@ impl SpotLight {
             position: *position,
             direction: *direction,
             cutoff: cutoff.into(),
+            inner_cutoff: None,
             attenuation,
             shadow_matrix: Mat4::identity(),
+            shadow_filter_radius: 1.0,
         }
     }
 
@@ -101,32 +109,10 @@ impl SpotLight {
             Wrapping::ClampToEdge,
             Wrapping::ClampToEdge,
         );
-        let depth_material = DepthMaterial {
-            render_states: RenderStates {
-                write_mask: WriteMask::DEPTH,
-                ..Default::default()
-            },
-            ..Default::default()
-        };
         shadow_texture
             .as_depth_target()
             .clear(ClearState::default())
-            .write::<RendererError>(|| {
-                for geometry in geometries
-                    .into_iter()
-                    .filter(|g| shadow_camera.in_frustum(&g.aabb()))
-                {
-                    render_with_material(
-                        &self.context,
-                        &shadow_camera,
-                        &geometry,
-                        &depth_material,
-                        &[],
-                    );
-                }
-                Ok(())
-            })
-            .unwrap();
+            .render_depth(&shadow_camera, geometries);
         self.shadow_texture = Some(shadow_texture);
     }
 
@@ -145,11 +131,13 @@ impl Light for SpotLight {
                 "
                     uniform sampler2D shadowMap{};
                     uniform mat4 shadowMVP{};
+                    uniform float shadowFilterRadius{};
 
                     uniform vec3 color{};
                     uniform vec3 attenuation{};
                     uniform vec3 position{};
                     uniform float cutoff{};
+                    uniform float innerCutoff{};
                     uniform vec3 direction{};
                     vec3 calculate_lighting{}(vec3 surface_color, vec3 position, vec3 normal, vec3 view_direction, float metallic, float roughness, float occlusion)
                     {{
@@ -159,18 +147,19 @@ impl Light for SpotLight {
 
                         float angle = acos(dot(-light_direction, normalize(direction{})));
                         float cutoff = cutoff{};
+                        float inner_cutoff = innerCutoff{};
 
                         vec3 result = vec3(0.0);
                         if (angle < cutoff) {{
                             vec3 light_color = attenuate(color{}, attenuation{}, distance);
                             result = calculate_light(light_color, light_direction, surface_color, view_direction, normal,
-                                metallic, roughness) * (1.0 - smoothstep(0.75 * cutoff, cutoff, angle));
-                            result *= calculate_shadow(light_direction, normal, shadowMap{}, shadowMVP{}, position);
+                                metallic, roughness) * (1.0 - smoothstep(inner_cutoff, cutoff, angle));
+                            result *= calculate_shadow_filtered(light_direction, normal, shadowMap{}, shadowMVP{}, position, shadowFilterRadius{});
                         }}
                         return result;
                     }}
 
-                ", i, i, i, i, i, i, i, i, i, i, i, i, i, i, i)
+                ", i, i, i, i, i, i, i, i, i, i, i, i, i, i, i, i, i, i, i)
         } else {
             format!(
                 "
@@ -178,6 +167,7 @@ impl Light for SpotLight {
                     uniform vec3 attenuation{};
                     uniform vec3 position{};
                     uniform float cutoff{};
+                    uniform float innerCutoff{};
                     uniform vec3 direction{};
                     vec3 calculate_lighting{}(vec3 surface_color, vec3 position, vec3 normal, vec3 view_direction, float metallic, float roughness, float occlusion)
                     {{
@@ -187,23 +177,28 @@ impl Light for SpotLight {
 
                         float angle = acos(dot(-light_direction, normalize(direction{})));
                         float cutoff = cutoff{};
+                        float inner_cutoff = innerCutoff{};
 
                         vec3 result = vec3(0.0);
                         if (angle < cutoff) {{
                             vec3 light_color = attenuate(color{}, attenuation{}, distance);
                             result = calculate_light(light_color, light_direction, surface_color, view_direction, normal,
-                                metallic, roughness) * (1.0 - smoothstep(0.75 * cutoff, cutoff, angle));
+                                metallic, roughness) * (1.0 - smoothstep(inner_cutoff, cutoff, angle));
                         }}
                         return result;
                     }}
 
-                ", i, i, i, i, i, i, i, i, i, i, i)
+                ", i, i, i, i, i, i, i, i, i, i, i, i, i)
         }
     }
     fn use_uniforms(&self, program: &Program, i: u32) {
         if let Some(ref tex) = self.shadow_texture {
             program.use_depth_texture(&format!("shadowMap{}", i), tex);
             program.use_uniform(&format!("shadowMVP{}", i), self.shadow_matrix);
+            program.use_uniform(
+                &format!("shadowFilterRadius{}", i),
+                self.shadow_filter_radius.max(0.0),
+            );
         }
         program.use_uniform(
             &format!("color{}", i),
@@ -220,6 +215,12 @@ impl Light for SpotLight {
         program.use_uniform(&format!("position{}", i), self.position);
         program.use_uniform(&format!("direction{}", i), self.direction.normalize());
         program.use_uniform(&format!("cutoff{}", i), self.cutoff.0);
+        program.use_uniform(
+            &format!("innerCutoff{}", i),
+            self.inner_cutoff
+                .map(|a| a.0.min(self.cutoff.0))
+                .unwrap_or(0.75 * self.cutoff.0),
+        );
     }
 
     fn id(&self) -> u8 {
@@ -25,6 +25,9 @@ macro_rules! impl_material_body {
         fn id(&self) -> u16 {
             self.$inner().id()
         }
+        fn discards_fragments(&self) -> bool {
+            self.$inner().discards_fragments()
+        }
     };
 }
 
@@ -74,6 +77,14 @@ mod isosurface_material;
 #[doc(inline)]
 pub use isosurface_material::*;
 
+mod volume_material;
+#[doc(inline)]
+pub use volume_material::*;
+
+mod wireframe_material;
+#[doc(inline)]
+pub use wireframe_material::*;
+
 use std::{ops::Deref, sync::Arc};
 
 ///
@@ -97,6 +108,14 @@ impl Texture2DRef {
         }
     }
 
+    ///
+    /// Creates a new [Texture2DRef] with an identity transformation from a [CpuTexture] containing sRGB encoded colors,
+    /// which are decoded to linear sRGB when sampled, see [Texture2D::new_srgb].
+    ///
+    pub fn from_cpu_texture_srgb(context: &Context, cpu_texture: &CpuTexture) -> Self {
+        Self::from_texture(Texture2D::new_srgb(context, cpu_texture))
+    }
+
     /// Creates a new [Texture2DRef] with an identity transformation from a [Texture2D].
     pub fn from_texture(texture: Texture2D) -> Self {
         Self {
@@ -218,6 +237,16 @@ pub trait Material {
     /// Returns the type of material.
     ///
     fn material_type(&self) -> MaterialType;
+
+    ///
+    /// Returns whether the fragment shader might discard fragments, for example when using an alpha cutout.
+    /// Materials that discard fragments cannot be replaced by a [DepthMaterial] when only rendering depth, like in [RenderTarget::render_with_depth_pre_pass],
+    /// since the depth of the discarded fragments would then be written.
+    /// The default implementation returns `false`.
+    ///
+    fn discards_fragments(&self) -> bool {
+        false
+    }
 }
 
 ///
@@ -283,6 +312,9 @@ impl<T: Material> Material for std::sync::RwLock<T> {
     fn id(&self) -> u16 {
         self.read().unwrap().id()
     }
+    fn discards_fragments(&self) -> bool {
+        self.read().unwrap().discards_fragments()
+    }
 }
 
 fn is_transparent(cpu_material: &CpuMaterial) -> bool {
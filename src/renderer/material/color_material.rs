@@ -16,6 +16,11 @@ pub struct ColorMaterial {
     pub render_states: RenderStates,
     /// Whether this material should be treated as a transparent material (An object needs to be rendered differently depending on whether it is transparent or opaque).
     pub is_transparent: bool,
+    /// A threshold on the alpha value of the color as a workaround for transparency.
+    /// If the alpha value of a pixel touched by an object with this material is less than the threshold, then that object is not contributing to the color of that pixel.
+    /// On the other hand, if the alpha value is more than the threshold, then it is contributing fully to that pixel and thereby blocks out everything behind.
+    /// This can be used for cutout transparency, for example icons or foliage, without the need for blending and sorting, so the material can still be opaque.
+    pub alpha_cutout: Option<f32>,
 }
 
 impl ColorMaterial {
@@ -51,6 +56,7 @@ impl ColorMaterial {
             texture,
             is_transparent: false,
             render_states: RenderStates::default(),
+            alpha_cutout: cpu_material.alpha_cutout,
         }
     }
 
@@ -77,6 +83,7 @@ impl ColorMaterial {
                 blend: Blend::TRANSPARENCY,
                 ..Default::default()
             },
+            alpha_cutout: cpu_material.alpha_cutout,
         }
     }
 
@@ -87,6 +94,7 @@ impl ColorMaterial {
             texture: physical_material.albedo_texture.clone(),
             render_states: physical_material.render_states,
             is_transparent: physical_material.is_transparent,
+            alpha_cutout: None,
         }
     }
 }
@@ -99,11 +107,15 @@ impl FromCpuMaterial for ColorMaterial {
 
 impl Material for ColorMaterial {
     fn id(&self) -> u16 {
-        if self.texture.is_some() {
+        let mut id = if self.texture.is_some() {
             0b1u16 << 15
         } else {
             0b1u16 << 15 | 0b1u16
+        };
+        if self.alpha_cutout.is_some() {
+            id |= 0b1u16 << 8;
         }
+        id
     }
 
     fn fragment_shader_source(&self, _lights: &[&dyn Light]) -> String {
@@ -111,6 +123,9 @@ impl Material for ColorMaterial {
         if self.texture.is_some() {
             shader.push_str("#define USE_TEXTURE\nin vec2 uvs;\n");
         }
+        if self.alpha_cutout.is_some() {
+            shader.push_str("#define ALPHACUT\n");
+        }
         shader.push_str(include_str!("../../core/shared.frag"));
         shader.push_str(ColorMapping::fragment_shader_source());
         shader.push_str(include_str!("shaders/color_material.frag"));
@@ -132,6 +147,9 @@ impl Material for ColorMaterial {
             program.use_uniform("textureTransformation", tex.transformation);
             program.use_texture("tex", tex);
         }
+        if let Some(alpha_cutout) = self.alpha_cutout {
+            program.use_uniform("alphaCutout", alpha_cutout);
+        }
     }
     fn render_states(&self) -> RenderStates {
         self.render_states
@@ -143,4 +161,7 @@ impl Material for ColorMaterial {
             MaterialType::Opaque
         }
     }
+    fn discards_fragments(&self) -> bool {
+        self.alpha_cutout.is_some()
+    }
 }
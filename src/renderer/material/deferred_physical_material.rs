@@ -10,6 +10,7 @@ use crate::renderer::*;
 /// However, it is not possible to use the [Object::render] method to render a [Geometry] with this material directly to the screen.
 /// Instead render the object into a [RenderTarget] consisting of a [Texture2DArray] with three RGBA u8 layers as color target and a [DepthTexture2D] as depth target.
 /// Then call the [DeferredPhysicalMaterial::lighting_pass] method with these textures to render to the screen.
+/// The exposure and gamma adjustments of [PhysicalMaterial] are not supported.
 ///
 #[derive(Clone)]
 pub struct DeferredPhysicalMaterial {
@@ -56,18 +57,10 @@ impl DeferredPhysicalMaterial {
     /// [DeferredPhysicalMaterial::metallic_roughness_texture] and [DeferredPhysicalMaterial::occlusion_texture] while any [CpuMaterial::metallic_roughness_texture] or [CpuMaterial::occlusion_texture] are ignored.
     ///
     pub fn new(context: &Context, cpu_material: &CpuMaterial) -> Self {
-        let albedo_texture =
-            cpu_material
-                .albedo_texture
-                .as_ref()
-                .map(|cpu_texture| match &cpu_texture.data {
-                    TextureData::RgbU8(_) | TextureData::RgbaU8(_) => {
-                        let mut cpu_texture = cpu_texture.clone();
-                        cpu_texture.data.to_linear_srgb();
-                        Texture2DRef::from_cpu_texture(context, &cpu_texture)
-                    }
-                    _ => Texture2DRef::from_cpu_texture(context, cpu_texture),
-                });
+        let albedo_texture = cpu_material
+            .albedo_texture
+            .as_ref()
+            .map(|cpu_texture| Texture2DRef::from_cpu_texture_srgb(context, cpu_texture));
         let metallic_roughness_texture =
             if let Some(ref cpu_texture) = cpu_material.occlusion_metallic_roughness_texture {
                 Some(Texture2DRef::from_cpu_texture(context, cpu_texture))
@@ -89,18 +82,10 @@ impl DeferredPhysicalMaterial {
             .normal_texture
             .as_ref()
             .map(|cpu_texture| Texture2DRef::from_cpu_texture(context, cpu_texture));
-        let emissive_texture =
-            cpu_material
-                .emissive_texture
-                .as_ref()
-                .map(|cpu_texture| match &cpu_texture.data {
-                    TextureData::RgbU8(_) | TextureData::RgbaU8(_) => {
-                        let mut cpu_texture = cpu_texture.clone();
-                        cpu_texture.data.to_linear_srgb();
-                        Texture2DRef::from_cpu_texture(context, &cpu_texture)
-                    }
-                    _ => Texture2DRef::from_cpu_texture(context, cpu_texture),
-                });
+        let emissive_texture = cpu_material
+            .emissive_texture
+            .as_ref()
+            .map(|cpu_texture| Texture2DRef::from_cpu_texture_srgb(context, cpu_texture));
         Self {
             name: cpu_material.name.clone(),
             albedo: cpu_material.albedo,
@@ -121,6 +106,7 @@ impl DeferredPhysicalMaterial {
 
     ///
     /// Constructs a deferred physical material from a physical material.
+    /// The [PhysicalMaterial::exposure] and [PhysicalMaterial::gamma] are ignored, since they are not supported by the deferred lighting pass.
     ///
     pub fn from_physical_material(physical_material: &PhysicalMaterial) -> Self {
         Self {
@@ -293,6 +279,10 @@ impl Material for DeferredPhysicalMaterial {
     fn material_type(&self) -> MaterialType {
         MaterialType::Deferred
     }
+
+    fn discards_fragments(&self) -> bool {
+        self.alpha_cutout.is_some()
+    }
 }
 
 impl Default for DeferredPhysicalMaterial {
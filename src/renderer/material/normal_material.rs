@@ -5,6 +5,10 @@ use crate::renderer::*;
 /// Render the object with colors that reflect its normals which primarily is used for debug purposes.
 /// A normal with an x value of -1 yields 0.0 in the red channel and an x value of 1 yields 1.0 in the red channel.
 /// The same mapping is applied from y value to green channel and z value to blue channel.
+/// The normals are in world space, unless [NormalMaterial::view_space] is true in which case they are in the view space of the camera.
+///
+/// Render into a separate color texture to use the normals as input to a post effect, for example screen space ambient occlusion or reflections.
+/// The normals can be decoded in the effect shader using the `decode_normal` function available in the shared shader source.
 ///
 #[derive(Clone)]
 pub struct NormalMaterial {
@@ -12,6 +16,8 @@ pub struct NormalMaterial {
     pub normal_scale: f32,
     /// A tangent space normal map, also known as bump map.
     pub normal_texture: Option<Texture2DRef>,
+    /// Whether the normals are in view space, ie. relative to the camera, instead of world space.
+    pub view_space: bool,
     /// Render states.
     pub render_states: RenderStates,
 }
@@ -26,6 +32,7 @@ impl NormalMaterial {
         Self {
             normal_scale: cpu_material.normal_scale,
             normal_texture,
+            view_space: false,
             render_states: RenderStates::default(),
         }
     }
@@ -35,6 +42,7 @@ impl NormalMaterial {
         Self {
             normal_scale: physical_material.normal_scale,
             normal_texture: physical_material.normal_texture.clone(),
+            view_space: false,
             render_states: RenderStates {
                 write_mask: WriteMask::default(),
                 blend: Blend::Disabled,
@@ -52,10 +60,11 @@ impl FromCpuMaterial for NormalMaterial {
 
 impl Material for NormalMaterial {
     fn id(&self) -> u16 {
-        if self.normal_texture.is_some() {
-            0b1u16 << 15 | 0b110u16
-        } else {
-            0b1u16 << 15 | 0b111u16
+        match (self.normal_texture.is_some(), self.view_space) {
+            (true, false) => 0b1u16 << 15 | 0b110u16,
+            (false, false) => 0b1u16 << 15 | 0b111u16,
+            (true, true) => 0b1u16 << 15 | 0b1001u16,
+            (false, true) => 0b1u16 << 15 | 0b1010u16,
         }
     }
 
@@ -64,7 +73,10 @@ impl Material for NormalMaterial {
             normal: true,
             ..FragmentAttributes::NONE
         };
-        let mut source = String::new();
+        let mut source = include_str!("../../core/shared.frag").to_string();
+        if self.view_space {
+            source.push_str("#define VIEW_SPACE\n");
+        }
         if self.normal_texture.is_some() {
             attributes.uv = true;
             attributes.tangents = true;
@@ -83,7 +95,10 @@ impl Material for NormalMaterial {
         }
     }
 
-    fn use_uniforms(&self, program: &Program, _camera: &Camera, _lights: &[&dyn Light]) {
+    fn use_uniforms(&self, program: &Program, camera: &Camera, _lights: &[&dyn Light]) {
+        if self.view_space {
+            program.use_uniform("viewMatrix", camera.view());
+        }
         if let Some(ref tex) = self.normal_texture {
             program.use_uniform("normalScale", self.normal_scale);
             program.use_uniform("textureTransformation", tex.transformation);
@@ -103,6 +118,7 @@ impl Default for NormalMaterial {
         Self {
             normal_texture: None,
             normal_scale: 1.0,
+            view_space: false,
             render_states: RenderStates::default(),
         }
     }
@@ -1,6 +1,19 @@
 use crate::core::*;
 use crate::renderer::*;
 
+///
+/// Defines how the per-vertex colors of a geometry, for example [CpuMesh::colors], are combined with the albedo of a [PhysicalMaterial].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum VertexColorMode {
+    /// The vertex color is multiplied with the albedo color and albedo texture.
+    #[default]
+    Multiply,
+    /// The vertex color replaces the albedo color, but is still multiplied with the albedo texture.
+    /// Note that geometries without vertex colors use white as the vertex color.
+    Replace,
+}
+
 ///
 /// A physically-based material that renders a [Geometry] in an approximate correct physical manner based on Physically Based Rendering (PBR).
 /// This material is affected by lights.
@@ -41,6 +54,15 @@ pub struct PhysicalMaterial {
     pub emissive_texture: Option<Texture2DRef>,
     /// The lighting model used when rendering this material
     pub lighting_model: LightingModel,
+    /// A factor which the lit color, including the emissive color, is multiplied with before tone mapping, which can be used to brighten or darken the result.
+    /// The default is one, ie. no change. Not supported by [DeferredPhysicalMaterial].
+    pub exposure: f32,
+    /// An additional gamma correction, ie. `color^(1/gamma)`, applied to the output after the color mapping defined by the camera,
+    /// which can be used to match the output on displays with a different response curve.
+    /// The default is one, ie. no additional correction since the color mapping already handles the conversion to sRGB. Not supported by [DeferredPhysicalMaterial].
+    pub gamma: f32,
+    /// Defines how the vertex colors of the geometry are combined with the albedo.
+    pub vertex_color_mode: VertexColorMode,
 }
 
 impl PhysicalMaterial {
@@ -70,18 +92,10 @@ impl PhysicalMaterial {
     }
 
     fn new_internal(context: &Context, cpu_material: &CpuMaterial, is_transparent: bool) -> Self {
-        let albedo_texture =
-            cpu_material
-                .albedo_texture
-                .as_ref()
-                .map(|cpu_texture| match &cpu_texture.data {
-                    TextureData::RgbU8(_) | TextureData::RgbaU8(_) => {
-                        let mut cpu_texture = cpu_texture.clone();
-                        cpu_texture.data.to_linear_srgb();
-                        Texture2DRef::from_cpu_texture(context, &cpu_texture)
-                    }
-                    _ => Texture2DRef::from_cpu_texture(context, cpu_texture),
-                });
+        let albedo_texture = cpu_material
+            .albedo_texture
+            .as_ref()
+            .map(|cpu_texture| Texture2DRef::from_cpu_texture_srgb(context, cpu_texture));
         let metallic_roughness_texture =
             if let Some(ref cpu_texture) = cpu_material.occlusion_metallic_roughness_texture {
                 Some(Texture2DRef::from_cpu_texture(context, cpu_texture))
@@ -103,18 +117,10 @@ impl PhysicalMaterial {
             .normal_texture
             .as_ref()
             .map(|cpu_texture| Texture2DRef::from_cpu_texture(context, cpu_texture));
-        let emissive_texture =
-            cpu_material
-                .emissive_texture
-                .as_ref()
-                .map(|cpu_texture| match &cpu_texture.data {
-                    TextureData::RgbU8(_) | TextureData::RgbaU8(_) => {
-                        let mut cpu_texture = cpu_texture.clone();
-                        cpu_texture.data.to_linear_srgb();
-                        Texture2DRef::from_cpu_texture(context, &cpu_texture)
-                    }
-                    _ => Texture2DRef::from_cpu_texture(context, cpu_texture),
-                });
+        let emissive_texture = cpu_material
+            .emissive_texture
+            .as_ref()
+            .map(|cpu_texture| Texture2DRef::from_cpu_texture_srgb(context, cpu_texture));
         Self {
             name: cpu_material.name.clone(),
             albedo: cpu_material.albedo,
@@ -139,6 +145,9 @@ impl PhysicalMaterial {
             emissive: cpu_material.emissive,
             emissive_texture,
             lighting_model: cpu_material.lighting_model,
+            exposure: 1.0,
+            gamma: 1.0,
+            vertex_color_mode: VertexColorMode::Multiply,
         }
     }
 }
@@ -167,11 +176,17 @@ impl Material for PhysicalMaterial {
         if self.emissive_texture.is_some() {
             id |= 0b1u16 << 4;
         }
+        if self.vertex_color_mode == VertexColorMode::Replace {
+            id |= 0b1u16 << 9;
+        }
         id
     }
 
     fn fragment_shader_source(&self, lights: &[&dyn Light]) -> String {
         let mut output = lights_shader_source(lights, self.lighting_model);
+        if self.vertex_color_mode == VertexColorMode::Replace {
+            output.push_str("#define REPLACE_VERTEX_COLOR;\n");
+        }
         if self.albedo_texture.is_some()
             || self.metallic_roughness_texture.is_some()
             || self.normal_texture.is_some()
@@ -218,6 +233,8 @@ impl Material for PhysicalMaterial {
     fn use_uniforms(&self, program: &Program, camera: &Camera, lights: &[&dyn Light]) {
         camera.tone_mapping.use_uniforms(program);
         camera.color_mapping.use_uniforms(program);
+        program.use_uniform("exposure", self.exposure);
+        program.use_uniform("gamma", self.gamma.max(0.001));
         if !lights.is_empty() {
             program.use_uniform_if_required("cameraPosition", camera.position());
             for (i, light) in lights.iter().enumerate() {
@@ -292,6 +309,9 @@ impl Default for PhysicalMaterial {
             emissive: Srgba::BLACK,
             emissive_texture: None,
             lighting_model: LightingModel::Blinn,
+            exposure: 1.0,
+            gamma: 1.0,
+            vertex_color_mode: VertexColorMode::Multiply,
         }
     }
 }
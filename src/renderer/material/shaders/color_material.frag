@@ -5,6 +5,10 @@ uniform sampler2D tex;
 uniform mat3 textureTransformation;
 #endif
 
+#ifdef ALPHACUT
+uniform float alphaCutout;
+#endif
+
 in vec4 col;
 
 layout (location = 0) out vec4 outColor;
@@ -17,5 +21,9 @@ void main()
     outColor *= texture(tex, (textureTransformation * vec3(uvs, 1.0)).xy);
     #endif
 
+    #ifdef ALPHACUT
+    if (outColor.a < alphaCutout) discard;
+    #endif
+
     outColor.rgb = color_mapping(outColor.rgb);
 }
\ No newline at end of file
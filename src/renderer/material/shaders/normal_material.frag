@@ -7,6 +7,10 @@ uniform mat3 textureTransformation;
 uniform float normalScale;
 #endif
 
+#ifdef VIEW_SPACE
+uniform mat4 viewMatrix;
+#endif
+
 layout (location = 0) out vec4 outColor;
 
 void main()
@@ -19,5 +23,8 @@ void main()
     mat3 tbn = mat3(tangent, bitangent, normal);
     normal = tbn * ((2.0 * texture(normalTexture, (textureTransformation * vec3(uvs, 1.0)).xy).xyz - 1.0) * vec3(normalScale, normalScale, 1.0));
 #endif
-    outColor = vec4(0.5 + 0.5 * normal, 1.0);
+#ifdef VIEW_SPACE
+    normal = normalize(mat3(viewMatrix) * normal);
+#endif
+    outColor = vec4(encode_normal(normal), 1.0);
 }
\ No newline at end of file
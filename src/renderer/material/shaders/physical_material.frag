@@ -2,6 +2,8 @@
 uniform float metallic;
 uniform float roughness;
 uniform vec3 cameraPosition;
+uniform float exposure;
+uniform float gamma;
 
 uniform vec4 albedo;
 #ifdef USE_ALBEDO_TEXTURE
@@ -40,7 +42,11 @@ layout (location = 0) out vec4 outColor;
 
 void main()
 {
+#ifdef REPLACE_VERTEX_COLOR
+    vec4 surface_color = col;
+#else
     vec4 surface_color = albedo * col;
+#endif
 #ifdef USE_ALBEDO_TEXTURE
     vec4 c = texture(albedoTexture, (albedoTexTransform * vec3(uvs, 1.0)).xy);
     #ifdef ALPHACUT
@@ -76,7 +82,8 @@ void main()
 #endif
 
     outColor.rgb = total_emissive + calculate_lighting(cameraPosition, surface_color.rgb, pos, normal, metallic_factor, roughness_factor, occlusion);
-    outColor.rgb = tone_mapping(outColor.rgb);
+    outColor.rgb = tone_mapping(exposure * outColor.rgb);
     outColor.rgb = color_mapping(outColor.rgb);
+    outColor.rgb = pow(outColor.rgb, vec3(1.0 / gamma));
     outColor.a = surface_color.a;
 }
\ No newline at end of file
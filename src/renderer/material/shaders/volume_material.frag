@@ -0,0 +1,42 @@
+uniform vec3 cameraPosition;
+uniform sampler3D tex;
+uniform sampler2D transferFunction;
+uniform vec3 size;
+uniform float stepSize;
+
+in vec3 pos;
+
+layout (location = 0) out vec4 outColor;
+
+void main() {
+    vec3 rayDir = normalize(pos - cameraPosition);
+
+    // Find the distances along the ray to where it enters and exits the box
+    vec3 t0 = (-0.5 * size - cameraPosition) / rayDir;
+    vec3 t1 = (0.5 * size - cameraPosition) / rayDir;
+    vec3 tMin = min(t0, t1);
+    vec3 tMax = max(t0, t1);
+    float tNear = max(max(max(tMin.x, tMin.y), tMin.z), 0.0);
+    float tFar = min(min(tMax.x, tMax.y), tMax.z);
+    int steps = int(max(tFar - tNear, 0.0) / stepSize) + 1;
+
+    // Composite the samples front to back
+    vec4 color = vec4(0.0);
+    for (int i = 0; i < 1024; i++) {
+        if (i >= steps || color.a >= 0.99) {
+            break;
+        }
+        vec3 rayPos = cameraPosition + (tNear + (float(i) + 0.5) * stepSize) * rayDir;
+        float density = texture(tex, rayPos / size + 0.5).r;
+        vec4 sampleColor = texture(transferFunction, vec2(density, 0.5));
+        // The opacity in the transfer function is per world unit, so correct it for the step size
+        float alpha = 1.0 - pow(1.0 - clamp(sampleColor.a, 0.0, 1.0), stepSize);
+        color.rgb += (1.0 - color.a) * alpha * sampleColor.rgb;
+        color.a += (1.0 - color.a) * alpha;
+    }
+
+    outColor.rgb = color.rgb / max(color.a, 0.0001);
+    outColor.rgb = tone_mapping(outColor.rgb);
+    outColor.rgb = color_mapping(outColor.rgb);
+    outColor.a = color.a;
+}
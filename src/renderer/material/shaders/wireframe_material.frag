@@ -0,0 +1,21 @@
+uniform vec4 lineColor;
+uniform vec4 fillColor;
+uniform float lineWidth;
+
+in vec2 uvs;
+
+layout (location = 0) out vec4 outColor;
+
+void main()
+{
+    vec3 barycentric = vec3(uvs, 1.0 - uvs.x - uvs.y);
+    vec3 width = fwidth(barycentric) * lineWidth;
+    vec3 edge = smoothstep(0.5 * width, width, barycentric);
+    float line = 1.0 - min(min(edge.x, edge.y), edge.z);
+
+    outColor = mix(fillColor, lineColor, line);
+    if (outColor.a < 0.01) {
+        discard;
+    }
+    outColor.rgb = color_mapping(outColor.rgb);
+}
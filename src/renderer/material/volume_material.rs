@@ -0,0 +1,108 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// A material that renders the voxel data in the [VolumeMaterial::voxels] as a semi-transparent volume, for example for medical or scientific visualization.
+/// Rays are marched through the volume with a fixed [VolumeMaterial::step_size] and at each step, the density, ie. the red channel of the voxel data,
+/// is mapped to a color and opacity by the [VolumeMaterial::transfer_function], after which the samples are composited front to back.
+/// This material should be applied to a cube with center in origo, for example [CpuMesh::cube].
+///
+#[derive(Clone)]
+pub struct VolumeMaterial {
+    /// The voxel data, where the red channel is the density in the range `[0..1]`.
+    pub voxels: std::sync::Arc<Texture3D>,
+    /// A lookup texture with a height of one pixel which maps a density in the range `[0..1]` (the horizontal texture coordinate) to a color and an opacity,
+    /// see [VolumeMaterial::new_transfer_function]. The opacity is the opacity of one world unit of the volume with that density.
+    pub transfer_function: std::sync::Arc<Texture2D>,
+    /// The distance in world units between the samples along each ray. A smaller step size gives a more accurate result but is more expensive.
+    pub step_size: f32,
+    /// The size of the cube that is used to render the voxel data. The texture is scaled to fill the entire cube.
+    pub size: Vec3,
+}
+
+impl VolumeMaterial {
+    ///
+    /// Creates a transfer function lookup texture (see [VolumeMaterial::transfer_function]) from the given colors,
+    /// where the first color is used for a density of zero, the last color for a density of one and the colors in between are linearly interpolated.
+    /// The colors are stored in an sRGB texture (see [Texture2D::new_srgb]), so they are decoded to linear sRGB when sampled and interpolated in linear color space.
+    ///
+    /// **Panics:** If no colors are given.
+    ///
+    pub fn new_transfer_function(context: &Context, colors: &[Srgba]) -> Texture2D {
+        if colors.is_empty() {
+            panic!("a transfer function must have at least one color");
+        }
+        Texture2D::new_srgb(
+            context,
+            &CpuTexture {
+                data: TextureData::RgbaU8(colors.iter().map(|c| (*c).into()).collect()),
+                width: colors.len() as u32,
+                height: 1,
+                min_filter: Interpolation::Linear,
+                mag_filter: Interpolation::Linear,
+                mip_map_filter: None,
+                wrap_s: Wrapping::ClampToEdge,
+                wrap_t: Wrapping::ClampToEdge,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+impl Material for VolumeMaterial {
+    fn id(&self) -> u16 {
+        0b1u16 << 15 | 0b1u16 << 7 | 0b1u16
+    }
+
+    fn fragment_shader_source(&self, _lights: &[&dyn Light]) -> String {
+        let mut source = String::new();
+        source.push_str(ToneMapping::fragment_shader_source());
+        source.push_str(ColorMapping::fragment_shader_source());
+        source.push_str(include_str!("shaders/volume_material.frag"));
+        source
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes {
+            position: true,
+            ..FragmentAttributes::NONE
+        }
+    }
+
+    fn use_uniforms(&self, program: &Program, camera: &Camera, _lights: &[&dyn Light]) {
+        camera.tone_mapping.use_uniforms(program);
+        camera.color_mapping.use_uniforms(program);
+        program.use_uniform("cameraPosition", camera.position());
+        program.use_uniform("size", self.size);
+        program.use_uniform("stepSize", self.step_size.max(0.0001));
+        program.use_texture_3d("tex", &self.voxels);
+        program.use_texture("transferFunction", &self.transfer_function);
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            blend: Blend::TRANSPARENCY,
+            write_mask: WriteMask::COLOR,
+            ..Default::default()
+        }
+    }
+
+    fn material_type(&self) -> MaterialType {
+        MaterialType::Transparent
+    }
+}
+
+impl FromCpuVoxelGrid for VolumeMaterial {
+    fn from_cpu_voxel_grid(context: &Context, cpu_voxel_grid: &CpuVoxelGrid) -> Self {
+        Self {
+            voxels: Texture3D::new(context, &cpu_voxel_grid.voxels).into(),
+            transfer_function: Self::new_transfer_function(
+                context,
+                &[Srgba::new(0, 0, 0, 0), Srgba::new(255, 255, 255, 255)],
+            )
+            .into(),
+            step_size: cpu_voxel_grid.size.magnitude() / 200.0,
+            size: cpu_voxel_grid.size,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// A material that renders the edges of the triangles of a [Geometry] as lines of the given width in pixels.
+/// Since line rendering is not supported on all platforms, the edges are computed in the fragment shader from barycentric coordinates
+/// given as uv coordinates, so the geometry must be constructed from a [CpuMesh] returned from [wireframe_cpu_mesh].
+/// This material is not affected by lights.
+///
+#[derive(Clone)]
+pub struct WireframeMaterial {
+    /// The color of the lines.
+    pub line_color: Srgba,
+    /// The color of the interior of the triangles. The interior is not rendered if the alpha value is zero.
+    pub fill_color: Srgba,
+    /// The width of the lines in pixels.
+    pub line_width: f32,
+    /// Render states.
+    pub render_states: RenderStates,
+}
+
+impl Default for WireframeMaterial {
+    fn default() -> Self {
+        Self {
+            line_color: Srgba::BLACK,
+            fill_color: Srgba::new(0, 0, 0, 0),
+            line_width: 1.0,
+            render_states: RenderStates {
+                cull: Cull::None,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Material for WireframeMaterial {
+    fn id(&self) -> u16 {
+        0b1u16 << 15 | 0b1011u16
+    }
+
+    fn fragment_shader_source(&self, _lights: &[&dyn Light]) -> String {
+        let mut shader = String::new();
+        shader.push_str(ColorMapping::fragment_shader_source());
+        shader.push_str(include_str!("shaders/wireframe_material.frag"));
+        shader
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes {
+            uv: true,
+            ..FragmentAttributes::NONE
+        }
+    }
+
+    fn use_uniforms(&self, program: &Program, camera: &Camera, _lights: &[&dyn Light]) {
+        camera.color_mapping.use_uniforms(program);
+        program.use_uniform("lineColor", self.line_color.to_linear_srgb());
+        program.use_uniform("fillColor", self.fill_color.to_linear_srgb());
+        program.use_uniform("lineWidth", self.line_width.max(0.0));
+    }
+
+    fn render_states(&self) -> RenderStates {
+        self.render_states
+    }
+
+    fn material_type(&self) -> MaterialType {
+        MaterialType::Opaque
+    }
+
+    fn discards_fragments(&self) -> bool {
+        true
+    }
+}
+
+///
+/// Returns a copy of the given mesh which can be rendered with a [WireframeMaterial].
+/// The returned mesh is not indexed, ie. each triangle has its own three vertices, and the uv coordinates of the vertices of each triangle
+/// are the barycentric coordinates `(1, 0)`, `(0, 1)` and `(0, 0)`. Normals and colors are kept, while tangents and the original uv coordinates are removed.
+///
+pub fn wireframe_cpu_mesh(cpu_mesh: &CpuMesh) -> CpuMesh {
+    let positions = cpu_mesh.positions.to_f32();
+    let barycentric = [vec2(1.0, 0.0), vec2(0.0, 1.0), vec2(0.0, 0.0)];
+    let mut new_positions = Vec::new();
+    let mut normals = cpu_mesh.normals.as_ref().map(|_| Vec::new());
+    let mut colors = cpu_mesh.colors.as_ref().map(|_| Vec::new());
+    let mut uvs = Vec::new();
+    cpu_mesh.for_each_triangle(|i0, i1, i2| {
+        for (i, index) in [i0, i1, i2].into_iter().enumerate() {
+            new_positions.push(positions[index]);
+            uvs.push(barycentric[i]);
+            if let (Some(normals), Some(source)) = (normals.as_mut(), cpu_mesh.normals.as_ref()) {
+                normals.push(source[index]);
+            }
+            if let (Some(colors), Some(source)) = (colors.as_mut(), cpu_mesh.colors.as_ref()) {
+                colors.push(source[index]);
+            }
+        }
+    });
+    CpuMesh {
+        positions: Positions::F32(new_positions),
+        normals,
+        colors,
+        uvs: Some(uvs),
+        ..Default::default()
+    }
+}
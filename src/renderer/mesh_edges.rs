@@ -0,0 +1,144 @@
+use crate::renderer::*;
+use std::collections::HashMap;
+
+///
+/// A unique edge in a [CpuMesh] together with the triangles adjacent to it, see [compute_edges].
+///
+#[derive(Clone, Debug)]
+pub struct MeshEdge {
+    /// The positions of the two end points of the edge.
+    pub positions: [Vec3; 2],
+    /// The indices of the first vertices with the positions of the two end points of the edge.
+    pub vertices: [usize; 2],
+    /// The indices of the triangles adjacent to the edge.
+    /// Usually one (a boundary edge) or two, but can be more if the mesh is non-manifold.
+    pub faces: Vec<usize>,
+    /// The normals of the triangles adjacent to the edge, in the same order as [MeshEdge::faces].
+    pub face_normals: Vec<Vec3>,
+}
+
+impl MeshEdge {
+    ///
+    /// Returns whether or not this edge is on the boundary of the mesh, ie. it is only adjacent to one triangle.
+    ///
+    pub fn is_boundary(&self) -> bool {
+        self.faces.len() == 1
+    }
+
+    ///
+    /// Returns whether or not this edge is adjacent to more than two triangles.
+    ///
+    pub fn is_non_manifold(&self) -> bool {
+        self.faces.len() > 2
+    }
+
+    ///
+    /// Returns whether or not all adjacent triangles lie in the same plane, for example the diagonal of a triangulated quad.
+    /// Such edges are usually not wanted when drawing the hard edges of a mesh.
+    ///
+    pub fn is_flat(&self) -> bool {
+        self.face_normals.len() > 1
+            && self
+                .face_normals
+                .iter()
+                .all(|n| n.dot(self.face_normals[0]) > 1.0 - 1.0e-5)
+    }
+
+    ///
+    /// Returns whether or not this edge is a silhouette edge when seen from the given position,
+    /// ie. one adjacent triangle faces the given position and another faces away from it.
+    /// Boundary edges are always silhouette edges.
+    ///
+    pub fn is_silhouette(&self, view_position: Vec3) -> bool {
+        if self.is_boundary() {
+            return true;
+        }
+        let direction = self.positions[0] - view_position;
+        let front_facing = |n: &Vec3| n.dot(direction) < 0.0;
+        let first = front_facing(&self.face_normals[0]);
+        self.face_normals.iter().any(|n| front_facing(n) != first)
+    }
+}
+
+///
+/// Computes the unique edges of the given mesh together with the triangles adjacent to each edge.
+/// Vertices with exactly the same position are considered the same vertex, so edges are found between triangles
+/// even though the vertices are duplicated, for example to have different normals or uv coordinates.
+/// Degenerate triangles are ignored.
+///
+pub fn compute_edges(cpu_mesh: &CpuMesh) -> Vec<MeshEdge> {
+    let positions = cpu_mesh.positions.to_f32();
+    let mut unique_vertex = HashMap::new();
+    let vertex_ids = positions
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            *unique_vertex
+                .entry([p.x.to_bits(), p.y.to_bits(), p.z.to_bits()])
+                .or_insert(i)
+        })
+        .collect::<Vec<_>>();
+
+    let mut edge_indices = HashMap::new();
+    let mut edges: Vec<MeshEdge> = Vec::new();
+    let mut face_index = 0;
+    cpu_mesh.for_each_triangle(|i0, i1, i2| {
+        let triangle = [vertex_ids[i0], vertex_ids[i1], vertex_ids[i2]];
+        let normal = (positions[triangle[1]] - positions[triangle[0]])
+            .cross(positions[triangle[2]] - positions[triangle[0]]);
+        if triangle[0] != triangle[1]
+            && triangle[1] != triangle[2]
+            && triangle[2] != triangle[0]
+            && normal.magnitude2() > f32::EPSILON * f32::EPSILON
+        {
+            let normal = normal.normalize();
+            for (a, b) in [
+                (triangle[0], triangle[1]),
+                (triangle[1], triangle[2]),
+                (triangle[2], triangle[0]),
+            ] {
+                let key = (a.min(b), a.max(b));
+                let index = *edge_indices.entry(key).or_insert_with(|| {
+                    edges.push(MeshEdge {
+                        positions: [positions[key.0], positions[key.1]],
+                        vertices: [key.0, key.1],
+                        faces: Vec::new(),
+                        face_normals: Vec::new(),
+                    });
+                    edges.len() - 1
+                });
+                edges[index].faces.push(face_index);
+                edges[index].face_normals.push(normal);
+            }
+        }
+        face_index += 1;
+    });
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cube_edges() {
+        let edges = compute_edges(&CpuMesh::cube());
+        // The 12 edges of the cube and the diagonal of each of the 6 sides
+        assert_eq!(edges.len(), 18);
+        assert!(edges
+            .iter()
+            .all(|e| e.faces.len() == 2 && !e.is_boundary() && !e.is_non_manifold()));
+        assert_eq!(edges.iter().filter(|e| !e.is_flat()).count(), 12);
+    }
+
+    #[test]
+    fn silhouette_edges_of_cube() {
+        let edges = compute_edges(&CpuMesh::cube());
+        let silhouette = edges
+            .iter()
+            .filter(|e| !e.is_flat() && e.is_silhouette(vec3(0.0, 0.0, 5.0)))
+            .count();
+        // Looking straight at the front side, the silhouette is the four edges around it
+        assert_eq!(silhouette, 4);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::renderer::*;
+use std::collections::HashMap;
+
+///
+/// Returns a copy of the given mesh with per vertex normals computed by averaging the normals of the adjacent triangles, weighted by their area.
+/// Unlike [CpuMesh::compute_normals], the normals of two adjacent triangles are only averaged if the angle between them is less than the given crease angle,
+/// so hard edges, for example the edges of a cube, are kept sharp while curved surfaces are smoothed.
+/// Vertices on a hard edge are split into one vertex for each side of the edge, so the returned mesh can have more vertices than the given mesh.
+/// Vertices with exactly the same position are considered the same vertex, so normals are also averaged across duplicated vertices.
+///
+/// A crease angle of zero gives flat shading with one normal per triangle, while a crease angle of 180 degrees gives the same result as [CpuMesh::compute_normals].
+/// Any existing tangents are kept but should be recomputed if the normals change significantly.
+///
+pub fn compute_normals_with_crease_angle(
+    cpu_mesh: &CpuMesh,
+    crease_angle: impl Into<Radians>,
+) -> CpuMesh {
+    let positions = cpu_mesh.positions.to_f32();
+    let mut unique_vertex = HashMap::new();
+    let vertex_ids = positions
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            *unique_vertex
+                .entry([p.x.to_bits(), p.y.to_bits(), p.z.to_bits()])
+                .or_insert(i)
+        })
+        .collect::<Vec<_>>();
+
+    let mut faces = Vec::new();
+    cpu_mesh.for_each_triangle(|i0, i1, i2| faces.push([i0, i1, i2]));
+    let weighted_normals = faces
+        .iter()
+        .map(|f| (positions[f[1]] - positions[f[0]]).cross(positions[f[2]] - positions[f[0]]))
+        .collect::<Vec<_>>();
+    let face_normals = weighted_normals
+        .iter()
+        .map(|n| {
+            if n.magnitude2() > f32::EPSILON * f32::EPSILON {
+                n.normalize()
+            } else {
+                Vec3::zero()
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut adjacent_faces = vec![Vec::new(); positions.len()];
+    for (f, face) in faces.iter().enumerate() {
+        for i in face {
+            adjacent_faces[vertex_ids[*i]].push(f);
+        }
+    }
+
+    let threshold = crease_angle.into().0.cos() - 1.0e-5;
+    let mut vertices = Vec::new();
+    let mut normals = Vec::new();
+    let mut new_vertex = HashMap::new();
+    let mut indices = Vec::with_capacity(3 * faces.len());
+    for (f, face) in faces.iter().enumerate() {
+        for i in face {
+            let mut normal = Vec3::zero();
+            for g in adjacent_faces[vertex_ids[*i]].iter() {
+                if face_normals[f] == Vec3::zero()
+                    || face_normals[f].dot(face_normals[*g]) >= threshold
+                {
+                    normal += weighted_normals[*g];
+                }
+            }
+            let normal = if normal.magnitude2() > f32::EPSILON * f32::EPSILON {
+                normal.normalize()
+            } else {
+                face_normals[f]
+            };
+            let index = *new_vertex
+                .entry((
+                    *i,
+                    [normal.x.to_bits(), normal.y.to_bits(), normal.z.to_bits()],
+                ))
+                .or_insert_with(|| {
+                    vertices.push(*i);
+                    normals.push(normal);
+                    vertices.len() - 1
+                });
+            indices.push(index as u32);
+        }
+    }
+
+    CpuMesh {
+        positions: match &cpu_mesh.positions {
+            Positions::F32(p) => Positions::F32(vertices.iter().map(|i| p[*i]).collect()),
+            Positions::F64(p) => Positions::F64(vertices.iter().map(|i| p[*i]).collect()),
+        },
+        indices: Indices::U32(indices),
+        normals: Some(normals),
+        tangents: cpu_mesh
+            .tangents
+            .as_ref()
+            .map(|t| vertices.iter().map(|i| t[*i]).collect()),
+        uvs: cpu_mesh
+            .uvs
+            .as_ref()
+            .map(|t| vertices.iter().map(|i| t[*i]).collect()),
+        colors: cpu_mesh
+            .colors
+            .as_ref()
+            .map(|t| vertices.iter().map(|i| t[*i]).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle_normals(cpu_mesh: &CpuMesh) -> Vec<(Vec3, [Vec3; 3])> {
+        let positions = cpu_mesh.positions.to_f32();
+        let normals = cpu_mesh.normals.as_ref().unwrap();
+        let mut result = Vec::new();
+        cpu_mesh.for_each_triangle(|i0, i1, i2| {
+            let face_normal = (positions[i1] - positions[i0])
+                .cross(positions[i2] - positions[i0])
+                .normalize();
+            result.push((face_normal, [normals[i0], normals[i1], normals[i2]]));
+        });
+        result
+    }
+
+    #[test]
+    fn cube_with_low_crease_angle_is_flat() {
+        let cube = compute_normals_with_crease_angle(&CpuMesh::cube(), degrees(30.0));
+        cube.validate().unwrap();
+        for (face_normal, normals) in triangle_normals(&cube) {
+            for normal in normals {
+                assert!((normal - face_normal).magnitude() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn cube_with_high_crease_angle_is_smooth() {
+        let cube = compute_normals_with_crease_angle(&CpuMesh::cube(), degrees(180.0));
+        cube.validate().unwrap();
+        let positions = cube.positions.to_f32();
+        let normals = cube.normals.as_ref().unwrap();
+        for (position, normal) in positions.iter().zip(normals) {
+            assert!((normal.magnitude() - 1.0).abs() < 1e-5);
+            // All vertices at the same corner share the same normal
+            for (other_position, other_normal) in positions.iter().zip(normals) {
+                if other_position == position {
+                    assert_eq!(other_normal, normal);
+                }
+            }
+            // Each corner normal points away from the center of the cube, between the three adjacent sides
+            for i in 0..3 {
+                assert!(normal[i] * position[i] > 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_sphere_is_unchanged_by_crease_angle() {
+        let sphere = CpuMesh::sphere(16);
+        let low = compute_normals_with_crease_angle(&sphere, degrees(60.0));
+        assert!(low.vertex_count() <= sphere.vertex_count());
+        let positions = low.positions.to_f32();
+        for (position, normal) in positions.iter().zip(low.normals.as_ref().unwrap()) {
+            assert!(normal.dot(position.normalize()) > 0.99);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use crate::renderer::*;
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+///
+/// Returns a plane in the xz-plane from -1 to 1 in both the x and z direction with normals pointing in the positive y direction,
+/// ie. it is visible from above when back faces are culled.
+/// The plane is subdivided into a grid of `width_segments` times `height_segments` quads, so it consists of `(width_segments + 1) * (height_segments + 1)` vertices,
+/// which is useful for example to displace the vertices in a vertex shader. The uv coordinates goes from 0 to 1 along the x and z axis.
+/// See also [CpuMesh::square] for a single quad in the xy-plane.
+///
+pub fn plane_cpu_mesh(width_segments: u32, height_segments: u32) -> CpuMesh {
+    let width_segments = width_segments.max(1);
+    let height_segments = height_segments.max(1);
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    for j in 0..=height_segments {
+        let v = j as f32 / height_segments as f32;
+        for i in 0..=width_segments {
+            let u = i as f32 / width_segments as f32;
+            positions.push(vec3(2.0 * u - 1.0, 0.0, 2.0 * v - 1.0));
+            uvs.push(vec2(u, v));
+        }
+    }
+    let mut indices = Vec::new();
+    let stride = width_segments + 1;
+    for j in 0..height_segments {
+        for i in 0..width_segments {
+            let a = j * stride + i;
+            let b = a + 1;
+            let c = a + stride;
+            let d = c + 1;
+            indices.extend([a, c, b, b, c, d]);
+        }
+    }
+    let vertex_count = positions.len();
+    CpuMesh {
+        positions: Positions::F32(positions),
+        indices: Indices::U32(indices),
+        normals: Some(vec![vec3(0.0, 1.0, 0.0); vertex_count]),
+        tangents: Some(vec![vec4(1.0, 0.0, 0.0, 1.0); vertex_count]),
+        uvs: Some(uvs),
+        ..Default::default()
+    }
+}
+
+///
+/// Returns a torus in the xz-plane centered at the origin where the distance from the center of the torus to the center of the tube is one
+/// and the radius of the tube is `tube_radius`.
+/// The torus is subdivided into `radial_subdivisions` segments around the y-axis and `tubular_subdivisions` segments around the tube.
+/// The positions, normals, uv coordinates and indices are computed, so the result can be used directly in for example [Mesh::new].
+/// See also [CpuMesh::sphere], [CpuMesh::cylinder] and [CpuMesh::cone] for other primitives.
+///
+pub fn torus_cpu_mesh(
+    radial_subdivisions: u32,
+    tubular_subdivisions: u32,
+    tube_radius: f32,
+) -> CpuMesh {
+    let radial_subdivisions = radial_subdivisions.max(3);
+    let tubular_subdivisions = tubular_subdivisions.max(3);
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    for i in 0..=radial_subdivisions {
+        let u = i as f32 / radial_subdivisions as f32;
+        let (sin_theta, cos_theta) = (2.0 * PI * u).sin_cos();
+        let center = vec3(cos_theta, 0.0, sin_theta);
+        for j in 0..=tubular_subdivisions {
+            let v = j as f32 / tubular_subdivisions as f32;
+            let (sin_phi, cos_phi) = (2.0 * PI * v).sin_cos();
+            let normal = center * cos_phi + vec3(0.0, sin_phi, 0.0);
+            positions.push(center + normal * tube_radius);
+            normals.push(normal);
+            uvs.push(vec2(u, v));
+        }
+    }
+    let mut indices = Vec::new();
+    let stride = tubular_subdivisions + 1;
+    for i in 0..radial_subdivisions {
+        for j in 0..tubular_subdivisions {
+            let a = i * stride + j;
+            let b = (i + 1) * stride + j;
+            indices.extend([a, a + 1, b, b, a + 1, b + 1]);
+        }
+    }
+    CpuMesh {
+        positions: Positions::F32(positions),
+        indices: Indices::U32(indices),
+        normals: Some(normals),
+        uvs: Some(uvs),
+        ..Default::default()
+    }
+}
+
+///
+/// Returns a sphere with radius one centered at the origin constructed by subdividing each triangle of an icosahedron into four triangles `subdivisions` times.
+/// Compared to [CpuMesh::sphere], the triangles are more evenly distributed over the surface, which is better for example for displacement.
+/// The uv coordinates are computed using an equirectangular mapping, so textures will be slightly distorted where the triangles cross the seam at the negative x-axis.
+///
+pub fn icosphere_cpu_mesh(subdivisions: u32) -> CpuMesh {
+    let t = (1.0 + 5.0f32.sqrt()) / 2.0;
+    let mut positions = [
+        vec3(-1.0, t, 0.0),
+        vec3(1.0, t, 0.0),
+        vec3(-1.0, -t, 0.0),
+        vec3(1.0, -t, 0.0),
+        vec3(0.0, -1.0, t),
+        vec3(0.0, 1.0, t),
+        vec3(0.0, -1.0, -t),
+        vec3(0.0, 1.0, -t),
+        vec3(t, 0.0, -1.0),
+        vec3(t, 0.0, 1.0),
+        vec3(-t, 0.0, -1.0),
+        vec3(-t, 0.0, 1.0),
+    ]
+    .iter()
+    .map(|p| p.normalize())
+    .collect::<Vec<_>>();
+    let mut triangles: Vec<[u32; 3]> = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+    for _ in 0..subdivisions {
+        let mut midpoints = HashMap::new();
+        let mut midpoint = |a: u32, b: u32| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                positions.push((positions[a as usize] + positions[b as usize]).normalize());
+                positions.len() as u32 - 1
+            })
+        };
+        triangles = triangles
+            .iter()
+            .flat_map(|&[a, b, c]| {
+                let ab = midpoint(a, b);
+                let bc = midpoint(b, c);
+                let ca = midpoint(c, a);
+                [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+    let uvs = positions
+        .iter()
+        .map(|p| {
+            vec2(
+                0.5 + p.z.atan2(p.x) / (2.0 * PI),
+                0.5 - p.y.clamp(-1.0, 1.0).asin() / PI,
+            )
+        })
+        .collect();
+    CpuMesh {
+        normals: Some(positions.clone()),
+        positions: Positions::F32(positions),
+        indices: Indices::U32(triangles.into_iter().flatten().collect()),
+        uvs: Some(uvs),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_mesh(cpu_mesh: &CpuMesh) {
+        cpu_mesh.validate().unwrap();
+        let positions = cpu_mesh.positions.to_f32();
+        let normals = cpu_mesh.normals.as_ref().unwrap();
+        let uvs = cpu_mesh.uvs.as_ref().unwrap();
+        assert_eq!(normals.len(), positions.len());
+        assert_eq!(uvs.len(), positions.len());
+        for normal in normals {
+            assert!((normal.magnitude() - 1.0).abs() < 1e-5, "{normal:?}");
+        }
+        for uv in uvs {
+            assert!((0.0..=1.0).contains(&uv.x) && (0.0..=1.0).contains(&uv.y));
+        }
+        cpu_mesh.for_each_triangle(|i0, i1, i2| {
+            // Counterclockwise winding, ie. the triangle faces the same way as the vertex normals
+            let face_normal = (positions[i1] - positions[i0]).cross(positions[i2] - positions[i0]);
+            assert!(face_normal.dot(normals[i0] + normals[i1] + normals[i2]) > 0.0);
+        });
+    }
+
+    #[test]
+    fn plane() {
+        let cpu_mesh = plane_cpu_mesh(4, 3);
+        check_mesh(&cpu_mesh);
+        assert_eq!(cpu_mesh.vertex_count(), 5 * 4);
+        assert_eq!(cpu_mesh.indices.len(), Some(4 * 3 * 6));
+        assert_eq!(plane_cpu_mesh(0, 0).vertex_count(), 4);
+    }
+
+    #[test]
+    fn torus() {
+        let cpu_mesh = torus_cpu_mesh(16, 8, 0.25);
+        check_mesh(&cpu_mesh);
+        assert_eq!(cpu_mesh.vertex_count(), 17 * 9);
+        assert_eq!(cpu_mesh.indices.len(), Some(16 * 8 * 6));
+    }
+
+    #[test]
+    fn icosphere() {
+        for subdivisions in 0..3 {
+            let cpu_mesh = icosphere_cpu_mesh(subdivisions);
+            check_mesh(&cpu_mesh);
+            assert_eq!(
+                cpu_mesh.indices.len(),
+                Some(20 * 4usize.pow(subdivisions) * 3)
+            );
+            for p in cpu_mesh.positions.to_f32() {
+                assert!((p.magnitude() - 1.0).abs() < 1e-5);
+            }
+        }
+    }
+}
@@ -15,6 +15,14 @@ macro_rules! impl_object_body {
         fn material_type(&self) -> MaterialType {
             self.$inner().material_type()
         }
+
+        fn layer(&self) -> u32 {
+            self.$inner().layer()
+        }
+
+        fn material(&self) -> Option<&dyn Material> {
+            self.$inner().material()
+        }
     };
 }
 
@@ -22,6 +30,10 @@ mod gm;
 #[doc(inline)]
 pub use gm::*;
 
+mod layered;
+#[doc(inline)]
+pub use layered::*;
+
 mod model;
 #[doc(inline)]
 pub use model::*;
@@ -38,6 +50,10 @@ mod skybox;
 #[doc(inline)]
 pub use skybox::*;
 
+mod atmosphere_sky;
+#[doc(inline)]
+pub use atmosphere_sky::*;
+
 mod imposters;
 #[doc(inline)]
 pub use imposters::*;
@@ -54,6 +70,10 @@ mod axes;
 #[doc(inline)]
 pub use axes::*;
 
+mod canvas2d;
+#[doc(inline)]
+pub use canvas2d::*;
+
 use crate::core::*;
 use crate::renderer::*;
 
@@ -72,8 +92,29 @@ pub trait Object: Geometry {
     /// Returns the type of material applied to this object.
     ///
     fn material_type(&self) -> MaterialType;
+
+    ///
+    /// Returns the layer bitmask of this object which is used to filter which objects are rendered, see for example [RenderTarget::render_layers].
+    /// The default is [DEFAULT_LAYER]. Use [Layered] to place any object in other layers.
+    ///
+    fn layer(&self) -> u32 {
+        DEFAULT_LAYER
+    }
+
+    ///
+    /// Returns the material of this object if it is rendered with a single [Material], otherwise `None`, which is the default.
+    /// This is used to render the object with other render states than the render states of the material, for example in [RenderTarget::render_with_depth_pre_pass].
+    ///
+    fn material(&self) -> Option<&dyn Material> {
+        None
+    }
 }
 
+///
+/// The layer bitmask that objects belong to by default (see [Object::layer]).
+///
+pub const DEFAULT_LAYER: u32 = 1;
+
 use std::ops::Deref;
 impl<T: Object + ?Sized> Object for &T {
     impl_object_body!(deref);
@@ -96,7 +137,17 @@ impl<T: Object> Object for std::sync::Arc<T> {
 }
 
 impl<T: Object> Object for std::cell::RefCell<T> {
-    impl_object_body!(borrow);
+    fn render(&self, camera: &Camera, lights: &[&dyn Light]) {
+        self.borrow().render(camera, lights)
+    }
+
+    fn material_type(&self) -> MaterialType {
+        self.borrow().material_type()
+    }
+
+    fn layer(&self) -> u32 {
+        self.borrow().layer()
+    }
 }
 
 impl<T: Object> Object for std::sync::RwLock<T> {
@@ -107,4 +158,8 @@ impl<T: Object> Object for std::sync::RwLock<T> {
     fn material_type(&self) -> MaterialType {
         self.read().unwrap().material_type()
     }
+
+    fn layer(&self) -> u32 {
+        self.read().unwrap().layer()
+    }
 }
@@ -0,0 +1,305 @@
+use super::skybox::skybox_vertex_buffer;
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// A procedural sky which color is calculated analytically from the direction of the sun using the Preetham daylight model.
+/// Contrary to a [Skybox], no textures are needed and the sky is updated as the sun moves, see [AtmosphereSky::set_sun_direction].
+/// Use [AtmosphereSky::sun_color] and [AtmosphereSky::sun_intensity] to create a [DirectionalLight] that matches the sky.
+///
+pub struct AtmosphereSky {
+    context: Context,
+    vertex_buffer: VertexBuffer,
+    sun_direction: Vec3,
+    turbidity: f32,
+    /// A scale factor applied to the sky color.
+    pub intensity: f32,
+}
+
+impl AtmosphereSky {
+    ///
+    /// Creates a new sky where the sun is placed in the given direction, ie. the direction from the observer towards the sun, and with the given turbidity.
+    /// The turbidity describes the amount of haze in the atmosphere and should be in the range `[2, 10]`, where 2 is a very clear sky.
+    ///
+    pub fn new(context: &Context, sun_direction: Vec3, turbidity: f32) -> Self {
+        Self {
+            context: context.clone(),
+            vertex_buffer: skybox_vertex_buffer(context),
+            sun_direction: sun_direction.normalize(),
+            turbidity: turbidity.clamp(2.0, 10.0),
+            intensity: 0.1,
+        }
+    }
+
+    ///
+    /// Returns the direction from the observer towards the sun.
+    ///
+    pub fn sun_direction(&self) -> Vec3 {
+        self.sun_direction
+    }
+
+    ///
+    /// Sets the direction from the observer towards the sun.
+    ///
+    pub fn set_sun_direction(&mut self, sun_direction: Vec3) {
+        self.sun_direction = sun_direction.normalize();
+    }
+
+    ///
+    /// Returns the turbidity, ie. the amount of haze in the atmosphere.
+    ///
+    pub fn turbidity(&self) -> f32 {
+        self.turbidity
+    }
+
+    ///
+    /// Sets the turbidity, ie. the amount of haze in the atmosphere. The value is clamped to the range `[2, 10]`.
+    ///
+    pub fn set_turbidity(&mut self, turbidity: f32) {
+        self.turbidity = turbidity.clamp(2.0, 10.0);
+    }
+
+    ///
+    /// Returns the color of the sun light when it reaches the ground, ie. the sun light after it is scattered in the atmosphere.
+    /// The color is more red when the sun is close to the horizon and when the turbidity is high.
+    ///
+    pub fn sun_color(&self) -> Srgba {
+        let t = self.sun_transmittance();
+        let max = t.x.max(t.y).max(t.z).max(f32::EPSILON);
+        let c = t / max;
+        Srgba::from([
+            (linear_to_srgb(c.x) * 255.0) as u8,
+            (linear_to_srgb(c.y) * 255.0) as u8,
+            (linear_to_srgb(c.z) * 255.0) as u8,
+            255,
+        ])
+    }
+
+    ///
+    /// Returns the intensity of the sun light when it reaches the ground, which is zero when the sun is below the horizon.
+    ///
+    pub fn sun_intensity(&self) -> f32 {
+        let t = self.sun_transmittance();
+        t.x.max(t.y).max(t.z)
+    }
+
+    ///
+    /// Returns a [DirectionalLight] which matches the sun in this sky.
+    ///
+    pub fn sun_light(&self) -> DirectionalLight {
+        DirectionalLight::new(
+            &self.context,
+            self.sun_intensity(),
+            self.sun_color(),
+            &-self.sun_direction,
+        )
+    }
+
+    fn sun_transmittance(&self) -> Vec3 {
+        if self.sun_direction.y <= 0.0 {
+            return vec3(0.0, 0.0, 0.0);
+        }
+        // Relative air mass (Kasten and Young) and an approximation of Rayleigh and Mie scattering for red, green and blue light.
+        let zenith = self.sun_direction.y.clamp(0.0, 1.0).acos().to_degrees();
+        let air_mass =
+            1.0 / (zenith.to_radians().cos() + 0.50572 * (96.07995 - zenith).powf(-1.6364));
+        let rayleigh = vec3(0.0119, 0.0280, 0.0678);
+        let mie = 0.0054 * (self.turbidity - 1.0);
+        vec3(
+            (-(rayleigh.x + mie) * air_mass).exp(),
+            (-(rayleigh.y + mie) * air_mass).exp(),
+            (-(rayleigh.z + mie) * air_mass).exp(),
+        )
+    }
+
+    fn material(&self) -> AtmosphereMaterial {
+        let t = self.turbidity;
+        let theta_s = self
+            .sun_direction
+            .y
+            .clamp(-1.0, 1.0)
+            .acos()
+            .min(0.5 * std::f32::consts::PI);
+        let chi = (4.0 / 9.0 - t / 120.0) * (std::f32::consts::PI - 2.0 * theta_s);
+        let zenith_luminance = (4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192;
+        let theta = vec4(theta_s.powi(3), theta_s.powi(2), theta_s, 1.0);
+        let zenith_x = t * t * theta.dot(vec4(0.00166, -0.00375, 0.00209, 0.0))
+            + t * theta.dot(vec4(-0.02903, 0.06377, -0.03202, 0.00394))
+            + theta.dot(vec4(0.11693, -0.21196, 0.06052, 0.25886));
+        let zenith_y = t * t * theta.dot(vec4(0.00275, -0.00610, 0.00317, 0.0))
+            + t * theta.dot(vec4(-0.04214, 0.08970, -0.04153, 0.00516))
+            + theta.dot(vec4(0.15346, -0.26756, 0.06670, 0.26688));
+        AtmosphereMaterial {
+            sun_direction: self.sun_direction,
+            zenith: vec3(zenith_luminance.max(0.0), zenith_x, zenith_y),
+            a: vec3(
+                0.1787 * t - 1.4630,
+                -0.0193 * t - 0.2592,
+                -0.0167 * t - 0.2608,
+            ),
+            b: vec3(
+                -0.3554 * t + 0.4275,
+                -0.0665 * t + 0.0008,
+                -0.0950 * t + 0.0092,
+            ),
+            c: vec3(
+                -0.0227 * t + 5.3251,
+                -0.0004 * t + 0.2125,
+                -0.0079 * t + 0.2102,
+            ),
+            d: vec3(
+                0.1206 * t - 2.5771,
+                -0.0641 * t - 0.8989,
+                -0.0441 * t - 1.6537,
+            ),
+            e: vec3(
+                -0.0670 * t + 0.3703,
+                -0.0033 * t + 0.0452,
+                -0.0109 * t + 0.0529,
+            ),
+            sun_color: self.sun_transmittance(),
+            intensity: self.intensity,
+        }
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+    .clamp(0.0, 1.0)
+}
+
+impl<'a> IntoIterator for &'a AtmosphereSky {
+    type Item = &'a dyn Object;
+    type IntoIter = std::iter::Once<&'a dyn Object>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self)
+    }
+}
+
+impl Geometry for AtmosphereSky {
+    fn draw(
+        &self,
+        camera: &Camera,
+        program: &Program,
+        render_states: RenderStates,
+        _attributes: FragmentAttributes,
+    ) {
+        program.use_uniform("view", camera.view());
+        program.use_uniform("projection", camera.projection());
+        program.use_vertex_attribute("position", &self.vertex_buffer);
+        program.draw_arrays(render_states, camera.viewport(), 36);
+    }
+
+    fn vertex_shader_source(&self, _required_attributes: FragmentAttributes) -> String {
+        include_str!("shaders/skybox.vert").to_owned()
+    }
+
+    fn id(&self, _required_attributes: FragmentAttributes) -> u16 {
+        0b1u16 << 15 | 0b1u16
+    }
+
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::INFINITE
+    }
+
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) {
+        render_with_material(&self.context, camera, &self, material, lights)
+    }
+
+    fn render_with_effect(
+        &self,
+        material: &dyn Effect,
+        camera: &Camera,
+        lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        depth_texture: Option<DepthTexture>,
+    ) {
+        render_with_effect(
+            &self.context,
+            camera,
+            self,
+            material,
+            lights,
+            color_texture,
+            depth_texture,
+        )
+    }
+}
+
+impl Object for AtmosphereSky {
+    fn render(&self, camera: &Camera, lights: &[&dyn Light]) {
+        render_with_material(&self.context, camera, self, self.material(), lights)
+    }
+
+    fn material_type(&self) -> MaterialType {
+        MaterialType::Opaque
+    }
+}
+
+struct AtmosphereMaterial {
+    sun_direction: Vec3,
+    zenith: Vec3,
+    a: Vec3,
+    b: Vec3,
+    c: Vec3,
+    d: Vec3,
+    e: Vec3,
+    sun_color: Vec3,
+    intensity: f32,
+}
+
+impl Material for AtmosphereMaterial {
+    fn id(&self) -> u16 {
+        0b1u16 << 15 | 0b1000u16
+    }
+
+    fn fragment_shader_source(&self, _lights: &[&dyn Light]) -> String {
+        format!(
+            "{}{}{}{}",
+            include_str!("../../core/shared.frag"),
+            ToneMapping::fragment_shader_source(),
+            ColorMapping::fragment_shader_source(),
+            include_str!("shaders/atmosphere_sky.frag")
+        )
+    }
+
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes::NONE
+    }
+
+    fn use_uniforms(&self, program: &Program, camera: &Camera, _lights: &[&dyn Light]) {
+        camera.tone_mapping.use_uniforms(program);
+        camera.color_mapping.use_uniforms(program);
+        program.use_uniform("sunDirection", self.sun_direction);
+        program.use_uniform("zenith", self.zenith);
+        program.use_uniform("perezA", self.a);
+        program.use_uniform("perezB", self.b);
+        program.use_uniform("perezC", self.c);
+        program.use_uniform("perezD", self.d);
+        program.use_uniform("perezE", self.e);
+        program.use_uniform("sunColor", self.sun_color);
+        program.use_uniform("intensity", self.intensity);
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            depth_test: DepthTest::LessOrEqual,
+            cull: Cull::Front,
+            ..Default::default()
+        }
+    }
+
+    fn material_type(&self) -> MaterialType {
+        MaterialType::Opaque
+    }
+}
@@ -0,0 +1,431 @@
+use crate::renderer::*;
+
+///
+/// Defines where the origin, ie. the (0, 0) pixel coordinate, of a [Canvas2D] is located.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CanvasOrigin {
+    /// The origin is at the top left corner of the viewport and the y-axis points down.
+    TopLeft,
+    /// The origin is at the bottom left corner of the viewport and the y-axis points up.
+    #[default]
+    BottomLeft,
+}
+
+const FRINGE_WIDTH: f32 = 1.0;
+
+///
+/// An immediate mode canvas for drawing 2D vector graphics, like polygons, circles and polylines, in screen space, for example for overlays or plots on top of a 3D scene.
+/// All coordinates are given in physical pixels relative to the [CanvasOrigin] of the viewport.
+/// The shapes are collected when calling the drawing methods and drawn when calling [Canvas2D::render], after which the canvas is empty again.
+/// The edges of the shapes are anti-aliased by fading out the alpha value over a fringe of one pixel.
+/// The GPU buffers are reused between renders, so keep the canvas around instead of creating a new one each frame.
+///
+pub struct Canvas2D {
+    context: Context,
+    origin: CanvasOrigin,
+    positions: Vec<Vec2>,
+    colors: Vec<Srgba>,
+    geometry: Option<CanvasGeometry>,
+}
+
+impl Canvas2D {
+    ///
+    /// Creates a new empty canvas with the given origin.
+    ///
+    pub fn new(context: &Context, origin: CanvasOrigin) -> Self {
+        Self {
+            context: context.clone(),
+            origin,
+            positions: Vec::new(),
+            colors: Vec::new(),
+            geometry: None,
+        }
+    }
+
+    ///
+    /// Returns the origin of this canvas.
+    ///
+    pub fn origin(&self) -> CanvasOrigin {
+        self.origin
+    }
+
+    ///
+    /// Sets the origin of this canvas. The origin is applied when rendering, so it also applies to the shapes that are already drawn.
+    ///
+    pub fn set_origin(&mut self, origin: CanvasOrigin) {
+        self.origin = origin;
+    }
+
+    ///
+    /// Returns whether or not there is anything to render.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    ///
+    /// Removes all shapes drawn since last render.
+    ///
+    pub fn clear(&mut self) {
+        self.positions.clear();
+        self.colors.clear();
+    }
+
+    ///
+    /// Fills the convex polygon defined by the given points with the given color.
+    ///
+    pub fn fill_polygon(&mut self, points: &[Vec2], color: Srgba) {
+        if points.len() < 3 {
+            return;
+        }
+        for i in 1..points.len() - 1 {
+            self.triangle([points[0], points[i], points[i + 1]], [color, color, color]);
+        }
+        let transparent = Srgba { a: 0, ..color };
+        let normals = vertex_normals(points);
+        for i in 0..points.len() {
+            let j = (i + 1) % points.len();
+            self.quad(
+                [
+                    points[i],
+                    points[j],
+                    points[j] + normals[j] * FRINGE_WIDTH,
+                    points[i] + normals[i] * FRINGE_WIDTH,
+                ],
+                [color, color, transparent, transparent],
+            );
+        }
+    }
+
+    ///
+    /// Fills the axis aligned rectangle with the given corners with the given color.
+    ///
+    pub fn fill_rectangle(&mut self, min: Vec2, max: Vec2, color: Srgba) {
+        self.fill_polygon(&[min, vec2(max.x, min.y), max, vec2(min.x, max.y)], color);
+    }
+
+    ///
+    /// Fills the circle with the given center and radius with the given color.
+    ///
+    pub fn fill_circle(&mut self, center: Vec2, radius: f32, color: Srgba) {
+        self.fill_polygon(&circle_points(center, radius), color);
+    }
+
+    ///
+    /// Draws lines with the given thickness and color between each consecutive pair of the given points.
+    /// The lines are joined with miter joints, so semi-transparent lines have the same color along the whole polyline.
+    ///
+    pub fn stroke_polyline(&mut self, points: &[Vec2], thickness: f32, color: Srgba) {
+        self.stroke(points, false, thickness, color);
+    }
+
+    ///
+    /// Draws the outline of the polygon defined by the given points with the given thickness and color.
+    ///
+    pub fn stroke_polygon(&mut self, points: &[Vec2], thickness: f32, color: Srgba) {
+        self.stroke(points, true, thickness, color);
+    }
+
+    ///
+    /// Draws the outline of the circle with the given center and radius with the given thickness and color.
+    ///
+    pub fn stroke_circle(&mut self, center: Vec2, radius: f32, thickness: f32, color: Srgba) {
+        self.stroke(&circle_points(center, radius), true, thickness, color);
+    }
+
+    ///
+    /// Renders all shapes drawn since last render into the given viewport and then clears the canvas.
+    /// Must be called in the callback given as input to a [RenderTarget] or [ColorTarget] write method.
+    ///
+    pub fn render(&mut self, viewport: Viewport) {
+        if self.is_empty() {
+            return;
+        }
+        let height = viewport.height as f32;
+        let origin = self.origin;
+        let positions = self
+            .positions
+            .iter()
+            .map(|p| match origin {
+                CanvasOrigin::BottomLeft => vec3(p.x, p.y, 0.0),
+                CanvasOrigin::TopLeft => vec3(p.x, height - p.y, 0.0),
+            })
+            .collect::<Vec<_>>();
+        let colors = self
+            .colors
+            .iter()
+            .map(|c| c.to_linear_srgb())
+            .collect::<Vec<_>>();
+        self.positions.clear();
+        self.colors.clear();
+        let context = &self.context;
+        let geometry = self.geometry.get_or_insert_with(|| CanvasGeometry {
+            context: context.clone(),
+            positions: VertexBuffer::new(context),
+            colors: VertexBuffer::new(context),
+        });
+        geometry.positions.fill(&positions);
+        geometry.colors.fill(&colors);
+        geometry.render_with_material(
+            &ColorMaterial {
+                render_states: RenderStates {
+                    write_mask: WriteMask::COLOR,
+                    depth_test: DepthTest::Always,
+                    blend: Blend::TRANSPARENCY,
+                    ..Default::default()
+                },
+                is_transparent: true,
+                ..Default::default()
+            },
+            &Camera::new_2d(viewport),
+            &[],
+        );
+    }
+
+    fn stroke(&mut self, points: &[Vec2], closed: bool, thickness: f32, color: Srgba) {
+        // Consecutive points at the same position do not define a direction.
+        let mut points = points.to_vec();
+        points.dedup_by(|a, b| (*a - *b).magnitude2() < f32::EPSILON);
+        if closed
+            && points.len() > 2
+            && (points[0] - points[points.len() - 1]).magnitude2() < f32::EPSILON
+        {
+            points.pop();
+        }
+        if points.len() < 2 {
+            return;
+        }
+        let normals = stroke_normals(&points, closed);
+        let transparent = Srgba { a: 0, ..color };
+        let half_width = (0.5 * thickness - 0.5 * FRINGE_WIDTH).max(0.0);
+        let edge_count = if closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        for i in 0..edge_count {
+            let j = (i + 1) % points.len();
+            let (p0, p1) = (points[i], points[j]);
+            let (inner0, inner1) = (normals[i] * half_width, normals[j] * half_width);
+            let (outer0, outer1) = (
+                inner0 + normals[i] * FRINGE_WIDTH,
+                inner1 + normals[j] * FRINGE_WIDTH,
+            );
+            self.quad(
+                [p0 - inner0, p1 - inner1, p1 + inner1, p0 + inner0],
+                [color, color, color, color],
+            );
+            self.quad(
+                [p0 + inner0, p1 + inner1, p1 + outer1, p0 + outer0],
+                [color, color, transparent, transparent],
+            );
+            self.quad(
+                [p0 - inner0, p1 - inner1, p1 - outer1, p0 - outer0],
+                [color, color, transparent, transparent],
+            );
+        }
+    }
+
+    fn quad(&mut self, points: [Vec2; 4], colors: [Srgba; 4]) {
+        self.triangle(
+            [points[0], points[1], points[2]],
+            [colors[0], colors[1], colors[2]],
+        );
+        self.triangle(
+            [points[0], points[2], points[3]],
+            [colors[0], colors[2], colors[3]],
+        );
+    }
+
+    fn triangle(&mut self, points: [Vec2; 3], colors: [Srgba; 3]) {
+        self.positions.extend_from_slice(&points);
+        self.colors.extend_from_slice(&colors);
+    }
+}
+
+fn circle_points(center: Vec2, radius: f32) -> Vec<Vec2> {
+    let count = ((radius.abs().sqrt() * 8.0) as usize).clamp(12, 128);
+    (0..count)
+        .map(|i| {
+            let angle = i as f32 * 2.0 * std::f32::consts::PI / count as f32;
+            center + radius * vec2(angle.cos(), angle.sin())
+        })
+        .collect()
+}
+
+///
+/// Returns the outward pointing normal at each vertex of a convex polygon, scaled such that the fringe has the same width along all edges.
+///
+fn vertex_normals(points: &[Vec2]) -> Vec<Vec2> {
+    let mut area = 0.0;
+    for i in 0..points.len() {
+        let (p0, p1) = (points[i], points[(i + 1) % points.len()]);
+        area += p0.x * p1.y - p1.x * p0.y;
+    }
+    let sign = if area >= 0.0 { 1.0 } else { -1.0 };
+    let edge_normal = |i: usize| {
+        let d = points[(i + 1) % points.len()] - points[i];
+        if d.magnitude2() < f32::EPSILON {
+            vec2(0.0, 0.0)
+        } else {
+            sign * vec2(d.y, -d.x).normalize()
+        }
+    };
+    (0..points.len())
+        .map(|i| {
+            miter_normal(
+                edge_normal((i + points.len() - 1) % points.len()),
+                edge_normal(i),
+            )
+        })
+        .collect()
+}
+
+///
+/// Returns the normal at each point of a stroke, where the normals at the joints are scaled such that the stroke has the same width along all segments
+/// and the segments on each side of a joint share the same vertices, ie. they do not overlap.
+///
+fn stroke_normals(points: &[Vec2], closed: bool) -> Vec<Vec2> {
+    let count = points.len();
+    let edge_normal = |i: usize| {
+        let d = points[(i + 1) % count] - points[i];
+        vec2(-d.y, d.x).normalize()
+    };
+    (0..count)
+        .map(|i| {
+            if !closed && i == 0 {
+                edge_normal(0)
+            } else if !closed && i == count - 1 {
+                edge_normal(count - 2)
+            } else {
+                miter_normal(edge_normal((i + count - 1) % count), edge_normal(i))
+            }
+        })
+        .collect()
+}
+
+///
+/// Returns the normal at a joint between two edges with the given normals, scaled such that the offset along both edge normals is one.
+/// The length is limited for sharp angles.
+///
+fn miter_normal(n0: Vec2, n1: Vec2) -> Vec2 {
+    let n = n0 + n1;
+    if n.magnitude2() < f32::EPSILON {
+        n1
+    } else {
+        let n = n.normalize();
+        n / n.dot(n1).max(0.25)
+    }
+}
+
+struct CanvasGeometry {
+    context: Context,
+    positions: VertexBuffer,
+    colors: VertexBuffer,
+}
+
+impl Geometry for CanvasGeometry {
+    fn draw(
+        &self,
+        camera: &Camera,
+        program: &Program,
+        render_states: RenderStates,
+        _attributes: FragmentAttributes,
+    ) {
+        program.use_uniform("viewProjection", camera.projection() * camera.view());
+        program.use_vertex_attribute("position", &self.positions);
+        program.use_vertex_attribute("color", &self.colors);
+        program.draw_arrays(
+            render_states,
+            camera.viewport(),
+            self.positions.vertex_count(),
+        );
+    }
+
+    fn vertex_shader_source(&self, _required_attributes: FragmentAttributes) -> String {
+        "
+        uniform mat4 viewProjection;
+        in vec3 position;
+        in vec4 color;
+        out vec4 col;
+        void main()
+        {
+            col = color;
+            gl_Position = viewProjection * vec4(position, 1.0);
+        }
+        "
+        .to_owned()
+    }
+
+    fn id(&self, _required_attributes: FragmentAttributes) -> u16 {
+        0b1u16 << 15 | 0b1001u16
+    }
+
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) {
+        render_with_material(&self.context, camera, self, material, lights)
+    }
+
+    fn render_with_effect(
+        &self,
+        effect: &dyn Effect,
+        camera: &Camera,
+        lights: &[&dyn Light],
+        color_texture: Option<ColorTexture>,
+        depth_texture: Option<DepthTexture>,
+    ) {
+        render_with_effect(
+            &self.context,
+            camera,
+            self,
+            effect,
+            lights,
+            color_texture,
+            depth_texture,
+        )
+    }
+
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::INFINITE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!((a - b).magnitude() < 1e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn stroke_normals_of_polyline() {
+        let normals = stroke_normals(&[vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(10.0, 10.0)], false);
+        assert_near(normals[0], vec2(0.0, 1.0));
+        // The joint is shared by both segments and offset by one along both segment normals
+        assert_near(normals[1], vec2(-1.0, 1.0));
+        assert_near(normals[2], vec2(-1.0, 0.0));
+    }
+
+    #[test]
+    fn stroke_normals_of_closed_polygon() {
+        let normals = stroke_normals(
+            &[
+                vec2(0.0, 0.0),
+                vec2(1.0, 0.0),
+                vec2(1.0, 1.0),
+                vec2(0.0, 1.0),
+            ],
+            true,
+        );
+        assert_near(normals[0], vec2(1.0, 1.0));
+        assert_near(normals[1], vec2(-1.0, 1.0));
+        assert_near(normals[2], vec2(-1.0, -1.0));
+        assert_near(normals[3], vec2(1.0, -1.0));
+    }
+}
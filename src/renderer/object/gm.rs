@@ -69,4 +69,8 @@ impl<G: Geometry, M: Material> Object for Gm<G, M> {
     fn material_type(&self) -> MaterialType {
         self.material.material_type()
     }
+
+    fn material(&self) -> Option<&dyn Material> {
+        Some(&self.material)
+    }
 }
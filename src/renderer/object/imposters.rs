@@ -2,7 +2,64 @@ use crate::core::*;
 use crate::renderer::*;
 use std::f32::consts::PI;
 
-const NO_VIEW_ANGLES: u32 = 8;
+///
+/// The number of view angles an [Imposters] object is rendered from.
+/// The views are evenly distributed around the vertical axis (azimuth) and, if the elevation is larger than one,
+/// in a grid of rows from the horizontal plane (zero elevation) up to straight above the objects.
+/// The number of layers in the imposter texture, and thereby the memory usage, is `azimuth * elevation`.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImposterAngles {
+    /// The number of view angles around the vertical axis.
+    pub azimuth: u32,
+    /// The number of view angles from the horizontal plane to straight above the objects. A value of one means that the objects are only seen from the side.
+    pub elevation: u32,
+}
+
+impl Default for ImposterAngles {
+    fn default() -> Self {
+        Self {
+            azimuth: 8,
+            elevation: 1,
+        }
+    }
+}
+
+impl From<u32> for ImposterAngles {
+    fn from(azimuth: u32) -> Self {
+        Self {
+            azimuth,
+            elevation: 1,
+        }
+    }
+}
+
+///
+/// Options for constructing [Imposters], see [Imposters::new_with_options].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImposterOptions {
+    /// The view angles the objects are rendered from.
+    /// More view angles reduce the popping when the camera moves around the imposters, but the memory usage of the imposter texture grows linearly with the number of view angles.
+    pub angles: ImposterAngles,
+    /// The interpolation used when sampling the imposter texture.
+    pub interpolation: Interpolation,
+    /// If specified, mip maps are generated for the imposter texture and sampled using the given filter, which makes imposters far away look smoother.
+    pub mip_map_filter: Option<Interpolation>,
+    /// The wrapping used when sampling the imposter texture.
+    pub wrapping: Wrapping,
+}
+
+impl Default for ImposterOptions {
+    fn default() -> Self {
+        Self {
+            angles: ImposterAngles::default(),
+            interpolation: Interpolation::Linear,
+            mip_map_filter: None,
+            wrapping: Wrapping::ClampToEdge,
+        }
+    }
+}
 
 ///
 /// A level-of-detail technique to replace rendering of high-poly meshes.
@@ -20,6 +77,7 @@ impl Imposters {
     ///
     /// Constructs a new [Imposters] and render the imposter texture from the given objects with the given lights.
     /// The imposters are placed at the given positions.
+    /// The [default](ImposterOptions::default) options are used, see [Imposters::new_with_options] to specify for example other view angles.
     ///
     pub fn new(
         context: &Context,
@@ -28,20 +86,77 @@ impl Imposters {
         lights: &[&dyn Light],
         max_texture_size: u32,
     ) -> Self {
+        Self::new_with_options(
+            context,
+            positions,
+            objects,
+            lights,
+            max_texture_size,
+            ImposterOptions::default(),
+        )
+    }
+
+    ///
+    /// Constructs a new [Imposters] and render the imposter texture from the given objects with the given lights.
+    /// The imposters are placed at the given positions.
+    /// The objects are rendered from the view angles given in the options and the imposter texture is sampled as specified in the options, see [ImposterOptions].
+    /// If the elevation of the view angles is larger than one, the imposters always face the camera, also when seen from above, otherwise they rotate only around the vertical axis.
+    ///
+    /// **Panics:** If texture arrays or instanced rendering are not supported by the graphics driver, see [Context::capabilities].
+    ///
+    pub fn new_with_options(
+        context: &Context,
+        positions: &[Vec3],
+        objects: impl IntoIterator<Item = impl Object> + Clone,
+        lights: &[&dyn Light],
+        max_texture_size: u32,
+        options: ImposterOptions,
+    ) -> Self {
+        let angles = options.angles;
+        assert!(
+            angles.azimuth > 0 && angles.elevation > 0,
+            "the number of view angles must be at least 1"
+        );
+        assert!(
+            context.capabilities().texture_arrays,
+            "imposters require texture arrays, which are not supported by the graphics driver"
+        );
+        assert!(
+            context.capabilities().instancing,
+            "imposters require instanced rendering, which is not supported by the graphics driver"
+        );
         let mut aabb = AxisAlignedBoundingBox::EMPTY;
         objects
             .clone()
             .into_iter()
             .for_each(|o| aabb.expand_with_aabb(&o.aabb()));
-        let mut sprites = Sprites::new(context, positions, Some(vec3(0.0, 1.0, 0.0)));
-        sprites.set_transformation(get_sprite_transform(aabb));
+        let mut sprites = Sprites::new(
+            context,
+            positions,
+            (angles.elevation == 1).then_some(vec3(0.0, 1.0, 0.0)),
+        );
+        sprites.set_transformation(get_sprite_transform(aabb, angles));
         Imposters {
             context: context.clone(),
             sprites,
-            material: ImpostersMaterial::new(context, aabb, objects, lights, max_texture_size),
+            material: ImpostersMaterial::new(
+                context,
+                aabb,
+                objects,
+                lights,
+                max_texture_size,
+                options,
+            ),
         }
     }
 
+    ///
+    /// Returns the view angles the objects are rendered from.
+    ///
+    pub fn angles(&self) -> ImposterAngles {
+        self.material.options.angles
+    }
+
     ///
     /// Set the positions of the imposters.
     ///
@@ -64,24 +179,36 @@ impl Imposters {
             .clone()
             .into_iter()
             .for_each(|o| aabb.expand_with_aabb(&o.aabb()));
-        self.sprites.set_transformation(get_sprite_transform(aabb));
+        self.sprites
+            .set_transformation(get_sprite_transform(aabb, self.material.options.angles));
         self.material
             .update(aabb, objects, lights, max_texture_size);
     }
 }
 
-fn get_sprite_transform(aabb: AxisAlignedBoundingBox) -> Mat4 {
+fn get_sprite_transform(aabb: AxisAlignedBoundingBox, angles: ImposterAngles) -> Mat4 {
     if aabb.is_empty() {
         Mat4::identity()
     } else {
         let (min, max) = (aabb.min(), aabb.max());
-        let width = f32::sqrt(f32::powi(max.x - min.x, 2) + f32::powi(max.z - min.z, 2));
-        let height = max.y - min.y;
+        let (width, height) = imposter_size(aabb, angles);
         let center = 0.5 * min + 0.5 * max;
         Mat4::from_translation(center) * Mat4::from_nonuniform_scale(0.5 * width, 0.5 * height, 0.0)
     }
 }
 
+fn imposter_size(aabb: AxisAlignedBoundingBox, angles: ImposterAngles) -> (f32, f32) {
+    let (min, max) = (aabb.min(), aabb.max());
+    if angles.elevation == 1 {
+        let width = f32::sqrt(f32::powi(max.x - min.x, 2) + f32::powi(max.z - min.z, 2));
+        (width, max.y - min.y)
+    } else {
+        // The objects can be seen from any direction, so the size is the diameter of the bounding sphere
+        let size = max.distance(min);
+        (size, size)
+    }
+}
+
 impl<'a> IntoIterator for &'a Imposters {
     type Item = &'a dyn Object;
     type IntoIter = std::iter::Once<&'a dyn Object>;
@@ -122,6 +249,7 @@ impl Object for Imposters {
 struct ImpostersMaterial {
     context: Context,
     texture: Texture2DArray,
+    options: ImposterOptions,
 }
 
 impl ImpostersMaterial {
@@ -131,14 +259,16 @@ impl ImpostersMaterial {
         objects: impl IntoIterator<Item = impl Object> + Clone,
         lights: &[&dyn Light],
         max_texture_size: u32,
+        options: ImposterOptions,
     ) -> Self {
         let mut m = Self {
             context: context.clone(),
+            options,
             texture: Texture2DArray::new_empty::<[u8; 4]>(
                 context,
                 1,
                 1,
-                NO_VIEW_ANGLES,
+                options.angles.azimuth * options.angles.elevation,
                 Interpolation::Nearest,
                 Interpolation::Nearest,
                 None,
@@ -157,9 +287,9 @@ impl ImpostersMaterial {
         max_texture_size: u32,
     ) {
         if !aabb.is_empty() {
+            let max_texture_size = max_texture_size.min(self.context.max_texture_size());
             let (min, max) = (aabb.min(), aabb.max());
-            let width = f32::sqrt(f32::powi(max.x - min.x, 2) + f32::powi(max.z - min.z, 2));
-            let height = max.y - min.y;
+            let (width, height) = imposter_size(aabb, self.options.angles);
             let texture_width = (max_texture_size as f32 * (width / height).min(1.0)) as u32;
             let texture_height = (max_texture_size as f32 * (height / width).min(1.0)) as u32;
             let viewport = Viewport::new_at_origo(texture_width, texture_height);
@@ -174,17 +304,33 @@ impl ImpostersMaterial {
                 4.0 * (width + height),
             );
             camera.disable_tone_and_color_mapping();
-            self.texture = Texture2DArray::new_empty::<[f16; 4]>(
-                &self.context,
-                texture_width,
-                texture_height,
-                NO_VIEW_ANGLES,
-                Interpolation::Linear,
-                Interpolation::Linear,
-                None,
-                Wrapping::ClampToEdge,
-                Wrapping::ClampToEdge,
-            );
+            // Fall back to an 8-bit texture if half precision float textures cannot be rendered to, for example on some WebGL2 contexts
+            let layers = self.options.angles.azimuth * self.options.angles.elevation;
+            self.texture = if self.context.capabilities().half_float_color_buffers {
+                Texture2DArray::new_empty::<[f16; 4]>(
+                    &self.context,
+                    texture_width,
+                    texture_height,
+                    layers,
+                    self.options.interpolation,
+                    self.options.interpolation,
+                    self.options.mip_map_filter,
+                    self.options.wrapping,
+                    self.options.wrapping,
+                )
+            } else {
+                Texture2DArray::new_empty::<[u8; 4]>(
+                    &self.context,
+                    texture_width,
+                    texture_height,
+                    layers,
+                    self.options.interpolation,
+                    self.options.interpolation,
+                    self.options.mip_map_filter,
+                    self.options.wrapping,
+                    self.options.wrapping,
+                )
+            };
             let mut depth_texture = DepthTexture2D::new::<f32>(
                 &self.context,
                 texture_width,
@@ -192,20 +338,33 @@ impl ImpostersMaterial {
                 Wrapping::ClampToEdge,
                 Wrapping::ClampToEdge,
             );
-            for i in 0..NO_VIEW_ANGLES {
-                let layers = [i];
-                let angle = i as f32 * 2.0 * PI / NO_VIEW_ANGLES as f32;
-                camera.set_view(
-                    center + width * vec3(f32::cos(angle), 0.0, f32::sin(angle)),
-                    center,
-                    vec3(0.0, 1.0, 0.0),
-                );
-                RenderTarget::new(
-                    self.texture.as_color_target(&layers, None),
-                    depth_texture.as_depth_target(),
-                )
-                .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
-                .render(&camera, objects.clone(), lights);
+            for j in 0..self.options.angles.elevation {
+                let elevation = if self.options.angles.elevation > 1 {
+                    j as f32 * 0.5 * PI / (self.options.angles.elevation - 1) as f32
+                } else {
+                    0.0
+                };
+                for i in 0..self.options.angles.azimuth {
+                    let layers = [j * self.options.angles.azimuth + i];
+                    let angle = i as f32 * 2.0 * PI / self.options.angles.azimuth as f32;
+                    let direction = vec3(
+                        f32::cos(elevation) * f32::cos(angle),
+                        f32::sin(elevation),
+                        f32::cos(elevation) * f32::sin(angle),
+                    );
+                    let up = vec3(
+                        -f32::sin(elevation) * f32::cos(angle),
+                        f32::cos(elevation),
+                        -f32::sin(elevation) * f32::sin(angle),
+                    );
+                    camera.set_view(center + width * direction, center, up);
+                    RenderTarget::new(
+                        self.texture.as_color_target(&layers, None),
+                        depth_texture.as_depth_target(),
+                    )
+                    .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
+                    .render(&camera, objects.clone(), lights);
+                }
             }
         }
     }
@@ -236,7 +395,8 @@ impl Material for ImpostersMaterial {
     fn use_uniforms(&self, program: &Program, camera: &Camera, _lights: &[&dyn Light]) {
         camera.tone_mapping.use_uniforms(program);
         camera.color_mapping.use_uniforms(program);
-        program.use_uniform("no_views", NO_VIEW_ANGLES as i32);
+        program.use_uniform("no_views", self.options.angles.azimuth as i32);
+        program.use_uniform("no_elevations", self.options.angles.elevation as i32);
         program.use_uniform("view", camera.view());
         program.use_texture_array("tex", &self.texture);
     }
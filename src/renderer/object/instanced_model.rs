@@ -90,6 +90,7 @@ impl<M: Material + FromCpuMaterial + Clone + Default> InstancedModel<M> {
     ///
     /// Constructs an [InstancedModel] from a [CpuModel] and the given [Instances] attributes, ie. constructs a list of [Gm]s with a [InstancedMesh] as geometry (constructed from the [CpuMesh]es in the [CpuModel]) and
     /// a [material] type specified by the generic parameter which implement [FromCpuMaterial] (constructed from the [CpuMaterial]s in the [CpuModel]).
+    /// If a geometry has a material with a normal texture but no tangents, the tangents are computed from the normals and uv coordinates.
     ///
     pub fn new(
         context: &Context,
@@ -117,8 +118,10 @@ impl<M: Material + FromCpuMaterial + Clone + Default> InstancedModel<M> {
                 } else {
                     M::default()
                 };
+                let geometry =
+                    super::model::with_tangents_if_needed(cpu_model, primitive, geometry);
                 let mut gm = Gm {
-                    geometry: InstancedMesh::new(context, instances, geometry),
+                    geometry: InstancedMesh::new(context, instances, &geometry),
                     material,
                 };
                 gm.set_transformation(primitive.transformation);
@@ -0,0 +1,94 @@
+use crate::renderer::*;
+
+///
+/// Places an [Object] in the layers given by the [Layered::layer] bitmask instead of the [DEFAULT_LAYER],
+/// which is used to filter which objects are rendered, see for example [RenderTarget::render_layers].
+/// Otherwise, the object is rendered as if it was not wrapped.
+///
+pub struct Layered<T: Object> {
+    /// The wrapped object.
+    pub object: T,
+    /// The layer bitmask of the object, see [Object::layer].
+    pub layer: u32,
+}
+
+impl<T: Object> Layered<T> {
+    ///
+    /// Places the given object in the layers given by the layer bitmask.
+    ///
+    pub fn new(object: T, layer: u32) -> Self {
+        Self { object, layer }
+    }
+
+    ///
+    /// Returns the layer bitmask of the object.
+    ///
+    pub fn layer(&self) -> u32 {
+        self.layer
+    }
+
+    ///
+    /// Sets the layer bitmask of the object.
+    ///
+    pub fn set_layer(&mut self, layer: u32) {
+        self.layer = layer;
+    }
+}
+
+impl<'a, T: Object> IntoIterator for &'a Layered<T> {
+    type Item = &'a dyn Object;
+    type IntoIter = std::iter::Once<&'a dyn Object>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self)
+    }
+}
+
+impl<T: Object + Clone> Clone for Layered<T> {
+    fn clone(&self) -> Self {
+        Self {
+            object: self.object.clone(),
+            layer: self.layer,
+        }
+    }
+}
+
+use std::ops::Deref;
+impl<T: Object> std::ops::Deref for Layered<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.object
+    }
+}
+
+impl<T: Object> std::ops::DerefMut for Layered<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.object
+    }
+}
+
+impl<T: Object> Geometry for Layered<T> {
+    impl_geometry_body!(deref);
+
+    fn animate(&mut self, time: f32) {
+        self.object.animate(time)
+    }
+}
+
+impl<T: Object> Object for Layered<T> {
+    fn render(&self, camera: &Camera, lights: &[&dyn Light]) {
+        self.object.render(camera, lights)
+    }
+
+    fn material_type(&self) -> MaterialType {
+        self.object.material_type()
+    }
+
+    fn layer(&self) -> u32 {
+        self.layer
+    }
+
+    fn material(&self) -> Option<&dyn Material> {
+        self.object.material()
+    }
+}
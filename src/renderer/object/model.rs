@@ -91,6 +91,7 @@ impl<M: Material + FromCpuMaterial + Clone + Default> Model<M> {
     ///
     /// Constructs a [Model] from a [CpuModel], ie. constructs a list of [Gm]s with a [Mesh] as geometry (constructed from the [CpuMesh]es in the [CpuModel]) and
     /// a [material] type specified by the generic parameter which implement [FromCpuMaterial] (constructed from the [CpuMaterial]s in the [CpuModel]).
+    /// If a geometry has a material with a normal texture but no tangents, the tangents are computed from the normals and uv coordinates.
     ///
     pub fn new(context: &Context, cpu_model: &CpuModel) -> Result<Self, RendererError> {
         let materials = cpu_model
@@ -114,8 +115,9 @@ impl<M: Material + FromCpuMaterial + Clone + Default> Model<M> {
                 } else {
                     M::default()
                 };
+                let geometry = with_tangents_if_needed(cpu_model, primitive, geometry);
                 let mut gm = Gm {
-                    geometry: Mesh::new(context, geometry),
+                    geometry: Mesh::new(context, &geometry),
                     material,
                 };
                 gm.set_transformation(primitive.transformation);
@@ -172,3 +174,92 @@ impl<M: Material> std::ops::DerefMut for Model<M> {
         &mut self.0
     }
 }
+
+///
+/// Returns the given geometry with computed tangents if the material of the primitive has a normal texture
+/// and the geometry has normals and uv coordinates but no tangents, otherwise the geometry itself.
+///
+pub(super) fn with_tangents_if_needed<'a>(
+    cpu_model: &CpuModel,
+    primitive: &three_d_asset::Primitive,
+    geometry: &'a CpuMesh,
+) -> std::borrow::Cow<'a, CpuMesh> {
+    let normal_mapped = primitive
+        .material_index
+        .and_then(|i| cpu_model.materials.get(i))
+        .map(|m| m.normal_texture.is_some())
+        .unwrap_or(false);
+    if normal_mapped
+        && geometry.tangents.is_none()
+        && geometry.normals.is_some()
+        && geometry.uvs.is_some()
+    {
+        let mut geometry = geometry.clone();
+        geometry.compute_tangents();
+        std::borrow::Cow::Owned(geometry)
+    } else {
+        std::borrow::Cow::Borrowed(geometry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad_model(normal_texture: Option<CpuTexture>) -> CpuModel {
+        let mut quad = CpuMesh::square();
+        quad.tangents = None;
+        // Normals that are not perpendicular to the uv directions
+        quad.normals = Some(vec![vec3(1.0, 0.0, 1.0).normalize(); 4]);
+        CpuModel {
+            name: String::new(),
+            geometries: vec![three_d_asset::Primitive {
+                name: String::new(),
+                transformation: Mat4::identity(),
+                animations: Vec::new(),
+                geometry: three_d_asset::Geometry::Triangles(quad),
+                material_index: Some(0),
+            }],
+            materials: vec![CpuMaterial {
+                normal_texture,
+                ..Default::default()
+            }],
+        }
+    }
+
+    fn mesh(cpu_model: &CpuModel) -> &CpuMesh {
+        match &cpu_model.geometries[0].geometry {
+            three_d_asset::Geometry::Triangles(mesh) => mesh,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn tangents_are_orthogonalized_against_the_normal() {
+        let cpu_model = quad_model(Some(CpuTexture::default()));
+        let geometry =
+            with_tangents_if_needed(&cpu_model, &cpu_model.geometries[0], mesh(&cpu_model));
+        let normals = geometry.normals.as_ref().unwrap();
+        let tangents = geometry.tangents.as_ref().unwrap();
+        assert_eq!(tangents.len(), 4);
+        for (normal, tangent) in normals.iter().zip(tangents) {
+            assert!(normal.dot(tangent.truncate()).abs() < 1e-5, "{tangent:?}");
+            assert!(
+                (tangent.truncate().magnitude() - 1.0).abs() < 1e-5,
+                "{tangent:?}"
+            );
+            assert_eq!(tangent.w.abs(), 1.0);
+            // Points in the direction of increasing u
+            assert!(tangent.x > 0.0, "{tangent:?}");
+        }
+    }
+
+    #[test]
+    fn tangents_are_only_computed_for_normal_mapped_materials() {
+        let cpu_model = quad_model(None);
+        let geometry =
+            with_tangents_if_needed(&cpu_model, &cpu_model.geometries[0], mesh(&cpu_model));
+        assert!(matches!(geometry, std::borrow::Cow::Borrowed(_)));
+        assert!(geometry.tangents.is_none());
+    }
+}
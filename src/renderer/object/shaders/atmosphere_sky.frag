@@ -0,0 +1,45 @@
+uniform vec3 sunDirection;
+uniform vec3 zenith;
+uniform vec3 perezA;
+uniform vec3 perezB;
+uniform vec3 perezC;
+uniform vec3 perezD;
+uniform vec3 perezE;
+uniform vec3 sunColor;
+uniform float intensity;
+
+in vec3 coords;
+
+layout (location = 0) out vec4 outColor;
+
+vec3 perez(float cos_theta, float gamma, float cos_gamma)
+{
+    return (1.0 + perezA * exp(perezB / cos_theta)) * (1.0 + perezC * exp(perezD * gamma) + perezE * cos_gamma * cos_gamma);
+}
+
+void main() {
+    vec3 direction = normalize(coords);
+    float cos_theta = max(direction.y, 0.01);
+    float cos_gamma = clamp(dot(direction, sunDirection), -1.0, 1.0);
+    float gamma = acos(cos_gamma);
+    float cos_theta_sun = max(sunDirection.y, 0.01);
+
+    vec3 Yxy = zenith * perez(cos_theta, gamma, cos_gamma) / perez(1.0, acos(cos_theta_sun), cos_theta_sun);
+
+    // Yxy to XYZ to linear sRGB
+    float Y = Yxy.x;
+    vec3 XYZ = vec3(Yxy.y * Y / Yxy.z, Y, (1.0 - Yxy.y - Yxy.z) * Y / Yxy.z);
+    vec3 color = mat3(3.2406, -0.9689, 0.0557, -1.5372, 1.8758, -0.2040, -0.4986, 0.0415, 1.0570) * XYZ;
+    color = max(color * intensity, vec3(0.0));
+
+    // The sun disc
+    if (cos_gamma > 0.99998) {
+        color += 20.0 * sunColor;
+    }
+
+    // Fade to dark below the horizon
+    color *= smoothstep(-0.1, 0.0, direction.y) * 0.9 + 0.1;
+
+    outColor = vec4(tone_mapping(color), 1.0);
+    outColor.rgb = color_mapping(outColor.rgb);
+}
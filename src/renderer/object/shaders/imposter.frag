@@ -2,21 +2,32 @@ uniform sampler2DArray tex;
 
 uniform mat4 view;
 uniform int no_views;
+uniform int no_elevations;
 in vec2 uvs;
 
 layout (location = 0) out vec4 outColor;
 
 void main()
 {
-    vec3 dir = normalize(vec3(view[0][2], 0.0, view[2][2]));
+    // When seen from straight above, the azimuth is undefined, so any direction can be used
+    vec3 dir = vec3(view[0][2], 0.0, view[2][2]);
+    dir = length(dir) > 0.0001 ? normalize(dir) : vec3(1.0, 0.0, 0.0);
     float a = acos(dir.x);
     float angle = (dir.z > 0.0 ? a : 2.0 * PI - a) / (2.0 * PI);
 
     float layer = float(no_views) * clamp(angle, 0.0, 0.999);
 
+    float row = 0.0;
+    if(no_elevations > 1) {
+        float elevation = asin(clamp(view[1][2], 0.0, 1.0));
+        row = clamp(floor(elevation / (0.5 * PI) * float(no_elevations - 1) + 0.5), 0.0, float(no_elevations - 1));
+    }
+
     float index0 = floor(layer);
     float index1 = float((int(index0) + 1) % no_views);
     float frac = layer - index0;
+    index0 += row * float(no_views);
+    index1 += row * float(no_views);
 
     vec4 color0 = texture(tex, vec3(uvs.x, uvs.y, index0));
     vec4 color1 = texture(tex, vec3(uvs.x, uvs.y, index1));
@@ -24,6 +35,8 @@ void main()
     if(outColor.a < 0.5) {
         discard;
     }
+    // The views are rendered on a transparent black background, so filtering blends the color towards black at the edges.
+    outColor.rgb /= outColor.a;
     outColor.rgb = tone_mapping(outColor.rgb);
     outColor.rgb = color_mapping(outColor.rgb);
 }
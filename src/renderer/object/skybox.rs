@@ -3,7 +3,10 @@ use crate::renderer::*;
 use std::sync::Arc;
 
 ///
-/// An illusion of a sky.
+/// An illusion of a sky, ie. a cube map texture rendered as the background of the scene.
+/// The skybox is rendered around the camera using only the rotation of the camera, so it appears infinitely far away,
+/// and at the maximum depth, so all other geometry is rendered in front of it independent of the render order.
+/// The cube map texture can also be used for image based lighting, see [Environment] and [AmbientLight::new_with_environment].
 ///
 pub struct Skybox {
     context: Context,
@@ -82,51 +85,9 @@ impl Skybox {
     /// The colors are assumed to be in linear sRGB (`RgbU8`), linear sRGB with an alpha channel (`RgbaU8`) or HDR color space.
     ///
     pub fn new_with_texture(context: &Context, texture: Arc<TextureCubeMap>) -> Self {
-        let vertex_buffer = VertexBuffer::new_with_data(
-            context,
-            &[
-                vec3(1.0, 1.0, -1.0),
-                vec3(-1.0, 1.0, -1.0),
-                vec3(1.0, 1.0, 1.0),
-                vec3(-1.0, 1.0, 1.0),
-                vec3(1.0, 1.0, 1.0),
-                vec3(-1.0, 1.0, -1.0),
-                vec3(-1.0, -1.0, -1.0),
-                vec3(1.0, -1.0, -1.0),
-                vec3(1.0, -1.0, 1.0),
-                vec3(1.0, -1.0, 1.0),
-                vec3(-1.0, -1.0, 1.0),
-                vec3(-1.0, -1.0, -1.0),
-                vec3(1.0, -1.0, -1.0),
-                vec3(-1.0, -1.0, -1.0),
-                vec3(1.0, 1.0, -1.0),
-                vec3(-1.0, 1.0, -1.0),
-                vec3(1.0, 1.0, -1.0),
-                vec3(-1.0, -1.0, -1.0),
-                vec3(-1.0, -1.0, 1.0),
-                vec3(1.0, -1.0, 1.0),
-                vec3(1.0, 1.0, 1.0),
-                vec3(1.0, 1.0, 1.0),
-                vec3(-1.0, 1.0, 1.0),
-                vec3(-1.0, -1.0, 1.0),
-                vec3(1.0, -1.0, -1.0),
-                vec3(1.0, 1.0, -1.0),
-                vec3(1.0, 1.0, 1.0),
-                vec3(1.0, 1.0, 1.0),
-                vec3(1.0, -1.0, 1.0),
-                vec3(1.0, -1.0, -1.0),
-                vec3(-1.0, 1.0, -1.0),
-                vec3(-1.0, -1.0, -1.0),
-                vec3(-1.0, 1.0, 1.0),
-                vec3(-1.0, -1.0, 1.0),
-                vec3(-1.0, 1.0, 1.0),
-                vec3(-1.0, -1.0, -1.0),
-            ],
-        );
-
         Skybox {
             context: context.clone(),
-            vertex_buffer,
+            vertex_buffer: skybox_vertex_buffer(context),
             material: SkyboxMaterial { texture },
         }
     }
@@ -137,6 +98,61 @@ impl Skybox {
     pub fn texture(&self) -> &Arc<TextureCubeMap> {
         &self.material.texture
     }
+
+    ///
+    /// Computes the maps needed for image based lighting from the cube map texture of this skybox, see [Environment::new].
+    /// The computation is expensive, so only call this once and reuse the result, for example in an [AmbientLight].
+    ///
+    pub fn environment(&self) -> Environment {
+        Environment::new(&self.context, &self.material.texture)
+    }
+}
+
+///
+/// Returns a vertex buffer with the positions of a unit cube, which is used to render a sky around the camera.
+///
+pub(super) fn skybox_vertex_buffer(context: &Context) -> VertexBuffer {
+    VertexBuffer::new_with_data(
+        context,
+        &[
+            vec3(1.0, 1.0, -1.0),
+            vec3(-1.0, 1.0, -1.0),
+            vec3(1.0, 1.0, 1.0),
+            vec3(-1.0, 1.0, 1.0),
+            vec3(1.0, 1.0, 1.0),
+            vec3(-1.0, 1.0, -1.0),
+            vec3(-1.0, -1.0, -1.0),
+            vec3(1.0, -1.0, -1.0),
+            vec3(1.0, -1.0, 1.0),
+            vec3(1.0, -1.0, 1.0),
+            vec3(-1.0, -1.0, 1.0),
+            vec3(-1.0, -1.0, -1.0),
+            vec3(1.0, -1.0, -1.0),
+            vec3(-1.0, -1.0, -1.0),
+            vec3(1.0, 1.0, -1.0),
+            vec3(-1.0, 1.0, -1.0),
+            vec3(1.0, 1.0, -1.0),
+            vec3(-1.0, -1.0, -1.0),
+            vec3(-1.0, -1.0, 1.0),
+            vec3(1.0, -1.0, 1.0),
+            vec3(1.0, 1.0, 1.0),
+            vec3(1.0, 1.0, 1.0),
+            vec3(-1.0, 1.0, 1.0),
+            vec3(-1.0, -1.0, 1.0),
+            vec3(1.0, -1.0, -1.0),
+            vec3(1.0, 1.0, -1.0),
+            vec3(1.0, 1.0, 1.0),
+            vec3(1.0, 1.0, 1.0),
+            vec3(1.0, -1.0, 1.0),
+            vec3(1.0, -1.0, -1.0),
+            vec3(-1.0, 1.0, -1.0),
+            vec3(-1.0, -1.0, -1.0),
+            vec3(-1.0, 1.0, 1.0),
+            vec3(-1.0, -1.0, 1.0),
+            vec3(-1.0, 1.0, 1.0),
+            vec3(-1.0, -1.0, -1.0),
+        ],
+    )
 }
 
 impl<'a> IntoIterator for &'a Skybox {
@@ -0,0 +1,132 @@
+use crate::renderer::*;
+
+///
+/// Renders the scene as seen from a single point in all directions into an equirectangular (360°) panorama texture.
+/// The scene is first rendered into the six sides of a cube map which is then projected into the equirectangular texture.
+/// The resulting texture can for example be read back and saved as a 360° screenshot.
+///
+#[derive(Clone, Debug)]
+pub struct PanoramaCapture {
+    /// The position from where the scene is captured.
+    pub position: Vec3,
+    /// The width of the resulting panorama texture. The height is half the width.
+    pub width: u32,
+    /// The distance to the near plane of the cameras used to capture the scene.
+    pub z_near: f32,
+    /// The distance to the far plane of the cameras used to capture the scene.
+    pub z_far: f32,
+}
+
+impl PanoramaCapture {
+    ///
+    /// Creates a new panorama capture at the given position which produces a panorama texture with the given width and half the width as height.
+    ///
+    pub fn new(position: Vec3, width: u32) -> Self {
+        Self {
+            position,
+            width,
+            z_near: 0.01,
+            z_far: 1000.0,
+        }
+    }
+
+    ///
+    /// The height of the resulting panorama texture.
+    ///
+    pub fn height(&self) -> u32 {
+        (self.width / 2).max(1)
+    }
+
+    ///
+    /// Renders the given objects with the given lights from the capture position and returns the equirectangular panorama texture.
+    /// The horizontal axis of the texture corresponds to the longitude, where the center is looking in the positive x direction,
+    /// and the vertical axis corresponds to the latitude, where the top is looking in the positive y direction.
+    /// The data format of the texture is specified by the generic parameter `T`.
+    ///
+    pub fn capture<T: TextureDataType>(
+        &self,
+        context: &Context,
+        objects: impl IntoIterator<Item = impl Object> + Clone,
+        lights: &[&dyn Light],
+    ) -> Texture2D {
+        let cube_size = (self.width / 4).max(1);
+        let mut cube_map = TextureCubeMap::new_empty::<T>(
+            context,
+            cube_size,
+            cube_size,
+            Interpolation::Linear,
+            Interpolation::Linear,
+            None,
+            Wrapping::ClampToEdge,
+            Wrapping::ClampToEdge,
+            Wrapping::ClampToEdge,
+        );
+        let mut depth_texture = DepthTexture2D::new::<f32>(
+            context,
+            cube_size,
+            cube_size,
+            Wrapping::ClampToEdge,
+            Wrapping::ClampToEdge,
+        );
+        let viewport = Viewport::new_at_origo(cube_size, cube_size);
+        for side in CubeMapSide::iter() {
+            let camera = Camera::new_perspective(
+                viewport,
+                self.position,
+                self.position + side.direction(),
+                side.up(),
+                degrees(90.0),
+                self.z_near,
+                self.z_far,
+            );
+            RenderTarget::new(
+                cube_map.as_color_target(&[side], None),
+                depth_texture.as_depth_target(),
+            )
+            .clear(ClearState::default())
+            .render(&camera, objects.clone(), lights);
+        }
+
+        let mut panorama = Texture2D::new_empty::<T>(
+            context,
+            self.width,
+            self.height(),
+            Interpolation::Linear,
+            Interpolation::Linear,
+            None,
+            Wrapping::Repeat,
+            Wrapping::ClampToEdge,
+        );
+        let fragment_shader_source = "
+            uniform samplerCube cubeMap;
+
+            in vec2 uvs;
+
+            layout (location = 0) out vec4 outColor;
+
+            void main()
+            {
+                float phi = (uvs.x - 0.5) * 6.2831853;
+                float theta = (uvs.y - 0.5) * 3.1415927;
+                vec3 dir = vec3(cos(theta) * cos(phi), sin(theta), cos(theta) * sin(phi));
+                outColor = texture(cubeMap, dir);
+            }";
+        let program = Program::from_source(
+            context,
+            full_screen_vertex_shader_source(),
+            fragment_shader_source,
+        )
+        .expect("Failed compiling shader");
+        let viewport = Viewport::new_at_origo(self.width, self.height());
+        panorama
+            .as_color_target(None)
+            .clear(ClearState::default())
+            .write::<RendererError>(|| {
+                program.use_texture_cube("cubeMap", &cube_map);
+                full_screen_draw(context, &program, RenderStates::default(), viewport);
+                Ok(())
+            })
+            .unwrap();
+        panorama
+    }
+}
@@ -0,0 +1,452 @@
+use crate::renderer::*;
+
+///
+/// A geometry together with the [CpuMesh] it was created from, used as input to [raycast].
+///
+#[derive(Clone, Copy)]
+pub struct RaycastTarget<'a> {
+    /// The geometry which is used for the bounding box test and returned in the [RaycastHit].
+    pub geometry: &'a dyn Geometry,
+    /// The CPU-side mesh used for the precise ray-triangle intersection tests.
+    pub cpu_mesh: &'a CpuMesh,
+    /// The transformation applied to the positions in the CPU mesh, should be the same as the transformation applied to the geometry.
+    pub transformation: Mat4,
+}
+
+impl<'a> RaycastTarget<'a> {
+    ///
+    /// Creates a new raycast target where the positions in the CPU mesh are transformed by the given transformation,
+    /// for example [Mesh::transformation] if the geometry is a [Mesh].
+    ///
+    pub fn new(geometry: &'a dyn Geometry, cpu_mesh: &'a CpuMesh, transformation: Mat4) -> Self {
+        Self {
+            geometry,
+            cpu_mesh,
+            transformation,
+        }
+    }
+}
+
+///
+/// The result of a [raycast].
+///
+#[derive(Clone, Copy)]
+pub struct RaycastHit<'a> {
+    /// The geometry that was hit.
+    pub geometry: &'a dyn Geometry,
+    /// The index of the target that was hit in the list of targets given to [raycast].
+    pub target_index: usize,
+    /// The index of the triangle that was hit.
+    pub triangle_index: usize,
+    /// The distance from the ray origin to the hit.
+    pub distance: f32,
+    /// The position of the hit in world space.
+    pub position: Vec3,
+    /// The barycentric coordinates of the hit position with respect to the three vertices of the triangle that was hit.
+    pub barycentric: Vec3,
+}
+
+///
+/// Finds the nearest intersection between the ray starting at the given origin in the given direction and the given targets.
+/// This is done on the CPU, so contrary to [ray_intersect] it does not require a read back from the GPU.
+/// First, the ray is tested against the bounding box of each geometry (see [Geometry::aabb]) and then, if the bounding box is hit,
+/// against each triangle in the corresponding [CpuMesh], which is done by transforming the ray into the local space of the mesh instead of transforming each position.
+/// Returns `None` if nothing is hit.
+///
+pub fn raycast<'a>(
+    origin: Vec3,
+    direction: Vec3,
+    targets: &[RaycastTarget<'a>],
+) -> Option<RaycastHit<'a>> {
+    let direction = direction.normalize();
+    let mut nearest: Option<RaycastHit> = None;
+    for (target_index, target) in targets.iter().enumerate() {
+        let aabb_distance = match ray_aabb_intersection(origin, direction, &target.geometry.aabb())
+        {
+            Some(d) => d,
+            None => continue,
+        };
+        if nearest.map(|h| h.distance < aabb_distance).unwrap_or(false) {
+            continue;
+        }
+        let inverse = match target.transformation.invert() {
+            Some(inverse) => inverse,
+            None => continue,
+        };
+        let local_origin = (inverse * origin.extend(1.0)).truncate();
+        let local_direction = (inverse * direction.extend(0.0)).truncate();
+        let positions = positions_f32(target.cpu_mesh);
+        let mut triangle_index = 0;
+        target.cpu_mesh.for_each_triangle(|i0, i1, i2| {
+            if let Some((distance, barycentric)) = ray_triangle_intersection(
+                local_origin,
+                local_direction,
+                [positions[i0], positions[i1], positions[i2]],
+            ) {
+                if nearest.map(|h| distance < h.distance).unwrap_or(true) {
+                    nearest = Some(RaycastHit {
+                        geometry: target.geometry,
+                        target_index,
+                        triangle_index,
+                        distance,
+                        position: origin + direction * distance,
+                        barycentric,
+                    });
+                }
+            }
+            triangle_index += 1;
+        });
+    }
+    nearest
+}
+
+///
+/// Returns the positions of the mesh as single precision floats without copying them if they already are.
+///
+fn positions_f32(cpu_mesh: &CpuMesh) -> std::borrow::Cow<'_, [Vec3]> {
+    match &cpu_mesh.positions {
+        Positions::F32(positions) => std::borrow::Cow::Borrowed(positions),
+        positions => std::borrow::Cow::Owned(positions.to_f32()),
+    }
+}
+
+///
+/// Returns the distance along the ray to the first intersection with the axis aligned bounding box, zero if the origin is inside the box,
+/// or `None` if the ray does not intersect the box (using the slab method).
+/// The distance is measured in multiples of the length of the given direction, so use a normalized direction to get the distance in world units.
+/// A ray parallel to the sides of the box only intersects it if the origin is between those sides, and a ray that only grazes an edge or corner of the box counts as a hit.
+/// Combined with [Geometry::aabb] and [Camera::ray_at_pixel], this can be used for fast, but coarse, picking.
+///
+pub fn ray_aabb_intersection(
+    origin: Vec3,
+    direction: Vec3,
+    aabb: &AxisAlignedBoundingBox,
+) -> Option<f32> {
+    if aabb.is_empty() {
+        return None;
+    }
+    if aabb.is_infinite() {
+        return Some(0.0);
+    }
+    let (min, max) = (aabb.min(), aabb.max());
+    let mut t_min = 0.0f32;
+    let mut t_max = f32::INFINITY;
+    for i in 0..3 {
+        if direction[i].abs() < f32::EPSILON {
+            if origin[i] < min[i] || origin[i] > max[i] {
+                return None;
+            }
+        } else {
+            let t0 = (min[i] - origin[i]) / direction[i];
+            let t1 = (max[i] - origin[i]) / direction[i];
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+            if t_min > t_max {
+                return None;
+            }
+        }
+    }
+    Some(t_min)
+}
+
+///
+/// Returns the distance along the ray to the nearest intersection with a triangle in the given mesh and the index of that triangle,
+/// or `None` if the ray does not intersect the mesh.
+/// The positions of the mesh are transformed by the given transformation, for example [Mesh::transformation],
+/// which is done by transforming the ray into the local space of the mesh instead of transforming each position.
+/// The distance is measured in multiples of the length of the given direction, so use a normalized direction to get the distance in world units.
+///
+/// All triangles are tested, so for many meshes it is faster to use [raycast] which first tests against the bounding boxes.
+///
+pub fn ray_mesh_intersection(
+    origin: Vec3,
+    direction: Vec3,
+    cpu_mesh: &CpuMesh,
+    transformation: Mat4,
+) -> Option<(f32, usize)> {
+    let inverse = transformation.invert()?;
+    let local_origin = (inverse * origin.extend(1.0)).truncate();
+    let local_direction = (inverse * direction.extend(0.0)).truncate();
+    let positions = positions_f32(cpu_mesh);
+    let mut nearest: Option<(f32, usize)> = None;
+    let mut triangle_index = 0;
+    cpu_mesh.for_each_triangle(|i0, i1, i2| {
+        if let Some((distance, _)) = ray_triangle_intersection(
+            local_origin,
+            local_direction,
+            [positions[i0], positions[i1], positions[i2]],
+        ) {
+            if nearest.map(|(d, _)| distance < d).unwrap_or(true) {
+                nearest = Some((distance, triangle_index));
+            }
+        }
+        triangle_index += 1;
+    });
+    nearest
+}
+
+///
+/// Returns the distance along the ray and the barycentric coordinates of the intersection with the triangle,
+/// or `None` if the ray does not intersect the triangle (using the Möller–Trumbore algorithm).
+/// The distance is measured in multiples of the length of the given direction.
+/// Triangles are hit from both sides, but not if the ray is parallel to the plane of the triangle or if the triangle is degenerate, ie. has no area.
+///
+pub fn ray_triangle_intersection(
+    origin: Vec3,
+    direction: Vec3,
+    triangle: [Vec3; 3],
+) -> Option<(f32, Vec3)> {
+    let edge1 = triangle[1] - triangle[0];
+    let edge2 = triangle[2] - triangle[0];
+    let p = direction.cross(edge2);
+    let determinant = edge1.dot(p);
+    // The determinant is relative to the size of the triangle and the length of the direction, so it is compared to a threshold of the same scale.
+    if determinant.abs()
+        <= f32::EPSILON * edge1.magnitude() * edge2.magnitude() * direction.magnitude()
+    {
+        return None;
+    }
+    let inverse_determinant = 1.0 / determinant;
+    let s = origin - triangle[0];
+    let u = s.dot(p) * inverse_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge1);
+    let v = direction.dot(q) * inverse_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = edge2.dot(q) * inverse_determinant;
+    if t < 0.0 {
+        return None;
+    }
+    Some((t, vec3(1.0 - u - v, u, v)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIANGLE: [Vec3; 3] = [
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ];
+
+    fn unit_box() -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::new_with_positions(&[vec3(-1.0, -1.0, -1.0), vec3(1.0, 1.0, 1.0)])
+    }
+
+    ///
+    /// A geometry which only has a bounding box, since that is all [raycast] needs.
+    ///
+    struct BoxGeometry(AxisAlignedBoundingBox);
+
+    impl Geometry for BoxGeometry {
+        fn draw(&self, _: &Camera, _: &Program, _: RenderStates, _: FragmentAttributes) {
+            unreachable!()
+        }
+        fn vertex_shader_source(&self, _: FragmentAttributes) -> String {
+            unreachable!()
+        }
+        fn id(&self, _: FragmentAttributes) -> u16 {
+            unreachable!()
+        }
+        fn render_with_material(&self, _: &dyn Material, _: &Camera, _: &[&dyn Light]) {
+            unreachable!()
+        }
+        fn render_with_effect(
+            &self,
+            _: &dyn Effect,
+            _: &Camera,
+            _: &[&dyn Light],
+            _: Option<ColorTexture>,
+            _: Option<DepthTexture>,
+        ) {
+            unreachable!()
+        }
+        fn aabb(&self) -> AxisAlignedBoundingBox {
+            self.0
+        }
+    }
+
+    #[test]
+    fn ray_triangle_hit() {
+        let (distance, barycentric) =
+            ray_triangle_intersection(vec3(0.25, 0.25, 1.0), vec3(0.0, 0.0, -1.0), TRIANGLE)
+                .unwrap();
+        assert!((distance - 1.0).abs() < 1.0e-6);
+        assert!((barycentric - vec3(0.5, 0.25, 0.25)).magnitude() < 1.0e-6);
+        // Hit from the back side
+        let (distance, _) =
+            ray_triangle_intersection(vec3(0.25, 0.25, -2.0), vec3(0.0, 0.0, 1.0), TRIANGLE)
+                .unwrap();
+        assert!((distance - 2.0).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn ray_triangle_miss() {
+        assert!(
+            ray_triangle_intersection(vec3(1.0, 1.0, 1.0), vec3(0.0, 0.0, -1.0), TRIANGLE)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn ray_triangle_parallel() {
+        assert!(
+            ray_triangle_intersection(vec3(-1.0, 0.25, 0.0), vec3(1.0, 0.0, 0.0), TRIANGLE)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn ray_triangle_behind_origin() {
+        assert!(
+            ray_triangle_intersection(vec3(0.25, 0.25, -1.0), vec3(0.0, 0.0, -1.0), TRIANGLE)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn ray_aabb_hit() {
+        let distance =
+            ray_aabb_intersection(vec3(0.5, 0.0, 5.0), vec3(0.0, 0.0, -1.0), &unit_box()).unwrap();
+        assert!((distance - 4.0).abs() < 1.0e-6);
+        let distance =
+            ray_aabb_intersection(vec3(5.0, 5.0, 5.0), vec3(-1.0, -1.0, -1.0), &unit_box())
+                .unwrap();
+        assert!((distance - 4.0).abs() < 1.0e-6);
+        assert_eq!(
+            ray_aabb_intersection(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), &unit_box()),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn ray_aabb_miss() {
+        assert!(
+            ray_aabb_intersection(vec3(3.0, 0.0, 5.0), vec3(0.0, -1.0, -1.0), &unit_box())
+                .is_none()
+        );
+        assert!(ray_aabb_intersection(
+            vec3(0.0, 0.0, 5.0),
+            vec3(0.0, 0.0, -1.0),
+            &AxisAlignedBoundingBox::EMPTY
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn ray_aabb_parallel() {
+        assert!(
+            ray_aabb_intersection(vec3(2.0, 0.0, 5.0), vec3(0.0, 0.0, -1.0), &unit_box()).is_none()
+        );
+    }
+
+    #[test]
+    fn ray_aabb_behind_origin() {
+        assert!(
+            ray_aabb_intersection(vec3(0.0, 0.0, 5.0), vec3(0.0, 0.0, 1.0), &unit_box()).is_none()
+        );
+    }
+
+    #[test]
+    fn raycast_returns_nearest() {
+        let cpu_mesh = CpuMesh::square();
+        let far_transformation = Mat4::from_translation(vec3(0.0, 0.0, -2.0));
+        let mut far_aabb = cpu_mesh.compute_aabb();
+        far_aabb.transform(&far_transformation);
+        let far = BoxGeometry(far_aabb);
+        let near = BoxGeometry(cpu_mesh.compute_aabb());
+        let targets = [
+            RaycastTarget::new(&far, &cpu_mesh, far_transformation),
+            RaycastTarget::new(&near, &cpu_mesh, Mat4::identity()),
+        ];
+        let hit = raycast(vec3(0.5, 0.5, 5.0), vec3(0.0, 0.0, -2.0), &targets).unwrap();
+        assert_eq!(hit.target_index, 1);
+        assert!((hit.distance - 5.0).abs() < 1.0e-5);
+        assert!((hit.position - vec3(0.5, 0.5, 0.0)).magnitude() < 1.0e-5);
+        assert!(raycast(vec3(3.0, 0.0, 5.0), vec3(0.0, 0.0, -1.0), &targets).is_none());
+        assert!(raycast(vec3(0.5, 0.5, 5.0), vec3(0.0, 0.0, 1.0), &targets).is_none());
+    }
+
+    #[test]
+    fn ray_aabb_grazing() {
+        // Along an edge of the box
+        let distance =
+            ray_aabb_intersection(vec3(1.0, 1.0, 5.0), vec3(0.0, 0.0, -1.0), &unit_box()).unwrap();
+        assert!((distance - 4.0).abs() < 1.0e-6);
+        // Passing a corner of the box
+        let distance =
+            ray_aabb_intersection(vec3(3.0, 1.0, 1.0), vec3(-1.0, 1.0, 0.0), &unit_box());
+        assert!(distance.is_none());
+        // Touching a corner of the box
+        let distance =
+            ray_aabb_intersection(vec3(3.0, -1.0, 1.0), vec3(-1.0, 1.0, 0.0), &unit_box()).unwrap();
+        assert!((distance - 2.0).abs() < 1.0e-6);
+    }
+
+    fn triangle(cpu_mesh: &CpuMesh, index: usize) -> [Vec3; 3] {
+        let positions = cpu_mesh.positions.to_f32();
+        let mut triangles = Vec::new();
+        cpu_mesh.for_each_triangle(|i0, i1, i2| {
+            triangles.push([positions[i0], positions[i1], positions[i2]])
+        });
+        triangles[index]
+    }
+
+    #[test]
+    fn ray_mesh_hits_cube_face() {
+        let cube = CpuMesh::cube();
+        let (distance, index) = ray_mesh_intersection(
+            vec3(0.1, 0.2, 5.0),
+            vec3(0.0, 0.0, -1.0),
+            &cube,
+            Mat4::identity(),
+        )
+        .unwrap();
+        assert!((distance - 4.0).abs() < 1.0e-5);
+        assert!(triangle(&cube, index).iter().all(|p| p.z == 1.0));
+
+        let (distance, index) = ray_mesh_intersection(
+            vec3(5.0, 0.1, 0.2),
+            vec3(-1.0, 0.0, 0.0),
+            &cube,
+            Mat4::identity(),
+        )
+        .unwrap();
+        assert!((distance - 4.0).abs() < 1.0e-5);
+        assert!(triangle(&cube, index).iter().all(|p| p.x == 1.0));
+    }
+
+    #[test]
+    fn ray_mesh_uses_transformation() {
+        let cube = CpuMesh::cube();
+        let transformation = Mat4::from_translation(vec3(0.0, 0.0, -3.0)) * Mat4::from_scale(2.0);
+        let (distance, index) = ray_mesh_intersection(
+            vec3(0.1, 0.2, 5.0),
+            vec3(0.0, 0.0, -1.0),
+            &cube,
+            transformation,
+        )
+        .unwrap();
+        assert!((distance - 6.0).abs() < 1.0e-5);
+        assert!(triangle(&cube, index).iter().all(|p| p.z == 1.0));
+        assert!(ray_mesh_intersection(
+            vec3(2.5, 0.0, 5.0),
+            vec3(0.0, 0.0, -1.0),
+            &cube,
+            transformation
+        )
+        .is_none());
+        assert!(ray_mesh_intersection(
+            vec3(1.5, 0.0, 5.0),
+            vec3(0.0, 0.0, -1.0),
+            &cube,
+            transformation
+        )
+        .is_some());
+    }
+}
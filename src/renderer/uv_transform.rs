@@ -0,0 +1,106 @@
+use crate::core::*;
+
+///
+/// Returns a uv transformation, which can be used as [Texture2DRef::transformation](crate::renderer::Texture2DRef::transformation)
+/// or as a texture transformation of an instance, that translates the uv coordinates by the given offset.
+/// Animating the offset, for example `offset = velocity * time`, makes the texture scroll across the geometry.
+///
+pub fn uv_translation(offset: Vec2) -> Mat3 {
+    Mat3::from_translation(offset)
+}
+
+///
+/// Returns a uv transformation that rotates the uv coordinates counterclockwise by the given angle around the origin of the uv coordinates.
+/// See [UvTransform] to rotate around another point, for example the center of the texture.
+///
+pub fn uv_rotation(angle: impl Into<Radians>) -> Mat3 {
+    Mat3::from_angle_z(angle)
+}
+
+///
+/// Returns a uv transformation that scales the uv coordinates by the given factors around the origin of the uv coordinates.
+/// Note that scaling the uv coordinates up makes the texture appear smaller, for example a scale of two repeats the texture twice
+/// if the texture uses [Wrapping::Repeat].
+///
+pub fn uv_scale(scale: Vec2) -> Mat3 {
+    Mat3::from_nonuniform_scale(scale.x, scale.y)
+}
+
+///
+/// A transformation of uv coordinates composed of a scale and a rotation around a pivot point followed by a translation.
+/// Use [UvTransform::matrix] to get the transformation as a [Mat3] which can be used as [Texture2DRef::transformation](crate::renderer::Texture2DRef::transformation)
+/// or as a texture transformation of an instance, for example to animate textures like scrolling water or a conveyor belt.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UvTransform {
+    /// The translation of the uv coordinates applied after the scale and rotation.
+    pub translation: Vec2,
+    /// The counterclockwise rotation of the uv coordinates around the pivot point.
+    pub rotation: Radians,
+    /// The scale of the uv coordinates around the pivot point.
+    pub scale: Vec2,
+    /// The point in uv coordinates which the rotation and scale is around, for example `(0.5, 0.5)` for the center of the texture.
+    pub pivot: Vec2,
+}
+
+impl Default for UvTransform {
+    fn default() -> Self {
+        Self {
+            translation: vec2(0.0, 0.0),
+            rotation: radians(0.0),
+            scale: vec2(1.0, 1.0),
+            pivot: vec2(0.0, 0.0),
+        }
+    }
+}
+
+impl UvTransform {
+    ///
+    /// Returns the transformation matrix, ie. first the scale, then the rotation, both around the pivot point, and finally the translation.
+    ///
+    pub fn matrix(&self) -> Mat3 {
+        uv_translation(self.translation + self.pivot)
+            * uv_rotation(self.rotation)
+            * uv_scale(self.scale)
+            * uv_translation(-self.pivot)
+    }
+}
+
+impl From<UvTransform> for Mat3 {
+    fn from(transform: UvTransform) -> Self {
+        transform.matrix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform_point(matrix: Mat3, uv: Vec2) -> Vec2 {
+        let p = matrix * uv.extend(1.0);
+        vec2(p.x, p.y)
+    }
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!((a - b).magnitude() < 1e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn default_is_identity() {
+        assert_eq!(UvTransform::default().matrix(), Mat3::identity());
+    }
+
+    #[test]
+    fn matrix_maps_corners() {
+        let matrix = UvTransform {
+            translation: vec2(0.1, 0.0),
+            rotation: degrees(90.0).into(),
+            scale: vec2(2.0, 2.0),
+            pivot: vec2(0.5, 0.5),
+        }
+        .matrix();
+        assert_near(transform_point(matrix, vec2(0.5, 0.5)), vec2(0.6, 0.5));
+        assert_near(transform_point(matrix, vec2(0.0, 0.0)), vec2(1.6, -0.5));
+        assert_near(transform_point(matrix, vec2(1.0, 1.0)), vec2(-0.4, 1.5));
+    }
+}
@@ -12,6 +12,10 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 #[allow(missing_docs)]
 pub enum HeadlessError {
+    #[error("no OpenGL driver is available for creating a headless context")]
+    NoGlDriver(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("headless rendering is not supported by the OpenGL driver: {0}")]
+    HeadlessNotSupported(String),
     #[error("glutin error")]
     GlutinCreationError(#[from] glutin_029::CreationError),
     #[error("glutin error")]
@@ -28,17 +32,39 @@ pub enum HeadlessError {
 #[derive(Clone)]
 pub struct HeadlessContext {
     context: Context,
+    width: u32,
+    height: u32,
     _glutin_context: Rc<glutin_029::Context<PossiblyCurrent>>,
 }
 
 impl HeadlessContext {
     ///
     /// Creates a new headless graphics context.
+    /// Since there is no default framebuffer of a useful size, render into a texture, for example using [ColorTarget](crate::ColorTarget), instead.
+    /// See [HeadlessContext::new_with_size] for specifying the size of the default framebuffer.
     ///
-    #[allow(unsafe_code)]
     pub fn new() -> Result<Self, HeadlessError> {
+        Self::new_with_size(1, 1)
+    }
+
+    ///
+    /// Creates a new headless graphics context where the default framebuffer has the given size, if supported by the driver.
+    /// Use [HeadlessContext::viewport] as the viewport when rendering off-screen.
+    ///
+    /// Returns [HeadlessError::NoGlDriver] if no OpenGL driver is available, for example on a CI machine without a GPU or software rasterizer,
+    /// and [HeadlessError::HeadlessNotSupported] if a driver is available but does not support headless contexts.
+    ///
+    #[allow(unsafe_code)]
+    pub fn new_with_size(width: u32, height: u32) -> Result<Self, HeadlessError> {
         let cb = ContextBuilder::new();
-        let glutin_context = build_context(cb)?;
+        let glutin_context = build_context(cb, PhysicalSize::new(width.max(1), height.max(1)))
+            .map_err(|e| match e {
+                CreationError::NoBackendAvailable(e) => HeadlessError::NoGlDriver(e),
+                CreationError::NotSupported(message) => {
+                    HeadlessError::HeadlessNotSupported(message)
+                }
+                e => HeadlessError::GlutinCreationError(e),
+            })?;
         let glutin_context = unsafe { glutin_context.make_current().map_err(|(_, e)| e)? };
         let context = Context::from_gl_context(std::sync::Arc::new(unsafe {
             crate::context::Context::from_loader_function(|s| {
@@ -47,11 +73,72 @@ impl HeadlessContext {
         }))?;
         Ok(Self {
             context,
+            width: width.max(1),
+            height: height.max(1),
             _glutin_context: Rc::new(glutin_context),
         })
     }
 }
 
+impl HeadlessContext {
+    ///
+    /// Returns a viewport covering the size requested when creating this context.
+    ///
+    pub fn viewport(&self) -> crate::Viewport {
+        crate::Viewport::new_at_origo(self.width, self.height)
+    }
+
+    ///
+    /// Renders the objects using the given camera and lights into a new color texture of the same size as the camera viewport,
+    /// which is first cleared with the given clear state, and reads back the result as a [CpuTexture](crate::CpuTexture) with RGBA colors,
+    /// for example to save it to disk or to compare it to a reference image in a test.
+    /// A depth texture is also created, so the objects are rendered with depth testing.
+    /// The rendering does not require a window, so this can be used for server-side rendering.
+    ///
+    pub fn render_to_image(
+        &self,
+        camera: &crate::Camera,
+        objects: impl IntoIterator<Item = impl crate::Object>,
+        lights: &[&dyn crate::Light],
+        clear_state: crate::ClearState,
+    ) -> crate::CpuTexture {
+        use crate::*;
+        let viewport = Viewport::new_at_origo(camera.viewport().width, camera.viewport().height);
+        let mut camera = camera.clone();
+        camera.set_viewport(viewport);
+        let mut texture = Texture2D::new_empty::<[u8; 4]>(
+            &self.context,
+            viewport.width,
+            viewport.height,
+            Interpolation::Nearest,
+            Interpolation::Nearest,
+            None,
+            Wrapping::ClampToEdge,
+            Wrapping::ClampToEdge,
+        );
+        let mut depth_texture = DepthTexture2D::new::<f32>(
+            &self.context,
+            viewport.width,
+            viewport.height,
+            Wrapping::ClampToEdge,
+            Wrapping::ClampToEdge,
+        );
+        let pixels = RenderTarget::new(
+            texture.as_color_target(None),
+            depth_texture.as_depth_target(),
+        )
+        .clear(clear_state)
+        .render(&camera, objects, lights)
+        .read_color();
+        CpuTexture {
+            data: TextureData::RgbaU8(pixels),
+            width: viewport.width,
+            height: viewport.height,
+            ..Default::default()
+        }
+    }
+}
+
 impl std::ops::Deref for HeadlessContext {
     type Target = Context;
     fn deref(&self) -> &Self::Target {
@@ -71,23 +158,24 @@ fn build_context_surfaceless<T1: ContextCurrentState>(
 fn build_context_headless<T1: ContextCurrentState>(
     cb: ContextBuilder<T1>,
     el: &EventLoop<()>,
+    size: PhysicalSize<u32>,
 ) -> Result<glutin_029::Context<NotCurrent>, CreationError> {
-    let size_one = PhysicalSize::new(1, 1);
-    cb.build_headless(&el, size_one)
+    cb.build_headless(el, size)
 }
 
 #[cfg(target_os = "linux")]
 fn build_context_osmesa<T1: ContextCurrentState>(
     cb: ContextBuilder<T1>,
+    size: PhysicalSize<u32>,
 ) -> Result<glutin_029::Context<NotCurrent>, CreationError> {
     use glutin_029::platform::unix::HeadlessContextExt;
-    let size_one = PhysicalSize::new(1, 1);
-    cb.build_osmesa(size_one)
+    cb.build_osmesa(size)
 }
 
 #[cfg(target_os = "linux")]
 fn build_context<T1: ContextCurrentState>(
     cb: ContextBuilder<T1>,
+    size: PhysicalSize<u32>,
 ) -> Result<glutin_029::Context<NotCurrent>, CreationError> {
     // On unix operating systems, you should always try for surfaceless first,
     // and if that does not work, headless (pbuffers), and if that too fails,
@@ -103,14 +191,14 @@ fn build_context<T1: ContextCurrentState>(
         Err(err) => err,
     };*/
 
-    let _err3 = match build_context_osmesa(cb.clone()) {
+    let _err3 = match build_context_osmesa(cb.clone(), size) {
         Ok(ctx) => return Ok(ctx),
         Err(err) => err,
     };
 
     let el = EventLoop::new();
 
-    let err2 = match build_context_headless(cb, &el) {
+    let err2 = match build_context_headless(cb, &el, size) {
         Ok(ctx) => return Ok(ctx),
         Err(err) => err,
     };
@@ -121,7 +209,9 @@ fn build_context<T1: ContextCurrentState>(
 #[cfg(not(target_os = "linux"))]
 fn build_context<T1: ContextCurrentState>(
     cb: ContextBuilder<T1>,
+    size: PhysicalSize<u32>,
 ) -> Result<glutin_029::Context<NotCurrent>, CreationError> {
+    // On macOS and Windows, a headless context is backed by a hidden window or pbuffer of the requested size.
     let el = EventLoop::new();
-    build_context_headless(cb.clone(), &el)
+    build_context_headless(cb, &el, size)
 }
@@ -18,6 +18,7 @@ pub struct FrameInputGenerator {
     first_frame: bool,
     events: Vec<Event>,
     accumulated_time: f64,
+    frames_per_second: f64,
     viewport: Viewport,
     window_width: u32,
     window_height: u32,
@@ -27,6 +28,7 @@ pub struct FrameInputGenerator {
     secondary_cursor_pos: Option<LogicalPoint>,
     secondary_finger_id: Option<u64>,
     modifiers: Modifiers,
+    is_mac: bool,
     mouse_pressed: Option<MouseButton>,
 }
 
@@ -40,6 +42,7 @@ impl FrameInputGenerator {
         Self {
             events: Vec::new(),
             accumulated_time: 0.0,
+            frames_per_second: 0.0,
             viewport: Viewport::new_at_origo(size.width, size.height),
             window_width,
             window_height,
@@ -51,6 +54,7 @@ impl FrameInputGenerator {
             secondary_cursor_pos: None,
             secondary_finger_id: None,
             modifiers: Modifiers::default(),
+            is_mac: is_mac(),
             mouse_pressed: None,
         }
     }
@@ -72,15 +76,25 @@ impl FrameInputGenerator {
             duration.as_secs() as f64 * 1000.0 + duration.subsec_nanos() as f64 * 1e-6;
         self.accumulated_time += elapsed_time;
         self.last_time = now;
+        if elapsed_time > 0.0 {
+            let frames_per_second = 1000.0 / elapsed_time;
+            self.frames_per_second = if self.first_frame || self.frames_per_second == 0.0 {
+                frames_per_second
+            } else {
+                0.9 * self.frames_per_second + 0.1 * frames_per_second
+            };
+        }
 
         let frame_input = FrameInput {
             events: self.events.drain(..).collect(),
             elapsed_time,
+            frames_per_second: self.frames_per_second,
             accumulated_time: self.accumulated_time,
             viewport: self.viewport,
             window_width: self.window_width,
             window_height: self.window_height,
             device_pixel_ratio: self.device_pixel_ratio as f32,
+            modifiers: self.modifiers,
             first_frame: self.first_frame,
             context: context.clone(),
         };
@@ -160,7 +174,7 @@ impl FrameInputGenerator {
                         || keycode == VirtualKeyCode::RControl
                     {
                         self.modifiers.ctrl = state;
-                        if !cfg!(target_os = "macos") {
+                        if !self.is_mac {
                             self.modifiers.command = state;
                         }
                         self.events.push(crate::Event::ModifiersChange {
@@ -177,23 +191,40 @@ impl FrameInputGenerator {
                         self.events.push(crate::Event::ModifiersChange {
                             modifiers: self.modifiers,
                         });
-                    } else if (keycode == VirtualKeyCode::LWin || keycode == VirtualKeyCode::RWin)
-                        && cfg!(target_os = "macos")
-                    {
-                        self.modifiers.command = state;
+                    } else if keycode == VirtualKeyCode::LWin || keycode == VirtualKeyCode::RWin {
+                        self.modifiers.meta = state;
+                        if self.is_mac {
+                            self.modifiers.command = state;
+                        }
                         self.events.push(crate::Event::ModifiersChange {
                             modifiers: self.modifiers,
                         });
                     }
                 }
             }
+            WindowEvent::ModifiersChanged(state) => {
+                // Also sent when the window gains focus, so modifiers pressed or released while the window did not have focus are not missed
+                let modifiers = Modifiers::new(
+                    state.alt(),
+                    state.ctrl(),
+                    state.shift(),
+                    state.logo(),
+                    self.is_mac,
+                );
+                if modifiers != self.modifiers {
+                    self.modifiers = modifiers;
+                    self.events
+                        .push(crate::Event::ModifiersChange { modifiers });
+                }
+            }
             WindowEvent::MouseWheel { delta, .. } => {
                 if let Some(position) = self.cursor_pos {
                     match delta {
                         winit::event::MouseScrollDelta::LineDelta(x, y) => {
-                            let line_height = 24.0; // TODO
+                            let line_height = 24.0;
                             self.events.push(crate::Event::MouseWheel {
                                 delta: (*x * line_height, *y * line_height),
+                                lines: Some((*x, *y)),
                                 position: position.into(),
                                 modifiers: self.modifiers,
                                 handled: false,
@@ -203,6 +234,7 @@ impl FrameInputGenerator {
                             let d = delta.to_logical(self.device_pixel_ratio);
                             self.events.push(crate::Event::MouseWheel {
                                 delta: (d.x, d.y),
+                                lines: None,
                                 position: position.into(),
                                 modifiers: self.modifiers,
                                 handled: false,
@@ -283,6 +315,17 @@ impl FrameInputGenerator {
                     device_pixel_ratio: self.device_pixel_ratio as f32,
                     height: self.viewport.height as f32,
                 };
+                self.events.push(crate::Event::Touch {
+                    id: touch.id,
+                    phase: match touch.phase {
+                        TouchPhase::Started => crate::TouchPhase::Start,
+                        TouchPhase::Moved => crate::TouchPhase::Move,
+                        TouchPhase::Ended => crate::TouchPhase::End,
+                        TouchPhase::Cancelled => crate::TouchPhase::Cancel,
+                    },
+                    position: position.into(),
+                    handled: false,
+                });
                 match touch.phase {
                     TouchPhase::Started => {
                         if self.finger_id.is_none() {
@@ -326,6 +369,7 @@ impl FrameInputGenerator {
                                     position: position.into(),
                                     modifiers: self.modifiers,
                                     handled: false,
+                                    lines: None,
                                     delta: (
                                         (position.x - p.x).abs() - (last_pos.x - p.x).abs(),
                                         (position.y - p.y).abs() - (last_pos.y - p.y).abs(),
@@ -352,6 +396,7 @@ impl FrameInputGenerator {
                                     position: p.into(),
                                     modifiers: self.modifiers,
                                     handled: false,
+                                    lines: None,
                                     delta: (
                                         (position.x - p.x).abs() - (last_pos.x - p.x).abs(),
                                         (position.y - p.y).abs() - (last_pos.y - p.y).abs(),
@@ -368,6 +413,21 @@ impl FrameInputGenerator {
     }
 }
 
+// Returns whether or not the application runs on Mac, including in a browser on Mac, where the Command key is used instead of the control key for shortcuts.
+fn is_mac() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()
+            .and_then(|w| w.navigator().platform().ok())
+            .map(|platform| platform.starts_with("Mac"))
+            .unwrap_or(false)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        cfg!(target_os = "macos")
+    }
+}
+
 fn is_printable_char(chr: char) -> bool {
     let is_in_private_use_area = ('\u{e000}'..='\u{f8ff}').contains(&chr)
         || ('\u{f0000}'..='\u{ffffd}').contains(&chr)
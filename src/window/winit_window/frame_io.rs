@@ -1,4 +1,4 @@
-use crate::control::Event;
+use crate::control::{Event, EventReplay, Modifiers};
 use crate::core::{Context, RenderTarget, Viewport};
 
 ///
@@ -16,8 +16,14 @@ pub struct FrameInput {
     pub events: Vec<Event>,
 
     /// Milliseconds since last frame.
+    /// Use this to make animations and simulations independent of the frame rate.
+    /// Note that the elapsed time can be large on the first frame or after the application has been paused, for example when the window has been minimized
+    /// or the browser tab has been in the background, so consider clamping it, for example `frame_input.elapsed_time.min(100.0)`, before using it for a simulation step.
     pub elapsed_time: f64,
 
+    /// An estimate of the number of frames per second, which is smoothed over the last frames to avoid jumping between values each frame.
+    pub frames_per_second: f64,
+
     /// Milliseconds accumulated time since start.
     pub accumulated_time: f64,
 
@@ -33,6 +39,10 @@ pub struct FrameInput {
     /// Number of physical pixels for each logical pixel.
     pub device_pixel_ratio: f32,
 
+    /// The state of the modifiers at the end of the frame, ie. after all of the [events](FrameInput::events).
+    /// The state of the modifiers when an event occurred is also available in the event itself.
+    pub modifiers: Modifiers,
+
     /// Whether or not this is the first frame. Note: also set after the window becomes (partially) visible.
     pub first_frame: bool,
 
@@ -52,6 +62,23 @@ impl FrameInput {
     pub fn screen(&self) -> RenderTarget {
         RenderTarget::screen(&self.context, self.viewport.width, self.viewport.height)
     }
+
+    ///
+    /// Replaces the events of this frame input with the events of the next frame in the given replay
+    /// and the elapsed and accumulated time and the frames per second with the fixed frame time of the replay.
+    /// Returns false, and leaves this frame input unchanged, if all frames have been replayed.
+    ///
+    pub fn replay(&mut self, replay: &mut EventReplay) -> bool {
+        if let Some(events) = replay.next() {
+            self.events = events;
+            self.elapsed_time = replay.frame_time();
+            self.frames_per_second = 1000.0 / self.elapsed_time;
+            self.accumulated_time = replay.accumulated_time();
+            true
+        } else {
+            false
+        }
+    }
 }
 
 ///
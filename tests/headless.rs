@@ -0,0 +1,1202 @@
+//!
+//! Tests which require a graphics context. Run them with `cargo test --features headless`.
+//! A headless context can only be created on the main thread, so these tests use a custom test harness which runs the tests one by one.
+//! The tests are skipped if no OpenGL driver is available, for example on a CI machine without a GPU or software rasterizer.
+//!
+
+use three_d::*;
+
+type Test = (&'static str, fn(&Context));
+
+fn main() {
+    // Creating the context panics instead of returning an error if no display is available.
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = std::panic::catch_unwind(HeadlessContext::new);
+    std::panic::set_hook(hook);
+    let context = match result {
+        Ok(Ok(context)) => context,
+        Ok(Err(HeadlessError::NoGlDriver(_)))
+        | Ok(Err(HeadlessError::HeadlessNotSupported(_)))
+        | Err(_) => {
+            println!("skipping headless tests since no OpenGL driver is available");
+            return;
+        }
+        Ok(Err(e)) => panic!("failed to create a headless context: {}", e),
+    };
+    let tests: &[Test] = &[
+        ("texture3d_upload_and_sample", texture3d_upload_and_sample),
+        ("blit_to_larger_target", blit_to_larger_target),
+        ("fill_keeps_explicit_mip_maps", fill_keeps_explicit_mip_maps),
+        ("cube_map_sides", cube_map_sides),
+        ("view_space_normals", view_space_normals),
+        (
+            "clear_with_default_clear_state",
+            clear_with_default_clear_state,
+        ),
+        ("read_color_async", read_color_async),
+        ("render_layers", render_layers),
+        ("compressed_bc1_upload", compressed_bc1_upload),
+        ("imposter_interpolation", imposter_interpolation),
+        ("radial_blur", radial_blur),
+        ("dynamic_buffer_updates", dynamic_buffer_updates),
+        ("panorama_capture_directions", panorama_capture_directions),
+        ("read_texture_array_layer", read_texture_array_layer),
+        ("set_wrapping_tiles", set_wrapping_tiles),
+        (
+            "atmosphere_sky_sun_brightness",
+            atmosphere_sky_sun_brightness,
+        ),
+        ("reuse_material_program", reuse_material_program),
+        ("point_light_attenuation", point_light_attenuation),
+        ("spot_light_cone", spot_light_cone),
+        (
+            "fill_subregion_updates_region",
+            fill_subregion_updates_region,
+        ),
+        ("ssao_darkens_corners", ssao_darkens_corners),
+        ("scissor_render_state", scissor_render_state),
+        ("vertex_color_modes", vertex_color_modes),
+        (
+            "uniform_buffer_partial_update",
+            uniform_buffer_partial_update,
+        ),
+    ];
+    for (name, test) in tests {
+        test(&context);
+        println!("test {} ... ok", name);
+    }
+}
+
+fn render_screen_material(
+    context: &Context,
+    material: &dyn Material,
+    width: u32,
+    height: u32,
+) -> Vec<[u8; 4]> {
+    let mut texture = Texture2D::new_empty::<[u8; 4]>(
+        context,
+        width,
+        height,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        None,
+        Wrapping::ClampToEdge,
+        Wrapping::ClampToEdge,
+    );
+    let camera = Camera::new_2d(Viewport::new_at_origo(width, height));
+    texture
+        .as_color_target(None)
+        .clear(ClearState::color(0.0, 0.0, 0.0, 0.0))
+        .apply_screen_material(material, &camera, &[])
+        .read()
+}
+
+///
+/// Writes the voxel with index `x + 2 * y + 4 * z` of a 2x2x2 3D texture to the pixel with that index in the first row.
+///
+struct SampleTexture3D<'a> {
+    texture: &'a Texture3D,
+}
+
+impl Material for SampleTexture3D<'_> {
+    fn fragment_shader_source(&self, _lights: &[&dyn Light]) -> String {
+        "uniform sampler3D tex;
+        layout (location = 0) out vec4 outColor;
+        void main() {
+            int i = int(gl_FragCoord.x);
+            vec3 voxel = vec3(float(i % 2), float((i / 2) % 2), float(i / 4));
+            outColor = texture(tex, (voxel + 0.5) / 2.0);
+        }"
+        .to_string()
+    }
+    fn id(&self) -> u16 {
+        1
+    }
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes::NONE
+    }
+    fn use_uniforms(&self, program: &Program, _camera: &Camera, _lights: &[&dyn Light]) {
+        program.use_texture_3d("tex", self.texture);
+    }
+    fn render_states(&self) -> RenderStates {
+        RenderStates::builder()
+            .write_mask(WriteMask::COLOR)
+            .depth_test(DepthTest::Always)
+            .build()
+    }
+    fn material_type(&self) -> MaterialType {
+        MaterialType::Opaque
+    }
+}
+
+fn texture3d_upload_and_sample(context: &Context) {
+    let voxels: Vec<[u8; 4]> = (0..8u8).map(|i| [i * 30, 255 - i * 30, i, 255]).collect();
+    let texture = Texture3D::new(
+        context,
+        &CpuTexture3D {
+            data: TextureData::RgbaU8(voxels.clone()),
+            width: 2,
+            height: 2,
+            depth: 2,
+            min_filter: Interpolation::Nearest,
+            mag_filter: Interpolation::Nearest,
+            mip_map_filter: None,
+            ..Default::default()
+        },
+    );
+    let pixels = render_screen_material(context, &SampleTexture3D { texture: &texture }, 8, 1);
+    assert_eq!(pixels, voxels);
+}
+
+fn blit_to_larger_target(context: &Context) {
+    let red = [255, 0, 0, 255];
+    let blue = [0, 0, 255, 255];
+    let mut source = TextureRenderTarget::<[u8; 4], f32>::new(context, 2, 1);
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 4, 2);
+    let source = source.as_render_target();
+    source.clear(ClearState::color_and_depth(1.0, 0.0, 0.0, 1.0, 1.0));
+    source.clear_partially(
+        ScissorBox {
+            x: 1,
+            y: 0,
+            width: 1,
+            height: 1,
+        },
+        ClearState::color(0.0, 0.0, 1.0, 1.0),
+    );
+    let target = target.as_render_target();
+    target.clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0));
+    source.blit_to(&target, Interpolation::Nearest, WriteMask::default());
+    let pixels = target.read_color::<[u8; 4]>();
+    assert_eq!(pixels, vec![red, red, blue, blue, red, red, blue, blue]);
+}
+
+fn fill_keeps_explicit_mip_maps(context: &Context) {
+    let red = [255u8, 0, 0, 255];
+    let green = [0u8, 255, 0, 255];
+    let blue = [0u8, 0, 255, 255];
+    let mut texture = Texture2D::new_with_mip_maps::<[u8; 4]>(
+        context,
+        &[&[red; 4], &[green]],
+        2,
+        2,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        Wrapping::ClampToEdge,
+        Wrapping::ClampToEdge,
+    );
+    texture.fill(&[blue; 4]);
+    texture.fill_subregion(0, 0, 1, 1, &[red]);
+    assert_eq!(
+        texture.as_color_target(Some(1)).read::<[u8; 4]>(),
+        vec![green]
+    );
+    texture.generate_mip_maps();
+    assert_ne!(
+        texture.as_color_target(Some(1)).read::<[u8; 4]>(),
+        vec![green]
+    );
+}
+
+///
+/// Writes the color in the direction of cube map side `i`, in the order right, left, top, bottom, front and back, to the pixel with index `i` in the first row.
+///
+struct SampleCubeMap<'a> {
+    texture: &'a TextureCubeMap,
+}
+
+impl Material for SampleCubeMap<'_> {
+    fn fragment_shader_source(&self, _lights: &[&dyn Light]) -> String {
+        "uniform samplerCube tex;
+        layout (location = 0) out vec4 outColor;
+        void main() {
+            int i = int(gl_FragCoord.x);
+            vec3 directions[6] = vec3[6](vec3(1.0, 0.0, 0.0), vec3(-1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0),
+                vec3(0.0, -1.0, 0.0), vec3(0.0, 0.0, 1.0), vec3(0.0, 0.0, -1.0));
+            outColor = texture(tex, directions[i]);
+        }"
+        .to_string()
+    }
+    fn id(&self) -> u16 {
+        2
+    }
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes::NONE
+    }
+    fn use_uniforms(&self, program: &Program, _camera: &Camera, _lights: &[&dyn Light]) {
+        program.use_texture_cube("tex", self.texture);
+    }
+    fn render_states(&self) -> RenderStates {
+        RenderStates::builder()
+            .write_mask(WriteMask::COLOR)
+            .depth_test(DepthTest::Always)
+            .build()
+    }
+    fn material_type(&self) -> MaterialType {
+        MaterialType::Opaque
+    }
+}
+
+fn cube_map_sides(context: &Context) {
+    let colors: Vec<[u8; 4]> = vec![
+        [255, 0, 0, 255],
+        [0, 255, 0, 255],
+        [0, 0, 255, 255],
+        [255, 255, 0, 255],
+        [0, 255, 255, 255],
+        [255, 0, 255, 255],
+    ];
+    let sides = colors
+        .iter()
+        .map(|color| CpuTexture {
+            data: TextureData::RgbaU8(vec![*color; 4]),
+            width: 2,
+            height: 2,
+            min_filter: Interpolation::Nearest,
+            mag_filter: Interpolation::Nearest,
+            mip_map_filter: None,
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    let texture = TextureCubeMap::new(
+        context, &sides[0], &sides[1], &sides[2], &sides[3], &sides[4], &sides[5],
+    );
+    let pixels = render_screen_material(context, &SampleCubeMap { texture: &texture }, 6, 1);
+    assert_eq!(pixels, colors);
+}
+
+fn view_space_normals(context: &Context) {
+    let camera = Camera::new_perspective(
+        Viewport::new_at_origo(15, 15),
+        vec3(3.0, 0.0, 3.0),
+        vec3(0.0, 0.0, 0.0),
+        vec3(0.0, 1.0, 0.0),
+        degrees(45.0),
+        0.1,
+        10.0,
+    );
+    let sphere = Gm::new(
+        Mesh::new(context, &CpuMesh::sphere(32)),
+        NormalMaterial {
+            view_space: true,
+            ..Default::default()
+        },
+    );
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 15, 15);
+    let pixels = target
+        .as_render_target()
+        .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
+        .render(&camera, &sphere, &[])
+        .read_color::<[u8; 4]>();
+    // The normal at the center of the sphere points towards the camera
+    let center = pixels[7 * 15 + 7];
+    let normal = vec3(center[0] as f32, center[1] as f32, center[2] as f32) / 255.0 * 2.0
+        - vec3(1.0, 1.0, 1.0);
+    assert!(
+        (normal - vec3(0.0, 0.0, 1.0)).magnitude() < 0.05,
+        "{:?}",
+        normal
+    );
+}
+
+fn clear_with_default_clear_state(context: &Context) {
+    let previous = context.default_clear_state();
+    context.set_default_clear_state(ClearState::color_and_depth(0.2, 0.4, 0.6, 1.0, 1.0));
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 2, 2);
+    let pixels = target
+        .as_render_target()
+        .clear_default()
+        .read_color::<[u8; 4]>();
+    context.set_default_clear_state(previous);
+    assert_eq!(pixels, vec![[51, 102, 153, 255]; 4]);
+}
+
+fn read_color_async(context: &Context) {
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 3, 2);
+    let target = target.as_render_target();
+    target
+        .clear(ClearState::color_and_depth(1.0, 0.0, 0.0, 1.0, 1.0))
+        .clear_partially(
+            ScissorBox {
+                x: 1,
+                y: 1,
+                width: 2,
+                height: 1,
+            },
+            ClearState::color(0.0, 1.0, 0.0, 1.0),
+        );
+    let expected = target.read_color::<[u8; 4]>();
+    let readback = target.read_color_async::<[u8; 4]>();
+    assert_eq!((readback.width(), readback.height()), (3, 2));
+    let start = std::time::Instant::now();
+    let pixels = loop {
+        if let Some(pixels) = readback.try_get() {
+            break pixels;
+        }
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(5),
+            "the pixels were never ready"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    };
+    assert!(readback.is_ready());
+    assert_eq!(pixels, expected);
+}
+
+fn render_layers(context: &Context) {
+    let viewport = Viewport::new_at_origo(2, 1);
+    let camera = Camera::new_2d(viewport);
+    let quad = |x: f32, color: Srgba, layer: u32| {
+        Layered::new(
+            Gm::new(
+                Rectangle::new(context, (x, 0.5), degrees(0.0), 1.0, 1.0),
+                ColorMaterial {
+                    color,
+                    ..Default::default()
+                },
+            ),
+            layer,
+        )
+    };
+    let objects = [quad(0.5, Srgba::RED, 0b01), quad(1.5, Srgba::BLUE, 0b10)];
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 2, 1);
+    let target = target.as_render_target();
+    let pixels = target
+        .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
+        .render_layers(&camera, &objects, &[], 0b10)
+        .read_color::<[u8; 4]>();
+    assert_eq!(pixels, vec![[0, 0, 0, 0], [0, 0, 255, 255]]);
+    let pixels = target
+        .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
+        .render_layers(&camera, &objects, &[], 0b11)
+        .read_color::<[u8; 4]>();
+    assert_eq!(pixels, vec![[255, 0, 0, 255], [0, 0, 255, 255]]);
+}
+
+///
+/// Writes the texel of a 2D texture at the position of each pixel.
+///
+struct SampleTexture2D<'a> {
+    texture: &'a Texture2D,
+}
+
+impl Material for SampleTexture2D<'_> {
+    fn fragment_shader_source(&self, _lights: &[&dyn Light]) -> String {
+        "uniform sampler2D tex;
+        layout (location = 0) out vec4 outColor;
+        void main() {
+            outColor = texelFetch(tex, ivec2(gl_FragCoord.xy), 0);
+        }"
+        .to_string()
+    }
+    fn id(&self) -> u16 {
+        3
+    }
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes::NONE
+    }
+    fn use_uniforms(&self, program: &Program, _camera: &Camera, _lights: &[&dyn Light]) {
+        program.use_texture("tex", self.texture);
+    }
+    fn render_states(&self) -> RenderStates {
+        RenderStates::builder()
+            .write_mask(WriteMask::COLOR)
+            .depth_test(DepthTest::Always)
+            .build()
+    }
+    fn material_type(&self) -> MaterialType {
+        MaterialType::Opaque
+    }
+}
+
+fn compressed_bc1_upload(context: &Context) {
+    if !CompressedFormat::Bc1.is_supported(context) {
+        println!("skipping compressed_bc1_upload since BC1 is not supported");
+        return;
+    }
+    // A single 4x4 block where all texels use the first endpoint color, which is pure red in RGB565
+    let block: [u8; 8] = [0x00, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    let texture = Texture2D::new_compressed(
+        context,
+        CompressedFormat::Bc1,
+        &[&block],
+        4,
+        4,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        Wrapping::ClampToEdge,
+        Wrapping::ClampToEdge,
+    )
+    .unwrap();
+    assert_eq!(texture.compressed_format(), Some(CompressedFormat::Bc1));
+    let pixels = render_screen_material(context, &SampleTexture2D { texture: &texture }, 4, 4);
+    assert_eq!(pixels, vec![[255, 0, 0, 255]; 16]);
+}
+
+fn imposter_interpolation(context: &Context) {
+    // A cube with a vertical gradient from black at the bottom to white at the top, which is visible from all sides
+    let mut cpu_mesh = CpuMesh::cube();
+    cpu_mesh.colors = Some(
+        cpu_mesh
+            .positions
+            .to_f32()
+            .iter()
+            .map(|p| {
+                let c = ((p.y + 1.0) * 127.5) as u8;
+                Srgba::new_opaque(c, c, c)
+            })
+            .collect(),
+    );
+    let cube = Gm::new(Mesh::new(context, &cpu_mesh), ColorMaterial::default());
+    let camera = Camera::new_orthographic(
+        Viewport::new_at_origo(64, 64),
+        vec3(0.0, 0.0, 10.0),
+        vec3(0.0, 0.0, 0.0),
+        vec3(0.0, 1.0, 0.0),
+        2.0,
+        0.1,
+        100.0,
+    );
+    let distinct_values_in_center_column = |interpolation: Interpolation| {
+        let imposters = Imposters::new_with_options(
+            context,
+            &[vec3(0.0, 0.0, 0.0)],
+            &cube,
+            &[],
+            8,
+            ImposterOptions {
+                angles: 1.into(),
+                interpolation,
+                ..Default::default()
+            },
+        );
+        let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 64, 64);
+        let pixels = target
+            .as_render_target()
+            .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
+            .render(&camera, &imposters, &[])
+            .read_color::<[u8; 4]>();
+        let mut values = (0..64)
+            .map(|y| pixels[y * 64 + 32])
+            .filter(|p| p[3] > 0)
+            .map(|p| p[0])
+            .collect::<Vec<_>>();
+        values.sort();
+        values.dedup();
+        values.len()
+    };
+    let nearest = distinct_values_in_center_column(Interpolation::Nearest);
+    let linear = distinct_values_in_center_column(Interpolation::Linear);
+    // The imposter texture is only 8 texels wide and 5 texels high, so nearest sampling gives a few steps while linear sampling gives a smooth gradient
+    assert!(nearest <= 8, "{} distinct values with nearest", nearest);
+    assert!(
+        linear > 2 * nearest,
+        "{} distinct values with linear and {} with nearest",
+        linear,
+        nearest
+    );
+}
+
+fn radial_blur(context: &Context) {
+    // A white stripe in the middle of a black row
+    let source = Texture2D::new(
+        context,
+        &CpuTexture {
+            data: TextureData::RgbaU8(
+                (0..64)
+                    .map(|x| {
+                        if (24..40).contains(&x) {
+                            [255, 255, 255, 255]
+                        } else {
+                            [0, 0, 0, 255]
+                        }
+                    })
+                    .collect(),
+            ),
+            width: 64,
+            height: 1,
+            min_filter: Interpolation::Nearest,
+            mag_filter: Interpolation::Nearest,
+            mip_map_filter: None,
+            ..Default::default()
+        },
+    );
+    let camera = Camera::new_2d(Viewport::new_at_origo(64, 1));
+    let blur = |strength: f32| {
+        let mut target = Texture2D::new_empty::<[u8; 4]>(
+            context,
+            64,
+            1,
+            Interpolation::Nearest,
+            Interpolation::Nearest,
+            None,
+            Wrapping::ClampToEdge,
+            Wrapping::ClampToEdge,
+        );
+        let pixels = target
+            .as_color_target(None)
+            .clear(ClearState::color(0.0, 0.0, 0.0, 0.0))
+            .apply_screen_effect(
+                &RadialBlurEffect {
+                    center: vec2(0.5, 0.5),
+                    strength,
+                    samples: 16,
+                },
+                &camera,
+                &[],
+                Some(ColorTexture::Single(&source)),
+                None,
+            )
+            .read::<[u8; 4]>();
+        pixels
+    };
+    let sharp = blur(0.0);
+    assert_eq!(sharp[4], [0, 0, 0, 255]);
+    assert_eq!(sharp[32], [255, 255, 255, 255]);
+    let blurred = blur(0.9);
+    // The center stays sharp
+    assert_eq!(blurred[32], [255, 255, 255, 255]);
+    // Pixels away from the center are smeared with the stripe between them and the center
+    for x in [4, 60] {
+        assert!(
+            blurred[x][0] > 10 && blurred[x][0] < 245,
+            "pixel {} is {:?}",
+            x,
+            blurred[x]
+        );
+    }
+}
+
+fn dynamic_buffer_updates(context: &Context) {
+    let program = Program::from_source(
+        context,
+        "uniform Data { vec4 tint; };
+        in vec3 position;
+        in vec4 color;
+        out vec4 col;
+        void main() {
+            col = color * tint;
+            gl_Position = vec4(position, 1.0);
+        }",
+        "in vec4 col;
+        layout (location = 0) out vec4 outColor;
+        void main() {
+            outColor = col;
+        }",
+    )
+    .unwrap();
+    // A triangle covering the whole viewport
+    let positions = VertexBuffer::new_with_data(
+        context,
+        &[
+            vec3(-1.0, -1.0, 0.0),
+            vec3(3.0, -1.0, 0.0),
+            vec3(-1.0, 3.0, 0.0),
+        ],
+    );
+    let mut colors = VertexBuffer::new(context);
+    let mut uniforms = UniformBuffer::new(context, &[4]);
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 2, 2);
+    let target = target.as_render_target();
+    // Refill the buffers every frame, which orphans the storage that the draw call in the previous frame reads from
+    for frame in 0..16u8 {
+        let value = frame as f32 / 15.0;
+        colors.fill(&[vec4(value, 1.0 - value, 1.0, 1.0); 3]);
+        uniforms.update(0, &[1.0, 1.0, 0.5, 1.0]);
+        let pixels = target
+            .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
+            .write::<CoreError>(|| {
+                program.use_vertex_attribute("position", &positions);
+                program.use_vertex_attribute("color", &colors);
+                program.use_uniform_block("Data", &uniforms);
+                program.draw_arrays(
+                    RenderStates::builder()
+                        .depth_test(DepthTest::Always)
+                        .build(),
+                    target.viewport(),
+                    3,
+                );
+                Ok(())
+            })
+            .unwrap()
+            .read_color::<[u8; 4]>();
+        let expected = [frame * 17, 255 - frame * 17, 128, 255];
+        for pixel in pixels {
+            for c in 0..4 {
+                assert!(
+                    (pixel[c] as i32 - expected[c] as i32).abs() <= 1,
+                    "frame {}: {:?} != {:?}",
+                    frame,
+                    pixel,
+                    expected
+                );
+            }
+        }
+    }
+}
+
+fn panorama_capture_directions(context: &Context) {
+    // A differently colored cube in each horizontal direction
+    let cube = |direction: Vec3, color: Srgba| {
+        let mut cube = Gm::new(
+            Mesh::new(context, &CpuMesh::cube()),
+            ColorMaterial {
+                color,
+                ..Default::default()
+            },
+        );
+        cube.set_transformation(Mat4::from_translation(direction * 5.0) * Mat4::from_scale(2.0));
+        cube
+    };
+    let objects = [
+        cube(vec3(1.0, 0.0, 0.0), Srgba::RED),
+        cube(vec3(0.0, 0.0, 1.0), Srgba::GREEN),
+        cube(vec3(-1.0, 0.0, 0.0), Srgba::BLUE),
+        cube(vec3(0.0, 0.0, -1.0), Srgba::WHITE),
+    ];
+    let mut panorama =
+        PanoramaCapture::new(vec3(0.0, 0.0, 0.0), 32).capture::<[u8; 4]>(context, &objects, &[]);
+    let pixels = panorama.as_color_target(None).read::<[u8; 4]>();
+    let equator = &pixels[8 * 32..9 * 32];
+    // The center is looking in the positive x direction and the longitude increases towards positive z
+    assert_eq!(equator[16], [255, 0, 0, 255]);
+    assert_eq!(equator[24], [0, 255, 0, 255]);
+    assert_eq!(equator[0], [0, 0, 255, 255]);
+    assert_eq!(equator[8], [255, 255, 255, 255]);
+}
+
+fn read_texture_array_layer(context: &Context) {
+    let mut texture = Texture2DArray::new_empty::<[u8; 4]>(
+        context,
+        2,
+        2,
+        3,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        None,
+        Wrapping::ClampToEdge,
+        Wrapping::ClampToEdge,
+    );
+    for layer in 0..3 {
+        texture
+            .as_color_target(&[layer], None)
+            .clear(ClearState::color(0.0, 0.0, 0.0, 1.0));
+    }
+    let camera = Camera::new_2d(Viewport::new_at_origo(2, 2));
+    let quad = Gm::new(
+        Rectangle::new(context, (1.0, 1.0), degrees(0.0), 2.0, 2.0),
+        ColorMaterial {
+            color: Srgba::RED,
+            ..Default::default()
+        },
+    );
+    texture
+        .as_color_target(&[2], None)
+        .render(&camera, &quad, &[]);
+    assert_eq!(texture.read_layer::<[u8; 4]>(2), vec![[255, 0, 0, 255]; 4]);
+    assert_eq!(texture.read_layer::<[u8; 4]>(0), vec![[0, 0, 0, 255]; 4]);
+    assert_eq!(texture.read_layer::<[u8; 4]>(1), vec![[0, 0, 0, 255]; 4]);
+}
+
+///
+/// Samples a 2D texture at the uv coordinates given by the pixel position divided by the texture size, which exceed one when the target is larger than the texture.
+///
+struct SampleTexture2DBeyondEdge<'a> {
+    texture: &'a Texture2D,
+}
+
+impl Material for SampleTexture2DBeyondEdge<'_> {
+    fn fragment_shader_source(&self, _lights: &[&dyn Light]) -> String {
+        "uniform sampler2D tex;
+        layout (location = 0) out vec4 outColor;
+        void main() {
+            outColor = texture(tex, gl_FragCoord.xy / vec2(textureSize(tex, 0)));
+        }"
+        .to_string()
+    }
+    fn id(&self) -> u16 {
+        4
+    }
+    fn fragment_attributes(&self) -> FragmentAttributes {
+        FragmentAttributes::NONE
+    }
+    fn use_uniforms(&self, program: &Program, _camera: &Camera, _lights: &[&dyn Light]) {
+        program.use_texture("tex", self.texture);
+    }
+    fn render_states(&self) -> RenderStates {
+        RenderStates::builder()
+            .write_mask(WriteMask::COLOR)
+            .depth_test(DepthTest::Always)
+            .build()
+    }
+    fn material_type(&self) -> MaterialType {
+        MaterialType::Opaque
+    }
+}
+
+fn set_wrapping_tiles(context: &Context) {
+    let mut texture = Texture2D::new(
+        context,
+        &CpuTexture {
+            data: TextureData::RgbaU8(vec![[255, 0, 0, 255], [0, 0, 255, 255]]),
+            width: 2,
+            height: 1,
+            min_filter: Interpolation::Nearest,
+            mag_filter: Interpolation::Nearest,
+            mip_map_filter: None,
+            wrap_s: Wrapping::ClampToEdge,
+            wrap_t: Wrapping::ClampToEdge,
+            ..Default::default()
+        },
+    );
+    let red = [255, 0, 0, 255];
+    let blue = [0, 0, 255, 255];
+    let pixels = render_screen_material(
+        context,
+        &SampleTexture2DBeyondEdge { texture: &texture },
+        4,
+        1,
+    );
+    assert_eq!(pixels, vec![red, blue, blue, blue]);
+    texture.set_wrapping(Wrapping::Repeat, Wrapping::ClampToEdge);
+    let pixels = render_screen_material(
+        context,
+        &SampleTexture2DBeyondEdge { texture: &texture },
+        4,
+        1,
+    );
+    assert_eq!(pixels, vec![red, blue, red, blue]);
+    texture.set_wrapping(Wrapping::MirroredRepeat, Wrapping::ClampToEdge);
+    let pixels = render_screen_material(
+        context,
+        &SampleTexture2DBeyondEdge { texture: &texture },
+        4,
+        1,
+    );
+    assert_eq!(pixels, vec![red, blue, blue, red]);
+}
+
+fn atmosphere_sky_sun_brightness(context: &Context) {
+    let brightness = |sky: &AtmosphereSky, direction: Vec3| {
+        let camera = Camera::new_perspective(
+            Viewport::new_at_origo(1, 1),
+            vec3(0.0, 0.0, 0.0),
+            direction,
+            vec3(0.0, 1.0, 0.0),
+            degrees(10.0),
+            0.1,
+            10.0,
+        );
+        let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 1, 1);
+        let pixel = target
+            .as_render_target()
+            .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 1.0, 1.0))
+            .render(&camera, sky, &[])
+            .read_color::<[u8; 4]>()[0];
+        pixel[0] as u32 + pixel[1] as u32 + pixel[2] as u32
+    };
+    let towards = vec3(0.0, 0.5, 1.0);
+    let away = vec3(0.0, 0.5, -1.0);
+    let mut sky = AtmosphereSky::new(context, towards, 3.0);
+    assert!(brightness(&sky, towards) > brightness(&sky, away));
+    // The sky is updated when the sun moves
+    sky.set_sun_direction(away);
+    assert!(brightness(&sky, away) > brightness(&sky, towards));
+}
+
+fn reuse_material_program(context: &Context) {
+    let camera = Camera::new_2d(Viewport::new_at_origo(2, 1));
+    let left = Rectangle::new(context, (0.5, 0.5), degrees(0.0), 1.0, 1.0);
+    let right = Rectangle::new(context, (1.5, 0.5), degrees(0.0), 1.0, 1.0);
+    let red = ColorMaterial {
+        color: Srgba::RED,
+        ..Default::default()
+    };
+    let blue = ColorMaterial {
+        color: Srgba::BLUE,
+        ..Default::default()
+    };
+    // Compiled once and used for both geometries and materials
+    let program = material_program(context, &left, &red, &[]);
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 2, 1);
+    let pixels = target
+        .as_render_target()
+        .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
+        .write::<RendererError>(|| {
+            render_with_program(&camera, &left, &red, &program, &[]);
+            render_with_program(&camera, &right, &blue, &program, &[]);
+            Ok(())
+        })
+        .unwrap()
+        .read_color::<[u8; 4]>();
+    assert_eq!(pixels, vec![[255, 0, 0, 255], [0, 0, 255, 255]]);
+}
+
+///
+/// Renders a white 4x4 floor in the xy plane, seen from above, lit by the given light into a 32x32 target.
+///
+fn render_lit_floor(context: &Context, light: &dyn Light) -> Vec<[u8; 4]> {
+    let mut floor = Gm::new(
+        Mesh::new(context, &CpuMesh::square()),
+        PhysicalMaterial::new_opaque(
+            context,
+            &CpuMaterial {
+                albedo: Srgba::WHITE,
+                metallic: 0.0,
+                roughness: 1.0,
+                ..Default::default()
+            },
+        ),
+    );
+    floor.set_transformation(Mat4::from_scale(2.0));
+    let camera = Camera::new_orthographic(
+        Viewport::new_at_origo(32, 32),
+        vec3(0.0, 0.0, 5.0),
+        vec3(0.0, 0.0, 0.0),
+        vec3(0.0, 1.0, 0.0),
+        4.0,
+        0.1,
+        10.0,
+    );
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 32, 32);
+    let pixels = target
+        .as_render_target()
+        .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 1.0, 1.0))
+        .render(&camera, &floor, &[light])
+        .read_color::<[u8; 4]>();
+    pixels
+}
+
+fn point_light_attenuation(context: &Context) {
+    // The light is placed just above the left edge of the floor
+    let light = PointLight::new(
+        context,
+        3.0,
+        Srgba::WHITE,
+        &vec3(-2.0, 0.0, 0.5),
+        Attenuation::from_range(10.0),
+    );
+    let pixels = render_lit_floor(context, &light);
+    let row = &pixels[16 * 32..17 * 32];
+    for x in (2..24).step_by(8) {
+        assert!(
+            row[x][0] > row[x + 8][0],
+            "pixel {} is {:?} and pixel {} is {:?}",
+            x,
+            row[x],
+            x + 8,
+            row[x + 8]
+        );
+    }
+}
+
+fn spot_light_cone(context: &Context) {
+    // Shines straight down on the center of the floor, where the cone has a radius of 2 * tan(30°) ≈ 1.15 units or about 9 pixels
+    let mut light = SpotLight::new(
+        context,
+        3.0,
+        Srgba::WHITE,
+        &vec3(0.0, 0.0, 2.0),
+        &vec3(0.0, 0.0, -1.0),
+        degrees(30.0),
+        Attenuation::default(),
+    );
+    light.inner_cutoff = Some(degrees(15.0).into());
+    let pixels = render_lit_floor(context, &light);
+    let pixel = |x: usize, y: usize| pixels[y * 32 + x][0];
+    assert!(pixel(16, 16) > 0);
+    // The spot is circular
+    for d in 0..16 {
+        for (x, y) in [(15 - d, 16), (16, 16 + d), (16, 15 - d)] {
+            assert!(
+                (pixel(16 + d, 16) as i32 - pixel(x, y) as i32).abs() <= 2,
+                "pixel ({}, {}) differs from pixel ({}, 16)",
+                x,
+                y,
+                16 + d
+            );
+        }
+    }
+    // The light falls off smoothly towards the edge of the cone and there is no light outside
+    assert!(pixel(16, 16) > pixel(23, 16));
+    assert!(pixel(23, 16) > 0);
+    assert_eq!(pixel(28, 16), 0);
+    assert_eq!(pixel(26, 26), 0);
+}
+
+fn fill_subregion_updates_region(context: &Context) {
+    let o = [0, 0, 0, 255];
+    let r = [255, 0, 0, 255];
+    let g = [0, 255, 0, 255];
+    let b = [0, 0, 255, 255];
+    let w = [255, 255, 255, 255];
+    let mut texture = Texture2D::new_empty::<[u8; 4]>(
+        context,
+        4,
+        3,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        None,
+        Wrapping::ClampToEdge,
+        Wrapping::ClampToEdge,
+    );
+    texture.fill(&[o; 12]);
+    texture.fill_subregion(1, 0, 2, 2, &[r, g, b, w]);
+    assert_eq!(
+        texture.as_color_target(None).read::<[u8; 4]>(),
+        vec![o, r, g, o, o, b, w, o, o, o, o, o]
+    );
+    // A region outside the texture is rejected
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        texture.fill_subregion(3, 0, 2, 1, &[r, g])
+    }));
+    assert!(result.is_err());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        texture.fill_subregion(u32::MAX, 0, 2, 1, &[r, g])
+    }));
+    assert!(result.is_err());
+}
+
+fn ssao_darkens_corners(context: &Context) {
+    // A floor and a wall meeting in a concave corner along the x axis
+    let quad = |transformation: Mat4| {
+        let mut quad = Gm::new(
+            Mesh::new(context, &CpuMesh::square()),
+            ColorMaterial::default(),
+        );
+        quad.set_transformation(transformation * Mat4::from_scale(2.0));
+        quad
+    };
+    let objects = [
+        quad(Mat4::from_translation(vec3(0.0, 0.0, 2.0)) * Mat4::from_angle_x(degrees(-90.0))),
+        quad(Mat4::from_translation(vec3(0.0, 2.0, 0.0))),
+    ];
+    let camera = Camera::new_perspective(
+        Viewport::new_at_origo(64, 64),
+        vec3(0.0, 3.0, 6.0),
+        vec3(0.0, 0.5, 1.0),
+        vec3(0.0, 1.0, 0.0),
+        degrees(60.0),
+        0.1,
+        20.0,
+    );
+    let mut depth_texture = DepthTexture2D::new::<f32>(
+        context,
+        64,
+        64,
+        Wrapping::ClampToEdge,
+        Wrapping::ClampToEdge,
+    );
+    depth_texture
+        .as_depth_target()
+        .clear(ClearState::depth(1.0))
+        .render(&camera, &objects, &[]);
+    let mut target = Texture2D::new_empty::<[u8; 4]>(
+        context,
+        64,
+        64,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        None,
+        Wrapping::ClampToEdge,
+        Wrapping::ClampToEdge,
+    );
+    let pixels = target
+        .as_color_target(None)
+        .clear(ClearState::color(0.0, 0.0, 0.0, 0.0))
+        .apply_screen_effect(
+            &SsaoEffect::default(),
+            &camera,
+            &[],
+            None,
+            Some(DepthTexture::Single(&depth_texture)),
+        )
+        .read::<[u8; 4]>();
+    // The average occlusion factor in a small neighbourhood around the pixel at the given position, to even out the noise
+    let occlusion = |position: Vec3| {
+        let pixel = camera.pixel_at_position(position);
+        let (x, y) = (pixel.x as i32, 63 - pixel.y as i32);
+        let mut sum = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                sum += pixels[((y + dy) * 64 + x + dx) as usize][0] as u32;
+            }
+        }
+        sum / 9
+    };
+    let corner = occlusion(vec3(0.0, 0.0, 0.05));
+    let floor = occlusion(vec3(0.0, 0.0, 3.0));
+    assert!(
+        corner + 25 < floor,
+        "the corner has the occlusion factor {} and the open floor {}",
+        corner,
+        floor
+    );
+}
+
+fn scissor_render_state(context: &Context) {
+    let camera = Camera::new_2d(Viewport::new_at_origo(4, 4));
+    let quad = |color: Srgba, scissor: Option<ScissorBox>| {
+        Gm::new(
+            Rectangle::new(context, (2.0, 2.0), degrees(0.0), 4.0, 4.0),
+            ColorMaterial {
+                color,
+                render_states: RenderStates {
+                    scissor,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+    };
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 4, 4);
+    let target = target.as_render_target();
+    // The scissor box of the render states is intersected with the scissor box of the render call
+    let pixels = target
+        .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
+        .render_partially(
+            ScissorBox {
+                x: 0,
+                y: 1,
+                width: 4,
+                height: 3,
+            },
+            &camera,
+            &quad(
+                Srgba::RED,
+                Some(ScissorBox {
+                    x: 1,
+                    y: 0,
+                    width: 2,
+                    height: 3,
+                }),
+            ),
+            &[],
+        )
+        .read_color::<[u8; 4]>();
+    for y in 0..4 {
+        for x in 0..4 {
+            let expected = if (1..3).contains(&x) && (1..3).contains(&y) {
+                [255, 0, 0, 255]
+            } else {
+                [0, 0, 0, 0]
+            };
+            // The rows are read from the top
+            assert_eq!(pixels[(3 - y) * 4 + x], expected, "pixel ({}, {})", x, y);
+        }
+    }
+    // The scissor test is disabled again for render calls without a scissor box
+    let pixels = target
+        .clear(ClearState::depth(1.0))
+        .render(&camera, &quad(Srgba::BLUE, None), &[])
+        .read_color::<[u8; 4]>();
+    assert_eq!(pixels, vec![[0, 0, 255, 255]; 16]);
+}
+
+fn vertex_color_modes(context: &Context) {
+    let mut cpu_mesh = CpuMesh::square();
+    cpu_mesh.colors = Some(vec![Srgba::RED; 4]);
+    let mesh = Mesh::new(context, &cpu_mesh);
+    let camera = Camera::new_orthographic(
+        Viewport::new_at_origo(2, 2),
+        vec3(0.0, 0.0, 5.0),
+        vec3(0.0, 0.0, 0.0),
+        vec3(0.0, 1.0, 0.0),
+        2.0,
+        0.1,
+        10.0,
+    );
+    let light = AmbientLight::new(context, 1.0, Srgba::WHITE);
+    let render = |albedo: Srgba, vertex_color_mode: VertexColorMode| {
+        let material = PhysicalMaterial {
+            albedo,
+            vertex_color_mode,
+            ..Default::default()
+        };
+        let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 2, 2);
+        let pixels = target
+            .as_render_target()
+            .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 1.0, 1.0))
+            .render_with_material(&material, &camera, &mesh, &[&light])
+            .read_color::<[u8; 4]>();
+        pixels
+    };
+    let is_red = |pixels: &[[u8; 4]]| {
+        pixels
+            .iter()
+            .all(|p| p[0] > 0 && p[1] == 0 && p[2] == 0 && p[3] == 255)
+    };
+    // Multiplied with a white albedo gives red
+    let pixels = render(Srgba::WHITE, VertexColorMode::Multiply);
+    assert!(is_red(&pixels), "{:?}", pixels);
+    // Multiplied with a green albedo gives black
+    let pixels = render(Srgba::GREEN, VertexColorMode::Multiply);
+    assert_eq!(pixels, vec![[0, 0, 0, 255]; 4]);
+    // The albedo is ignored when replaced by the vertex color
+    let pixels = render(Srgba::GREEN, VertexColorMode::Replace);
+    assert!(is_red(&pixels), "{:?}", pixels);
+}
+
+fn uniform_buffer_partial_update(context: &Context) {
+    let program = Program::from_source(
+        context,
+        "in vec3 position;
+        void main() {
+            gl_Position = vec4(position, 1.0);
+        }",
+        "uniform Data { vec4 a; vec4 b; vec4 c; };
+        layout (location = 0) out vec4 outColor;
+        void main() {
+            int i = int(gl_FragCoord.x);
+            outColor = i == 0 ? a : (i == 1 ? b : c);
+        }",
+    )
+    .unwrap();
+    let positions = VertexBuffer::new_with_data(
+        context,
+        &[
+            vec3(-1.0, -1.0, 0.0),
+            vec3(3.0, -1.0, 0.0),
+            vec3(-1.0, 3.0, 0.0),
+        ],
+    );
+    let mut uniforms = UniformBuffer::new_with_usage(context, &[4, 4, 4], BufferUsage::Stream);
+    uniforms.update(0, &[1.0, 0.0, 0.0, 1.0]);
+    uniforms.update(1, &[0.0, 1.0, 0.0, 1.0]);
+    uniforms.update(2, &[0.0, 0.0, 1.0, 1.0]);
+    let mut target = TextureRenderTarget::<[u8; 4], f32>::new(context, 3, 1);
+    let target = target.as_render_target();
+    let draw = |uniforms: &UniformBuffer| {
+        target
+            .clear(ClearState::color_and_depth(0.0, 0.0, 0.0, 0.0, 1.0))
+            .write::<CoreError>(|| {
+                program.use_vertex_attribute("position", &positions);
+                program.use_uniform_block("Data", uniforms);
+                program.draw_arrays(
+                    RenderStates::builder()
+                        .depth_test(DepthTest::Always)
+                        .build(),
+                    target.viewport(),
+                    3,
+                );
+                Ok(())
+            })
+            .unwrap()
+            .read_color::<[u8; 4]>()
+    };
+    assert_eq!(
+        draw(&uniforms),
+        vec![[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]]
+    );
+    // Only the updated variable changes
+    uniforms.update(1, &[1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(uniforms.get(0), Some(&[1.0, 0.0, 0.0, 1.0][..]));
+    assert_eq!(uniforms.get(1), Some(&[1.0, 1.0, 1.0, 1.0][..]));
+    assert_eq!(
+        draw(&uniforms),
+        vec![[255, 0, 0, 255], [255, 255, 255, 255], [0, 0, 255, 255]]
+    );
+}